@@ -0,0 +1,620 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use simdnoise::export::{save_image, save_image_stack, save_npy, save_raw, ImageFormat};
+use simdnoise::{
+    Cell2ReturnType, CellDistanceFunction, CellReturnType, NoiseBuilder, NoiseNode, NoiseType,
+    Settings, SimplexSettings,
+};
+
+const WIDTH: usize = 512;
+const HEIGHT: usize = 512;
+const DEPTH: usize = 1;
+const TIME: usize = 1;
+const DEFAULT_FREQUENCY: f32 = 1.2;
+const DEFAULT_JITTER: f32 = 1.2;
+const DEFAULT_LACUNARITY: f32 = 0.5;
+const DEFAULT_GAIN: f32 = 2.0;
+const DEFAULT_OCTAVES: u8 = 3;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[clap(short, long, value_parser, default_value_t = WIDTH, help="The width of the generated noise", global=true)]
+    pub width: usize,
+
+    #[clap(long, value_parser, default_value_t = HEIGHT, help="The height of the generated noise", global=true)]
+    pub height: usize,
+
+    #[clap(long, value_parser, default_value_t = DEPTH, help="The z dimension of the generated noise", global=true)]
+    pub depth: usize,
+
+    #[clap(long, value_parser, default_value_t = TIME, help="The w dimension of the generated noise", global=true)]
+    pub time: usize,
+
+    #[clap(long, value_parser, default_value_t = Dimension::Two, help="The number of dimensions of the generated noise", global=true)]
+    pub dimension: Dimension,
+
+    #[clap(
+        long,
+        value_parser,
+        default_value_t = 8,
+        help = "The initial seed value",
+        global = true
+    )]
+    pub seed: i32,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "Use an offset for the first dimension",
+        global = true
+    )]
+    pub offset_x: Option<f32>,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "Use an offset for the second dimension",
+        global = true
+    )]
+    pub offset_y: Option<f32>,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "Use an offset for the third dimension",
+        global = true
+    )]
+    pub offset_z: Option<f32>,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "Use an offset for the fourth dimension",
+        global = true
+    )]
+    pub offset_w: Option<f32>,
+
+    #[clap(
+        long,
+        value_parser,
+        default_value_t = 0.0,
+        help = "The value the lowest noise is scaled to",
+        global = true
+    )]
+    pub scale_min: f32,
+
+    #[clap(
+        long,
+        value_parser,
+        default_value_t = 1.0,
+        help = "The value the highest noise is scaled to",
+        global = true
+    )]
+    pub scale_max: f32,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "The file to write, 3d and 4d images are written as one file per slice",
+        global = true
+    )]
+    pub output: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "The file format, by default taken from the extension of the output",
+        global = true
+    )]
+    pub format: Option<Format>,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum Dimension {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let num = match self {
+            Dimension::One => "one",
+            Dimension::Two => "two",
+            Dimension::Three => "three",
+            Dimension::Four => "four",
+        };
+        write!(f, "{}", num)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum Distance {
+    Euclidean,
+    Manhattan,
+    Natural,
+}
+
+impl From<Distance> for CellDistanceFunction {
+    fn from(distance: Distance) -> Self {
+        match distance {
+            Distance::Euclidean => CellDistanceFunction::Euclidean,
+            Distance::Manhattan => CellDistanceFunction::Manhattan,
+            Distance::Natural => CellDistanceFunction::Natural,
+        }
+    }
+}
+
+impl Display for Distance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Distance::Euclidean => "euclidean",
+            Distance::Manhattan => "manhattan",
+            Distance::Natural => "natural",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum CellReturn {
+    CellValue,
+    Distance,
+}
+
+impl From<CellReturn> for CellReturnType {
+    fn from(return_type: CellReturn) -> Self {
+        match return_type {
+            CellReturn::CellValue => CellReturnType::CellValue,
+            CellReturn::Distance => CellReturnType::Distance,
+        }
+    }
+}
+
+impl Display for CellReturn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CellReturn::CellValue => "cell-value",
+            CellReturn::Distance => "distance",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum Cell2Return {
+    Distance2,
+    Distance2Add,
+    Distance2Sub,
+    Distance2Mul,
+    Distance2Div,
+}
+
+impl From<Cell2Return> for Cell2ReturnType {
+    fn from(return_type: Cell2Return) -> Self {
+        match return_type {
+            Cell2Return::Distance2 => Cell2ReturnType::Distance2,
+            Cell2Return::Distance2Add => Cell2ReturnType::Distance2Add,
+            Cell2Return::Distance2Sub => Cell2ReturnType::Distance2Sub,
+            Cell2Return::Distance2Mul => Cell2ReturnType::Distance2Mul,
+            Cell2Return::Distance2Div => Cell2ReturnType::Distance2Div,
+        }
+    }
+}
+
+impl Display for Cell2Return {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Cell2Return::Distance2 => "distance2",
+            Cell2Return::Distance2Add => "distance2-add",
+            Cell2Return::Distance2Sub => "distance2-sub",
+            Cell2Return::Distance2Mul => "distance2-mul",
+            Cell2Return::Distance2Div => "distance2-div",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+enum Format {
+    /// 8 bit grayscale PGM
+    Pgm8,
+    /// 16 bit grayscale PGM
+    Pgm16,
+    /// 32 bit float PFM
+    Pfm,
+    /// 16 bit grayscale PNG
+    Png16,
+    /// Little endian 32 bit floats with a detached NRRD header
+    Raw,
+    /// NumPy array of 32 bit floats
+    Npy,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "pgm" => Some(Format::Pgm8),
+            "pfm" => Some(Format::Pfm),
+            "png" => Some(Format::Png16),
+            "raw" | "f32" => Some(Format::Raw),
+            "npy" => Some(Format::Npy),
+            _ => None,
+        }
+    }
+
+    fn image_format(&self) -> Option<ImageFormat> {
+        match self {
+            Format::Pgm8 => Some(ImageFormat::Pgm8),
+            Format::Pgm16 => Some(ImageFormat::Pgm16),
+            Format::Pfm => Some(ImageFormat::Pfm),
+            Format::Png16 => Some(ImageFormat::Png16),
+            Format::Raw | Format::Npy => None,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    #[command(arg_required_else_help = false)]
+    Cellular {
+        #[clap(long, value_parser, default_value_t = Distance::Euclidean, help="The distance function")]
+        distance: Distance,
+        #[clap(long, value_parser, default_value_t = CellReturn::Distance, help="What the noise returns")]
+        return_type: CellReturn,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_JITTER)]
+        jitter: f32,
+    },
+    #[command(arg_required_else_help = false)]
+    Cellular2 {
+        #[clap(long, value_parser, default_value_t = Distance::Euclidean, help="The distance function")]
+        distance: Distance,
+        #[clap(long, value_parser, default_value_t = Cell2Return::Distance2, help="How the two distances are combined")]
+        return_type: Cell2Return,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_JITTER)]
+        jitter: f32,
+        #[arg(
+            long,
+            value_parser,
+            default_value_t = 0,
+            help = "The index of the first distance"
+        )]
+        index0: usize,
+        #[arg(
+            long,
+            value_parser,
+            default_value_t = 1,
+            help = "The index of the second distance"
+        )]
+        index1: usize,
+    },
+    #[command(arg_required_else_help = false)]
+    Fbm {
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_LACUNARITY)]
+        lacunarity: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_GAIN)]
+        gain: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_OCTAVES)]
+        octaves: u8,
+    },
+    #[command(arg_required_else_help = false)]
+    Ridge {
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_LACUNARITY)]
+        lacunarity: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_GAIN)]
+        gain: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_OCTAVES)]
+        octaves: u8,
+    },
+    #[command(arg_required_else_help = false)]
+    Turbulence {
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_LACUNARITY)]
+        lacunarity: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_GAIN)]
+        gain: f32,
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_OCTAVES)]
+        octaves: u8,
+    },
+    #[command(arg_required_else_help = false)]
+    Gradient {
+        #[arg(short, long, value_parser, default_value_t = DEFAULT_FREQUENCY)]
+        frequency: f32,
+    },
+}
+
+/// One value per axis, x to w.
+#[derive(Copy, Clone)]
+struct Coordinate<T> {
+    x: T,
+    y: T,
+    z: T,
+    w: T,
+}
+
+macro_rules! offset_builder {
+    ($fn_1d: ident, $fn_2d: ident, $fn_3d: ident, $fn_4d: ident, $dimension: expr, $size: expr, $offset: expr) => {
+        match $dimension {
+            Dimension::One => NoiseBuilder::$fn_1d($offset.x, $size.x),
+            Dimension::Two => NoiseBuilder::$fn_2d($offset.x, $size.x, $offset.y, $size.y),
+            Dimension::Three => {
+                NoiseBuilder::$fn_3d($offset.x, $size.x, $offset.y, $size.y, $offset.z, $size.z)
+            }
+            Dimension::Four => NoiseBuilder::$fn_4d(
+                $offset.x, $size.x, $offset.y, $size.y, $offset.z, $size.z, $offset.w, $size.w,
+            ),
+        }
+    };
+}
+
+macro_rules! cellular_builder {
+    ($fn_2d: ident, $fn_3d: ident, $dimension: expr, $size: expr, $offset: expr) => {
+        match $dimension {
+            Dimension::Two => NoiseBuilder::$fn_2d($offset.x, $size.x, $offset.y, $size.y),
+            Dimension::Three => {
+                NoiseBuilder::$fn_3d($offset.x, $size.x, $offset.y, $size.y, $offset.z, $size.z)
+            }
+            _ => return Err("cellular noise only has two and three dimensions".to_string()),
+        }
+    };
+}
+
+macro_rules! fractal_noise {
+    ($fn_1d: ident, $fn_2d: ident, $fn_3d: ident, $fn_4d: ident, $dimension: expr, $size: expr, $offset: expr, $frequency: expr, $lacunarity: expr, $gain: expr, $octaves: expr) => {{
+        let mut settings =
+            offset_builder!($fn_1d, $fn_2d, $fn_3d, $fn_4d, $dimension, $size, $offset);
+        settings
+            .with_freq($frequency)
+            .with_lacunarity($lacunarity)
+            .with_gain($gain)
+            .with_octaves($octaves);
+        settings
+    }};
+}
+
+fn build_noise(
+    command: Commands,
+    dimension: Dimension,
+    seed: i32,
+    size: Coordinate<usize>,
+    offset: Coordinate<f32>,
+) -> Result<NoiseType, String> {
+    let noise_type = match command {
+        Commands::Cellular {
+            distance,
+            return_type,
+            frequency,
+            jitter,
+        } => {
+            let mut settings = cellular_builder!(
+                cellular_2d_offset,
+                cellular_3d_offset,
+                dimension,
+                size,
+                offset
+            );
+            settings
+                .with_distance_function(distance.into())
+                .with_return_type(return_type.into())
+                .with_freq(frequency)
+                .with_jitter(jitter)
+                .with_seed(seed);
+            NoiseType::Cellular(settings)
+        }
+        Commands::Cellular2 {
+            distance,
+            return_type,
+            frequency,
+            jitter,
+            index0,
+            index1,
+        } => {
+            let mut settings = cellular_builder!(
+                cellular2_2d_offset,
+                cellular2_3d_offset,
+                dimension,
+                size,
+                offset
+            );
+            settings
+                .with_distance_function(distance.into())
+                .with_return_type(return_type.into())
+                .with_freq(frequency)
+                .with_jitter(jitter)
+                .with_index0(index0)
+                .with_index1(index1)
+                .with_seed(seed);
+            NoiseType::Cellular2(settings)
+        }
+        Commands::Fbm {
+            frequency,
+            lacunarity,
+            gain,
+            octaves,
+        } => fractal_noise!(
+            fbm_1d_offset,
+            fbm_2d_offset,
+            fbm_3d_offset,
+            fbm_4d_offset,
+            dimension,
+            size,
+            offset,
+            frequency,
+            lacunarity,
+            gain,
+            octaves
+        )
+        .with_seed(seed)
+        .wrap(),
+        Commands::Ridge {
+            frequency,
+            lacunarity,
+            gain,
+            octaves,
+        } => fractal_noise!(
+            ridge_1d_offset,
+            ridge_2d_offset,
+            ridge_3d_offset,
+            ridge_4d_offset,
+            dimension,
+            size,
+            offset,
+            frequency,
+            lacunarity,
+            gain,
+            octaves
+        )
+        .with_seed(seed)
+        .wrap(),
+        Commands::Turbulence {
+            frequency,
+            lacunarity,
+            gain,
+            octaves,
+        } => fractal_noise!(
+            turbulence_1d_offset,
+            turbulence_2d_offset,
+            turbulence_3d_offset,
+            turbulence_4d_offset,
+            dimension,
+            size,
+            offset,
+            frequency,
+            lacunarity,
+            gain,
+            octaves
+        )
+        .with_seed(seed)
+        .wrap(),
+        Commands::Gradient { frequency } => {
+            let mut settings = offset_builder!(
+                gradient_1d_offset,
+                gradient_2d_offset,
+                gradient_3d_offset,
+                gradient_4d_offset,
+                dimension,
+                size,
+                offset
+            );
+            settings.with_freq(frequency).with_seed(seed);
+            settings.wrap()
+        }
+    };
+    noise_type.check()?;
+    Ok(noise_type)
+}
+
+/// The sizes of the used axes, slowest changing first, as NumPy orders them.
+fn shape(dimension: Dimension, size: Coordinate<usize>) -> Vec<usize> {
+    match dimension {
+        Dimension::One => vec![size.x],
+        Dimension::Two => vec![size.y, size.x],
+        Dimension::Three => vec![size.z, size.y, size.x],
+        Dimension::Four => vec![size.w, size.z, size.y, size.x],
+    }
+}
+
+/// Writes the noise and returns the paths written.
+fn write_output(
+    path: &Path,
+    format: Format,
+    data: &[f32],
+    shape: &[usize],
+) -> io::Result<Vec<PathBuf>> {
+    match format.image_format() {
+        Some(image_format) => {
+            let width = shape[shape.len() - 1];
+            let height = if shape.len() > 1 {
+                shape[shape.len() - 2]
+            } else {
+                1
+            };
+            let slices: usize = shape.iter().rev().skip(2).product();
+            if slices == 1 {
+                save_image(path, image_format, data, width, height)?;
+                Ok(vec![path.to_path_buf()])
+            } else {
+                save_image_stack(
+                    path.with_extension(""),
+                    image_format,
+                    data,
+                    width,
+                    height,
+                    slices,
+                )
+            }
+        }
+        None if format == Format::Npy => {
+            save_npy(path, data, shape)?;
+            Ok(vec![path.to_path_buf()])
+        }
+        None => {
+            let header_path = save_raw(path, data, shape)?;
+            Ok(vec![path.to_path_buf(), header_path])
+        }
+    }
+}
+
+fn run(args: Args) -> Result<Vec<PathBuf>, String> {
+    let path = args
+        .output
+        .ok_or_else(|| "no --output file given".to_string())?;
+    let format = match args.format.or_else(|| Format::from_path(&path)) {
+        Some(format) => format,
+        None => {
+            return Err(format!(
+                "cannot tell the format of {}, use --format",
+                path.display()
+            ))
+        }
+    };
+    let size = Coordinate {
+        x: args.width,
+        y: args.height,
+        z: args.depth,
+        w: args.time,
+    };
+    let offset = Coordinate {
+        x: args.offset_x.unwrap_or_default(),
+        y: args.offset_y.unwrap_or_default(),
+        z: args.offset_z.unwrap_or_default(),
+        w: args.offset_w.unwrap_or_default(),
+    };
+    let noise_type = build_noise(args.command, args.dimension, args.seed, size, offset)?;
+    let noise = NoiseNode::from(noise_type).generate_scaled(args.scale_min, args.scale_max);
+    write_output(&path, format, &noise, &shape(args.dimension, size))
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+fn main() {
+    match run(Args::parse()) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+        }
+        Err(message) => {
+            eprintln!("simdnoise: {}", message);
+            process::exit(1);
+        }
+    }
+}
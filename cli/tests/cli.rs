@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use simdnoise::export::load_npy;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("simdnoise_cli_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn simdnoise(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_simdnoise"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_cli_writes_pgm() {
+    let path = temp_path("fbm.pgm");
+    let output = simdnoise(&[
+        "--width",
+        "16",
+        "--height",
+        "8",
+        "--output",
+        path.to_str().unwrap(),
+        "fbm",
+        "-f",
+        "0.1",
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        path.display().to_string()
+    );
+    let bytes = std::fs::read(&path).unwrap();
+    let header = b"P5\n16 8\n255\n";
+    assert_eq!(&bytes[..header.len()], header);
+    assert_eq!(bytes.len(), header.len() + 16 * 8);
+}
+
+#[test]
+fn test_cli_writes_npy() {
+    let path = temp_path("cells.npy");
+    let output = simdnoise(&[
+        "--dimension",
+        "three",
+        "--width",
+        "6",
+        "--height",
+        "5",
+        "--depth",
+        "4",
+        "--output",
+        path.to_str().unwrap(),
+        "cellular",
+        "-f",
+        "0.3",
+    ]);
+    assert!(output.status.success());
+    let (data, shape) = load_npy::<_, f32>(&path).unwrap();
+    assert_eq!(shape, vec![4, 5, 6]);
+    assert_eq!(data.len(), 6 * 5 * 4);
+    assert!(data.iter().all(|v| (0.0..=1.0).contains(v)));
+}
+
+#[test]
+fn test_cli_reports_invalid_settings() {
+    let path = temp_path("invalid.pgm");
+    let output = simdnoise(&[
+        "--width",
+        "8",
+        "--height",
+        "8",
+        "--output",
+        path.to_str().unwrap(),
+        "cellular2",
+        "--index1",
+        "40",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap().trim(),
+        "simdnoise: invalid index settings in cellular2 noise"
+    );
+}
@@ -3,7 +3,7 @@ use std::slice::Iter;
 
 use clap::{Parser, Subcommand, ValueEnum};
 use minifb::{Key, Window, WindowOptions};
-use simdnoise::{CellDistanceFunction, Settings, SimplexSettings};
+use simdnoise::{CellDistanceFunction, ColorRamp, Settings, SimplexSettings};
 
 const FPS: usize = 60;
 
@@ -291,7 +291,13 @@ fn noise_1d_to_frames(noise: Vec<f32>, position: Coordinate<usize>) -> Vec<Vec<u
 }
 
 fn noise_2d_to_frames(noise: Vec<f32>) -> Vec<Vec<u32>> {
-    vec![noise.iter().map(|x| *x as u32).collect()]
+    let noise: Vec<f32> = noise.iter().map(|x| *x / SCALE_MAX).collect();
+    let rgba = ColorRamp::grayscale().map_rgba8(&noise);
+    // minifb takes 0RGB pixels
+    vec![rgba
+        .chunks(4)
+        .map(|c| u32::from_be_bytes([0, c[0], c[1], c[2]]))
+        .collect()]
 }
 
 fn noise_3d_to_frames(noise: Vec<f32>) -> Vec<Vec<u32>> {
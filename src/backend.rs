@@ -0,0 +1,252 @@
+//! Which instruction set the noise is generated with.
+//!
+//! By default every call picks the fastest backend the cpu supports. `force_backend` overrides
+//! that for the whole process, to reproduce a bug seen on another machine or to compare the
+//! speed of the backends.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use simdeez::prelude::*;
+
+use crate::intrinsics::portable::Portable;
+use crate::{noise_helpers_32, noise_helpers_64, NoiseType};
+
+/// An instruction set the noise can be generated with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// One value at a time, works everywhere.
+    Scalar,
+    /// The portable SIMD engine of `intrinsics::portable`, works everywhere.
+    Portable,
+    Sse2,
+    Sse41,
+    /// AVX2 with FMA3.
+    Avx2,
+    /// Arm NEON, picked by simdeez on aarch64.
+    Neon,
+    /// WebAssembly SIMD, picked by simdeez on wasm32.
+    Wasm,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 7] = [
+        Backend::Scalar,
+        Backend::Portable,
+        Backend::Sse2,
+        Backend::Sse41,
+        Backend::Avx2,
+        Backend::Neon,
+        Backend::Wasm,
+    ];
+
+    /// The name, as written by `Display`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Scalar => "scalar",
+            Backend::Portable => "portable",
+            Backend::Sse2 => "sse2",
+            Backend::Sse41 => "sse41",
+            Backend::Avx2 => "avx2",
+            Backend::Neon => "neon",
+            Backend::Wasm => "wasm",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Backend> {
+        Backend::ALL.iter().copied().find(|b| b.name() == name)
+    }
+
+    /// Whether the cpu this runs on supports the backend.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Backend::Scalar | Backend::Portable => true,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse2 => is_x86_feature_detected!("sse2"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse41 => is_x86_feature_detected!("sse4.1"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma"),
+            #[cfg(target_arch = "aarch64")]
+            Backend::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[cfg(target_arch = "wasm32")]
+            Backend::Wasm => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// The fastest backend the cpu supports, the one used unless another one is forced.
+    pub fn detect() -> Backend {
+        [
+            Backend::Avx2,
+            Backend::Sse41,
+            Backend::Sse2,
+            Backend::Neon,
+            Backend::Wasm,
+        ]
+        .iter()
+        .copied()
+        .find(Backend::is_supported)
+        .unwrap_or(Backend::Portable)
+    }
+
+    fn checked(self) -> Result<Backend, UnsupportedBackend> {
+        if self.is_supported() {
+            Ok(self)
+        } else {
+            Err(UnsupportedBackend(self))
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The cpu does not support the backend that was asked for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedBackend(pub Backend);
+
+impl fmt::Display for UnsupportedBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the cpu does not support the {} backend", self.0)
+    }
+}
+
+impl Error for UnsupportedBackend {}
+
+/// The forced backend, `NOT_FORCED` when detecting.
+static FORCED: AtomicU8 = AtomicU8::new(NOT_FORCED);
+const NOT_FORCED: u8 = u8::MAX;
+
+/// The backend the runtime dispatched functions, like `get_2d_noise` and the settings'
+/// `generate`, currently use.
+pub fn active_backend() -> Backend {
+    match FORCED.load(Ordering::Relaxed) {
+        NOT_FORCED => Backend::detect(),
+        forced => Backend::ALL[forced as usize],
+    }
+}
+
+/// Makes every runtime dispatched function use `backend`, or the detected one again with
+/// `None`. This is global to the process.
+pub fn force_backend(backend: Option<Backend>) -> Result<(), UnsupportedBackend> {
+    let forced = match backend {
+        Some(backend) => backend.checked()? as u8,
+        None => NOT_FORCED,
+    };
+    FORCED.store(forced, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Runs `R` with the instruction set of `backend`, which has to be supported.
+pub(crate) fn run_on<R: __SimdRunner<A, T>, A, T>(backend: Backend, args: A) -> T {
+    debug_assert!(backend.is_supported());
+    match backend {
+        Backend::Scalar => __run_simd_invoke_scalar::<R, A, T>(args),
+        Backend::Portable => unsafe { R::run::<Portable>(args) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Backend::Sse2 => unsafe { __run_simd_invoke_sse2::<R, A, T>(args) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Backend::Sse41 => unsafe { __run_simd_invoke_sse41::<R, A, T>(args) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        Backend::Avx2 => unsafe { __run_simd_invoke_avx2::<R, A, T>(args) },
+        // NEON and WebAssembly are the only instruction set simdeez detects on their targets
+        _ => __run_simd_runtime_decide::<R, A, T>(args),
+    }
+}
+
+/// Runs `R` with the active backend.
+pub(crate) fn run<R: __SimdRunner<A, T>, A, T>(args: A) -> T {
+    run_on::<R, A, T>(active_backend(), args)
+}
+
+/// `simd_runtime_generate!`, but dispatching to the active backend instead of always to the
+/// fastest one.
+///
+/// Functions generic over types, which `simd_runtime_generate!` does not take, only get the
+/// dispatching function, without the `_scalar` and `_generic` variants.
+macro_rules! backend_runtime_generate {
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident <$($gen:ident: $bound:path),+> ($($arg:ident:$typ:ty),* $(,)? ) -> $rt:ty $body:block  ) => {
+        simdeez_paste_item! {
+            $(#[$meta])*
+            #[inline(always)]
+            $vis fn $fn_name<$($gen: $bound),+>($($arg:$typ,)*) -> $rt {
+                let args_tuple = ($($arg,)*);
+                crate::backend::run::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+            }
+
+            #[allow(non_camel_case_types)]
+            struct [<__ $fn_name _dispatch_struct>];
+
+            impl<$($gen: $bound),+> __SimdRunner<fix_tuple_type!(($($typ),*)), $rt> for [<__ $fn_name _dispatch_struct>] {
+                unsafe fn run<S: Simd>(args_tuple: fix_tuple_type!(($($typ),*))) -> $rt {
+                    let ($($arg,)*) = args_tuple;
+                    S::invoke(#[inline(always)] || $body)
+                }
+            }
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident $(<$($lt:lifetime),+>)? ($($arg:ident:$typ:ty),* $(,)? ) -> $rt:ty $body:block  ) => {
+        simdeez_paste_item! {
+            $(#[$meta])*
+            #[inline(always)]
+            $vis fn $fn_name $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+                let args_tuple = ($($arg,)*);
+                crate::backend::run::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+            }
+
+            $(#[$meta])*
+            #[inline(always)]
+            #[allow(dead_code)]
+            $vis fn [<$fn_name _scalar>] $(<$($lt),+>)?($($arg:$typ,)*) -> $rt {
+                let args_tuple = ($($arg,)*);
+                __run_simd_invoke_scalar::<[<__ $fn_name _dispatch_struct>], fix_tuple_type!(($($typ),*)), $rt>(args_tuple)
+            }
+
+            __simd_generate_base!($(#[$meta])* $vis fn $fn_name $(<$($lt),+>)? ($($arg:$typ),* ) -> $rt $body);
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis fn $fn_name:ident ($($arg:ident:$typ:ty),* $(,)? ) $body:block  ) => {
+        backend_runtime_generate!($(#[$meta])* $vis fn $fn_name ($($arg:$typ),*) -> () $body);
+    };
+}
+
+macro_rules! get_noise {
+    ($call: ident, $fn_name: ident, $f_type: ty, $helpers: ident) => {
+        /// Safe version of the `get_` function of the same name in the backend's `intrinsics`
+        /// module, that checks the cpu supports the backend first.
+        pub fn $fn_name(
+            &self,
+            noise_type: &NoiseType,
+        ) -> Result<(Vec<$f_type>, $f_type, $f_type), UnsupportedBackend> {
+            struct Runner;
+
+            impl<'a> __SimdRunner<(&'a NoiseType,), (Vec<$f_type>, $f_type, $f_type)> for Runner {
+                unsafe fn run<S: Simd>(args: (&'a NoiseType,)) -> (Vec<$f_type>, $f_type, $f_type) {
+                    S::invoke(
+                        #[inline(always)]
+                        || $helpers::$call::<S>(args.0),
+                    )
+                }
+            }
+
+            Ok(run_on::<Runner, _, _>(self.checked()?, (noise_type,)))
+        }
+    };
+}
+
+impl Backend {
+    get_noise!(get_1d_noise, get_1d_noise, f32, noise_helpers_32);
+    get_noise!(get_2d_noise, get_2d_noise, f32, noise_helpers_32);
+    get_noise!(get_3d_noise, get_3d_noise, f32, noise_helpers_32);
+    get_noise!(get_4d_noise, get_4d_noise, f32, noise_helpers_32);
+    get_noise!(get_1d_noise, get_1d_noise_64, f64, noise_helpers_64);
+    get_noise!(get_2d_noise, get_2d_noise_64, f64, noise_helpers_64);
+    get_noise!(get_3d_noise, get_3d_noise_64, f64, noise_helpers_64);
+    get_noise!(get_4d_noise, get_4d_noise_64, f64, noise_helpers_64);
+}
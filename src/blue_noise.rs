@@ -0,0 +1,229 @@
+//! Blue noise textures generated with the void-and-cluster algorithm.
+//!
+//! Unlike the other noise types these are not evaluated per sample: a whole texture is
+//! built at once by ranking every cell so that any threshold of the ranks gives evenly
+//! spread points. The textures wrap around at the edges, so they can be tiled.
+//!
+//! The energy of the cells is updated around every cell that is set or cleared, and a tree
+//! over the cells keeps the tightest cluster and the largest void, so a texture of `n` cells
+//! takes `O(n log n)` steps.
+
+use crate::shared::{scale_buffer, SplitMix64};
+
+/// Standard deviation of the gaussian used to measure how clustered the points are.
+const SIGMA: f64 = 1.5;
+/// The gaussian is cut off at about three standard deviations.
+const KERNEL_RADIUS: isize = 5;
+/// Fraction of the cells set in the initial binary pattern.
+const INITIAL_DENSITY: usize = 10;
+/// A tree node without a cell below it.
+const NONE: usize = usize::MAX;
+
+/// Generates a tileable `width` x `height` blue noise texture.
+///
+/// Every cell gets a distinct rank, scaled to the range [0, 1]. Thresholding the result at
+/// `t` selects about `t * width * height` points that are spread evenly over the texture.
+pub fn blue_noise_2d(width: usize, height: usize, seed: i32) -> Vec<f32> {
+    void_and_cluster([width, height, 1], seed)
+}
+
+/// Generates a tileable `width` x `height` x `depth` blue noise volume.
+///
+/// Every cell gets a distinct rank, scaled to the range [0, 1]. Thresholding the result at
+/// `t` selects about `t * width * height * depth` points that are spread evenly over the volume.
+pub fn blue_noise_3d(width: usize, height: usize, depth: usize, seed: i32) -> Vec<f32> {
+    void_and_cluster([width, height, depth], seed)
+}
+
+fn void_and_cluster(dims: [usize; 3], seed: i32) -> Vec<f32> {
+    let len = dims[0] * dims[1] * dims[2];
+    if len < 2 {
+        return vec![0.0; len];
+    }
+    let mut field = EnergyField::new(dims);
+
+    // Random initial pattern
+    let initial = (len / INITIAL_DENSITY).max(1);
+    let mut cells: Vec<usize> = (0..len).collect();
+    let mut rng = SplitMix64::new(seed);
+    for i in 0..initial {
+        let j = i + (rng.next() % (len - i) as u64) as usize;
+        cells.swap(i, j);
+        field.toggle(cells[i], true);
+    }
+
+    // Move points from the tightest cluster to the largest void until it settles
+    for _ in 0..len {
+        let cluster = field.tightest_cluster();
+        field.toggle(cluster, false);
+        let void = field.largest_void();
+        field.toggle(void, true);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0.0; len];
+    let prototype = field.clone();
+
+    // Ranks below the initial pattern: remove the tightest clusters first
+    for rank in (0..initial).rev() {
+        let cluster = field.tightest_cluster();
+        field.toggle(cluster, false);
+        ranks[cluster] = rank as f32;
+    }
+
+    // Ranks above the initial pattern up to half full: fill the largest voids first
+    field = prototype;
+    let half = len / 2;
+    for rank in initial..half {
+        let void = field.largest_void();
+        field.toggle(void, true);
+        ranks[void] = rank as f32;
+    }
+
+    // Past half full the empty cells are the minority: set the tightest clusters of them first
+    let mut empty = EnergyField::new(dims);
+    for (index, _) in field.set.iter().enumerate().filter(|(_, &s)| !s) {
+        empty.toggle(index, true);
+    }
+    for rank in half..len {
+        let cluster = empty.tightest_cluster();
+        empty.toggle(cluster, false);
+        ranks[cluster] = rank as f32;
+    }
+
+    let max = (len - 1) as f32;
+    scale_buffer(ranks, 0.0, 1.0, 0.0, max)
+}
+
+#[derive(Clone)]
+struct EnergyField {
+    dims: [usize; 3],
+    kernel: Vec<f64>,
+    set: Vec<bool>,
+    energy: Vec<f64>,
+    /// Trees over the cells, a power of two of leaves, holding the tightest cluster and the
+    /// largest void below every node. The root is at 1 and the cells start at `leaves`.
+    clusters: Vec<usize>,
+    voids: Vec<usize>,
+    leaves: usize,
+    /// The tree nodes a toggle changes, a level at a time, and which of them are listed.
+    touched: Vec<usize>,
+    listed: Vec<bool>,
+}
+
+impl EnergyField {
+    fn new(dims: [usize; 3]) -> EnergyField {
+        let len = dims[0] * dims[1] * dims[2];
+        let kernel = (0..=KERNEL_RADIUS)
+            .map(|d| (-((d * d) as f64) / (2.0 * SIGMA * SIGMA)).exp())
+            .collect();
+        let leaves = len.next_power_of_two();
+        let mut field = EnergyField {
+            dims,
+            kernel,
+            set: vec![false; len],
+            energy: vec![0.0; len],
+            clusters: vec![NONE; 2 * leaves],
+            voids: vec![NONE; 2 * leaves],
+            leaves,
+            touched: Vec::new(),
+            listed: vec![false; 2 * leaves],
+        };
+        for index in 0..len {
+            field.voids[leaves + index] = index;
+        }
+        for node in (1..leaves).rev() {
+            field.update_node(node);
+        }
+        field
+    }
+
+    /// Sets or clears a cell, updating the energy of the cells around it with wrap around.
+    fn toggle(&mut self, index: usize, on: bool) {
+        self.set[index] = on;
+        let sign = if on { 1.0 } else { -1.0 };
+        let [width, height, depth] = self.dims;
+        let x = index % width;
+        let y = (index / width) % height;
+        let z = index / (width * height);
+        let radius = |size: usize| if size > 1 { KERNEL_RADIUS } else { 0 };
+        let wrap =
+            |c: usize, d: isize, size: usize| (c as isize + d).rem_euclid(size as isize) as usize;
+        let (rx, ry, rz) = (radius(width), radius(height), radius(depth));
+        self.touched.clear();
+        for dz in -rz..=rz {
+            let zi = wrap(z, dz, depth);
+            let gz = self.kernel[dz.unsigned_abs()];
+            for dy in -ry..=ry {
+                let yi = wrap(y, dy, height);
+                let gzy = gz * self.kernel[dy.unsigned_abs()];
+                let row = (zi * height + yi) * width;
+                for dx in -rx..=rx {
+                    let xi = wrap(x, dx, width);
+                    self.energy[row + xi] += sign * gzy * self.kernel[dx.unsigned_abs()];
+                    self.touched.push(self.leaves + row + xi);
+                }
+            }
+        }
+
+        let leaf = self.leaves + index;
+        self.clusters[leaf] = if on { index } else { NONE };
+        self.voids[leaf] = if on { NONE } else { index };
+
+        // The leaves are all at the same depth, so the nodes above them are updated a level
+        // at a time, each once
+        let mut nodes = std::mem::take(&mut self.touched);
+        while nodes[0] > 1 {
+            let mut parents = 0;
+            for i in 0..nodes.len() {
+                let parent = nodes[i] / 2;
+                if !self.listed[parent] {
+                    self.listed[parent] = true;
+                    nodes[parents] = parent;
+                    parents += 1;
+                }
+            }
+            nodes.truncate(parents);
+            for &node in &nodes {
+                self.listed[node] = false;
+                self.update_node(node);
+            }
+        }
+        self.touched = nodes;
+    }
+
+    /// Picks the best cells of the two children of `node`, the first one on ties.
+    fn update_node(&mut self, node: usize) {
+        let energy = &self.energy;
+        let pick = |a: usize, b: usize, better: fn(f64, f64) -> bool| match (a, b) {
+            (NONE, _) => b,
+            (_, NONE) => a,
+            _ if better(energy[b], energy[a]) => b,
+            _ => a,
+        };
+        let (left, right) = (2 * node, 2 * node + 1);
+        let cluster = pick(self.clusters[left], self.clusters[right], |e, best| {
+            e > best
+        });
+        let void = pick(self.voids[left], self.voids[right], |e, best| e < best);
+        self.clusters[node] = cluster;
+        self.voids[node] = void;
+    }
+
+    /// The set cell with the highest energy.
+    fn tightest_cluster(&self) -> usize {
+        Self::root(self.clusters[1])
+    }
+
+    /// The empty cell with the lowest energy.
+    fn largest_void(&self) -> usize {
+        Self::root(self.voids[1])
+    }
+
+    fn root(best: usize) -> usize {
+        assert!(best != NONE, "no cell to pick in blue noise generation");
+        best
+    }
+}
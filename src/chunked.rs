@@ -0,0 +1,188 @@
+//! Endless worlds generated one chunk at a time.
+//!
+//! A `ChunkedGenerator` maps integer chunk coordinates to blocks of noise. Chunk `c` covers
+//! the samples from `c * chunk_size` along each axis, plus `padding` samples of its
+//! neighbours on every side for filters that look past the edge, like normal maps or
+//! erosion. Padding samples are exactly the values the neighbours generate for them.
+//!
+//! The sample positions are worked out from the integer chunk coordinates in `f64` and
+//! sampled with the `f64` kernels, so the noise stays as smooth a million samples from the
+//! origin as next to it. The builders' offsets are `f32`, which skips whole samples beyond
+//! 2^24, but from `AlgorithmVersion::V3` their blocks far from the origin are sampled in
+//! `f64` from the offsets too.
+
+use simdeez::prelude::*;
+
+use crate::dimensional_being::DimensionalBeing;
+use crate::noise_helpers_64::Sample64;
+use crate::NoiseNode;
+
+/// Generates the chunks of an endless world of noise.
+///
+/// The size and offset of the settings the generator is made from are ignored, everything
+/// else, seed and frequency included, applies to every chunk. The values are not scaled:
+/// scaling every chunk by its own range would break the seams, scale by fixed bounds
+/// instead.
+///
+/// ```rust
+/// use simdnoise::*;
+///
+/// let mut world = ChunkedGenerator::new(NoiseBuilder::fbm_2d(1, 1).with_freq(0.01).wrap(), 64);
+/// world.with_padding(1);
+///
+/// // 66 by 66 samples, the first at x = 64 * 20_000 - 1 and y = -64 * 3 - 1
+/// let block = world.chunk_2d(20_000, -3);
+/// let normals = normal_map(&block, 66, 66, 10.0, EdgeMode::Clamp);
+/// assert_eq!(world.crop(&normals).len(), 64 * 64);
+/// ```
+#[derive(Clone)]
+pub struct ChunkedGenerator {
+    noise: NoiseNode,
+    chunk_size: usize,
+    padding: usize,
+}
+
+impl ChunkedGenerator {
+    /// A generator of chunks `chunk_size` samples across, without padding.
+    pub fn new<N: Into<NoiseNode>>(noise: N, chunk_size: usize) -> ChunkedGenerator {
+        assert!(chunk_size > 0, "chunks need at least one sample");
+        let noise = noise.into();
+        noise.validate();
+        ChunkedGenerator {
+            noise,
+            chunk_size,
+            padding: 0,
+        }
+    }
+
+    /// Adds `padding` samples of the neighbouring chunks on every side of each chunk.
+    pub fn with_padding(&mut self, padding: usize) -> &mut ChunkedGenerator {
+        self.padding = padding;
+        self
+    }
+
+    /// The number of dimensions of the noise, and of the chunk coordinates.
+    pub fn dimensions(&self) -> usize {
+        self.noise.get_dimensions().dim
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+
+    /// The samples along each axis of a block, the chunk and the padding on both sides.
+    pub fn block_size(&self) -> usize {
+        self.chunk_size + 2 * self.padding
+    }
+
+    /// The position of the first sample of the block of `chunk` along an axis, padding
+    /// included.
+    pub fn block_origin(&self, chunk: i64) -> i64 {
+        chunk * self.chunk_size as i64 - self.padding as i64
+    }
+
+    /// The chunk the sample at `position` along an axis belongs to, and the position of the
+    /// sample within the chunk, padding not counted.
+    pub fn chunk_of(&self, position: i64) -> (i64, usize) {
+        let size = self.chunk_size as i64;
+        (
+            position.div_euclid(size),
+            position.rem_euclid(size) as usize,
+        )
+    }
+
+    pub fn chunk_1d(&self, x: i64) -> Vec<f32> {
+        self.chunk(1, [x, 0, 0, 0])
+    }
+
+    /// The block of chunk `x`, `y`, rows along x.
+    pub fn chunk_2d(&self, x: i64, y: i64) -> Vec<f32> {
+        self.chunk(2, [x, y, 0, 0])
+    }
+
+    pub fn chunk_3d(&self, x: i64, y: i64, z: i64) -> Vec<f32> {
+        self.chunk(3, [x, y, z, 0])
+    }
+
+    pub fn chunk_4d(&self, x: i64, y: i64, z: i64, w: i64) -> Vec<f32> {
+        self.chunk(4, [x, y, z, w])
+    }
+
+    /// Removes the padding from a block, or from anything computed per sample of one, like
+    /// a normal map.
+    pub fn crop<T: Copy>(&self, block: &[T]) -> Vec<T> {
+        let (n, p, size) = (self.block_size(), self.padding, self.chunk_size);
+        let dim = self.dimensions();
+        assert_eq!(
+            block.len(),
+            n.pow(dim as u32),
+            "the buffer is not the size of a block"
+        );
+        let mut result = Vec::with_capacity(size.pow(dim as u32));
+        // Each row along x, of every used axis above it
+        let rows = size.pow(dim as u32 - 1);
+        for row in 0..rows {
+            let mut start = p;
+            let mut stride = n;
+            let mut rest = row;
+            for _ in 1..dim {
+                start += (rest % size + p) * stride;
+                rest /= size;
+                stride *= n;
+            }
+            result.extend_from_slice(&block[start..start + size]);
+        }
+        result
+    }
+
+    fn chunk(&self, dim: usize, chunk: [i64; 4]) -> Vec<f32> {
+        assert_eq!(
+            dim,
+            self.dimensions(),
+            "the chunk coordinates do not match the noise"
+        );
+        let mut origin = [0; 4];
+        for (o, c) in origin.iter_mut().zip(&chunk).take(dim) {
+            *o = self.block_origin(*c);
+        }
+        get_chunk_noise(&self.noise, dim, origin, self.block_size())
+    }
+}
+
+backend_runtime_generate!(
+    fn get_chunk_noise(noise: &NoiseNode, dim: usize, origin: [i64; 4], n: usize) -> Vec<f32> {
+        let mut lengths = [1; 4];
+        lengths.iter_mut().take(dim).for_each(|l| *l = n);
+        let mut result = Vec::with_capacity(lengths.iter().product());
+
+        // Every position is rounded once, from its exact integer value
+        let at = |axis: usize, i: usize| S::Vf64::set1((origin[axis] + i as i64) as f64);
+        let vector_width = S::Vf64::WIDTH;
+        for w in 0..lengths[3] {
+            for z in 0..lengths[2] {
+                for y in 0..lengths[1] {
+                    for x in (0..n).step_by(vector_width) {
+                        let mut px = S::Vf64::zeroes();
+                        for j in 0..vector_width {
+                            px[j] = (origin[0] + (x + j) as i64) as f64;
+                        }
+                        let f = match dim {
+                            1 => Sample64::<S>::sample_1d(noise, px),
+                            2 => Sample64::<S>::sample_2d(noise, px, at(1, y)),
+                            3 => Sample64::<S>::sample_3d(noise, px, at(1, y), at(2, z)),
+                            _ => Sample64::<S>::sample_4d(noise, px, at(1, y), at(2, z), at(3, w)),
+                        };
+                        for j in 0..vector_width.min(n - x) {
+                            result.push(f[j] as f32);
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+);
@@ -0,0 +1,227 @@
+//! Colour gradients that turn noise buffers into pixels.
+//!
+//! A `ColorRamp` is a list of stops, each a position and an RGBA colour. Values are mapped by
+//! finding the two stops around them and blending their colours, a SIMD vector at a time.
+//! Values before the first or after the last stop get the colour of that stop.
+
+use simdeez::prelude::*;
+
+/// How the colour changes between two stops.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RampInterpolation {
+    /// Blends linearly from one stop to the next.
+    Linear,
+    /// Eases in and out of every stop with a smoothstep.
+    Smooth,
+    /// Keeps the colour of a stop until the next one, for banded palettes.
+    Step,
+}
+
+/// Maps noise values to colours.
+///
+/// ```rust
+/// use simdnoise::*;
+///
+/// let noise = NoiseBuilder::fbm_2d(64, 64).generate_scaled(0.0, 1.0);
+/// let pixels = ColorRamp::terrain().map_rgba8(&noise);
+/// assert_eq!(pixels.len(), 64 * 64 * 4);
+///
+/// let ramp = ColorRamp::new(
+///     vec![(0.0, [0, 0, 64, 255]), (0.5, [255, 255, 255, 255]), (1.0, [64, 0, 0, 255])],
+///     RampInterpolation::Smooth,
+/// );
+/// let linear = ramp.map_linear_rgba(&noise);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorRamp {
+    stops: Vec<(f32, [u8; 4])>,
+    interpolation: RampInterpolation,
+}
+
+impl ColorRamp {
+    /// A ramp through the given `(position, rgba)` stops. The colours are sRGB, as they are
+    /// written in most palettes. The positions must be ascending, and there must be at least
+    /// one stop.
+    pub fn new(stops: Vec<(f32, [u8; 4])>, interpolation: RampInterpolation) -> ColorRamp {
+        if stops.is_empty() {
+            panic!("color ramp needs at least one stop");
+        }
+        let mut previous = f32::NEG_INFINITY;
+        for (position, _) in &stops {
+            if !position.is_finite() || *position <= previous {
+                panic!("color ramp stops need finite, ascending positions");
+            }
+            previous = *position;
+        }
+        ColorRamp {
+            stops,
+            interpolation,
+        }
+    }
+
+    /// Black to white over [0, 1].
+    pub fn grayscale() -> ColorRamp {
+        ColorRamp::new(
+            vec![(0.0, [0, 0, 0, 255]), (1.0, [255, 255, 255, 255])],
+            RampInterpolation::Linear,
+        )
+    }
+
+    /// Deep water to snowy peaks over [0, 1], with the coast at about 0.45.
+    pub fn terrain() -> ColorRamp {
+        ColorRamp::new(
+            vec![
+                (0.0, [0, 0, 96, 255]),
+                (0.4, [32, 96, 192, 255]),
+                (0.45, [224, 208, 144, 255]),
+                (0.5, [64, 160, 48, 255]),
+                (0.7, [32, 96, 32, 255]),
+                (0.85, [112, 96, 80, 255]),
+                (1.0, [255, 255, 255, 255]),
+            ],
+            RampInterpolation::Linear,
+        )
+    }
+
+    /// Black through red and yellow to white over [0, 1].
+    pub fn heat() -> ColorRamp {
+        ColorRamp::new(
+            vec![
+                (0.0, [0, 0, 0, 255]),
+                (0.35, [192, 0, 0, 255]),
+                (0.7, [255, 192, 0, 255]),
+                (1.0, [255, 255, 255, 255]),
+            ],
+            RampInterpolation::Linear,
+        )
+    }
+
+    /// The same stops with another interpolation.
+    pub fn with_interpolation(mut self, interpolation: RampInterpolation) -> ColorRamp {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn stops(&self) -> &[(f32, [u8; 4])] {
+        &self.stops
+    }
+
+    pub fn interpolation(&self) -> RampInterpolation {
+        self.interpolation
+    }
+
+    /// Maps every value to four sRGB bytes, red, green, blue and alpha.
+    pub fn map_rgba8(&self, data: &[f32]) -> Vec<u8> {
+        let mut rgba = vec![0; data.len() * 4];
+        map_bytes(self, data, &mut rgba);
+        rgba
+    }
+
+    /// Maps every value to four floats, red, green and blue in linear light and alpha, for
+    /// rendering and blending.
+    pub fn map_linear_rgba(&self, data: &[f32]) -> Vec<f32> {
+        let mut rgba = vec![0.0; data.len() * 4];
+        map_colors(self, data, &mut rgba);
+        for pixel in rgba.chunks_mut(4) {
+            for c in &mut pixel[..3] {
+                *c = srgb_to_linear(*c);
+            }
+        }
+        rgba
+    }
+
+    /// Four sRGB channels in [0, 1] for the values in `v`.
+    #[inline(always)]
+    fn apply<S: Simd>(&self, v: S::Vf32) -> [S::Vf32; 4] {
+        let stops = &self.stops;
+        let channel = |i: usize, k: usize| S::Vf32::set1(stops[i].1[k] as f32 / 255.0);
+        if stops.len() == 1 {
+            return [channel(0, 0), channel(0, 1), channel(0, 2), channel(0, 3)];
+        }
+        let last = stops.len() - 1;
+        let position = |i: usize| S::Vf32::set1(stops[i].0);
+        let v = v.max(position(0)).min(position(last));
+        let mut lo = position(0);
+        let mut hi = position(1);
+        let mut lo_color = [channel(0, 0), channel(0, 1), channel(0, 2), channel(0, 3)];
+        let mut hi_color = [channel(1, 0), channel(1, 1), channel(1, 2), channel(1, 3)];
+        for i in 1..last {
+            let above = v.cmp_gte(position(i));
+            lo = above.blendv(lo, position(i));
+            hi = above.blendv(hi, position(i + 1));
+            for k in 0..4 {
+                lo_color[k] = above.blendv(lo_color[k], channel(i, k));
+                hi_color[k] = above.blendv(hi_color[k], channel(i + 1, k));
+            }
+        }
+        let t = (v - lo) / (hi - lo);
+        let t = match self.interpolation {
+            RampInterpolation::Linear => t,
+            RampInterpolation::Smooth => t * t * (S::Vf32::set1(3.0) - t - t),
+            // Only the last stop itself reaches the upper colour
+            RampInterpolation::Step => t.cmp_gte(S::Vf32::set1(1.0)) & S::Vf32::set1(1.0),
+        };
+        let mut color = lo_color;
+        for k in 0..4 {
+            color[k] = lo_color[k] + (hi_color[k] - lo_color[k]) * t;
+        }
+        color
+    }
+}
+
+backend_runtime_generate!(
+    fn map_colors(ramp: &ColorRamp, data: &[f32], rgba: &mut [f32]) {
+        let vector_width = S::Vf32::WIDTH;
+        let mut value = S::Vf32::zeroes();
+        for (values, pixels) in data
+            .chunks(vector_width)
+            .zip(rgba.chunks_mut(vector_width * 4))
+        {
+            // A partly filled vector for the remainder
+            for (k, v) in values.iter().enumerate() {
+                value[k] = *v;
+            }
+            let color = ramp.apply::<S>(value);
+            for (k, pixel) in pixels.chunks_mut(4).enumerate() {
+                for c in 0..4 {
+                    pixel[c] = color[c][k];
+                }
+            }
+        }
+    }
+);
+
+backend_runtime_generate!(
+    fn map_bytes(ramp: &ColorRamp, data: &[f32], rgba: &mut [u8]) {
+        let vector_width = S::Vf32::WIDTH;
+        let mut value = S::Vf32::zeroes();
+        for (values, pixels) in data
+            .chunks(vector_width)
+            .zip(rgba.chunks_mut(vector_width * 4))
+        {
+            for (k, v) in values.iter().enumerate() {
+                value[k] = *v;
+            }
+            // Rounded half up, the channels are never negative
+            let color = ramp.apply::<S>(value).map(|c| {
+                let c = c.max(S::Vf32::zeroes()).min(S::Vf32::set1(1.0));
+                (c * S::Vf32::set1(255.0) + S::Vf32::set1(0.5))
+                    .floor()
+                    .cast_i32()
+            });
+            for (k, pixel) in pixels.chunks_mut(4).enumerate() {
+                for c in 0..4 {
+                    pixel[c] = color[c][k] as u8;
+                }
+            }
+        }
+    }
+);
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
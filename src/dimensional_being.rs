@@ -1,5 +1,30 @@
 use crate::noise_dimensions::NoiseDimensions;
+use crate::settings::Settings;
 
 pub trait DimensionalBeing {
     fn get_dimensions(&self) -> NoiseDimensions;
 }
+
+/// The frequencies, x to w, that the block helpers multiply the sample coordinates with.
+pub trait Frequency {
+    fn get_frequency(&self) -> [f32; 4];
+}
+
+impl<T: Settings + DimensionalBeing> Frequency for T {
+    // Only ask for the axes in use, cellular noise has no w frequency
+    fn get_frequency(&self) -> [f32; 4] {
+        let dim = self.get_dimensions().dim;
+        let mut frequency = [1.0; 4];
+        frequency[0] = self.get_freq_x();
+        if dim > 1 {
+            frequency[1] = self.get_freq_y();
+        }
+        if dim > 2 {
+            frequency[2] = self.get_freq_z();
+        }
+        if dim > 3 {
+            frequency[3] = self.get_freq_w();
+        }
+        frequency
+    }
+}
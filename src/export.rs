@@ -0,0 +1,538 @@
+//! Writes noise buffers as images.
+//!
+//! The integer formats take values in the range [0, 1], as `generate_scaled(0.0, 1.0)` returns
+//! them; values outside of it are clamped. PFM stores the floats as they are. The first value
+//! of a buffer is the top left pixel, rows run along x.
+//!
+//! ```rust,no_run
+//! use simdnoise::export::{save_image, save_image_stack, ImageFormat};
+//! use simdnoise::*;
+//!
+//! let noise = NoiseBuilder::fbm_2d(256, 128).generate_scaled(0.0, 1.0);
+//! save_image("fbm.png", ImageFormat::Png16, &noise, 256, 128).unwrap();
+//!
+//! // One image per z slice: cells_0000.pgm, cells_0001.pgm, ...
+//! let noise = NoiseBuilder::cellular_3d(64, 64, 16).generate_scaled(0.0, 1.0);
+//! save_image_stack("cells", ImageFormat::Pgm8, &noise, 64, 64, 16).unwrap();
+//! ```
+//!
+//! For analysis the unscaled values can be written as NumPy `.npy` arrays, or as raw
+//! little endian volumes with a detached NRRD header that ParaView, 3D Slicer and most
+//! other volume tools read. Both take the shape slowest axis first, as
+//! `NoiseDimensions::shape` returns it, and both have readers for round trips.
+//!
+//! ```rust,no_run
+//! use simdnoise::export::{load_npy, save_npy, save_raw};
+//! use simdnoise::*;
+//!
+//! let settings = NoiseBuilder::fbm_3d(64, 64, 64);
+//! let shape = settings.wrap().dimensions().shape();
+//! let (noise, _min, _max) = settings.generate();
+//! save_npy("fbm.npy", &noise, &shape).unwrap();
+//! save_raw("fbm.raw", &noise, &shape).unwrap(); // and fbm.nhdr
+//!
+//! let (back, back_shape) = load_npy::<_, f32>("fbm.npy").unwrap();
+//! assert_eq!(back_shape, shape);
+//! ```
+
+use std::fs;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// An image file format for grayscale noise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary PGM with 8 bits per pixel.
+    Pgm8,
+    /// Binary PGM with 16 bits per pixel.
+    Pgm16,
+    /// Portable float map, 32 bit floats.
+    Pfm,
+    /// PNG with 16 bits per pixel, stored without compression.
+    Png16,
+}
+
+impl ImageFormat {
+    /// The file extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Pgm8 | ImageFormat::Pgm16 => "pgm",
+            ImageFormat::Pfm => "pfm",
+            ImageFormat::Png16 => "png",
+        }
+    }
+}
+
+/// Writes a `width` by `height` buffer as an image.
+pub fn write_image<W: Write>(
+    mut writer: W,
+    format: ImageFormat,
+    data: &[f32],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    if width.checked_mul(height) != Some(data.len()) {
+        return Err(invalid_input("the buffer is not width * height long"));
+    }
+    match format {
+        ImageFormat::Pgm8 => {
+            write!(writer, "P5\n{} {}\n255\n", width, height)?;
+            let pixels: Vec<u8> = data.iter().map(|v| quantize(*v, 255) as u8).collect();
+            writer.write_all(&pixels)
+        }
+        ImageFormat::Pgm16 => {
+            write!(writer, "P5\n{} {}\n65535\n", width, height)?;
+            writer.write_all(&samples_16(data))
+        }
+        ImageFormat::Pfm => {
+            // A negative scale means little endian; the rows are stored bottom to top
+            write!(writer, "Pf\n{} {}\n-1.0\n", width, height)?;
+            let mut bytes = Vec::with_capacity(data.len() * 4);
+            for row in data.chunks(width.max(1)).rev() {
+                row.iter().for_each(|v| bytes.extend(&v.to_le_bytes()));
+            }
+            writer.write_all(&bytes)
+        }
+        ImageFormat::Png16 => write_png_16(writer, data, width, height),
+    }
+}
+
+/// Writes an image to a file, see `write_image`.
+pub fn save_image<P: AsRef<Path>>(
+    path: P,
+    format: ImageFormat,
+    data: &[f32],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_image(&mut writer, format, data, width, height)?;
+    writer.flush()
+}
+
+/// Writes a 3d buffer as one image per z slice, named `<path>_<z>.<extension>` with z
+/// padded to four digits, and returns the paths written.
+pub fn save_image_stack<P: AsRef<Path>>(
+    path: P,
+    format: ImageFormat,
+    data: &[f32],
+    width: usize,
+    height: usize,
+    depth: usize,
+) -> io::Result<Vec<PathBuf>> {
+    if width
+        .checked_mul(height)
+        .and_then(|len| len.checked_mul(depth))
+        != Some(data.len())
+    {
+        return Err(invalid_input(
+            "the buffer is not width * height * depth long",
+        ));
+    }
+    let path = path.as_ref();
+    let stem = path.file_name().unwrap_or_default().to_string_lossy();
+    let slice_len = (width * height).max(1);
+    let mut paths = Vec::with_capacity(depth);
+    for (z, slice) in data.chunks(slice_len).enumerate().take(depth) {
+        let slice_path = path.with_file_name(format!("{}_{:04}.{}", stem, z, format.extension()));
+        save_image(&slice_path, format, slice, width, height)?;
+        paths.push(slice_path);
+    }
+    Ok(paths)
+}
+
+/// Writes 8 bit RGB pixels, three bytes each, as a binary PPM.
+pub fn write_ppm<W: Write>(
+    mut writer: W,
+    rgb: &[u8],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    if width.checked_mul(height).and_then(|len| len.checked_mul(3)) != Some(rgb.len()) {
+        return Err(invalid_input("the buffer is not width * height * 3 long"));
+    }
+    write!(writer, "P6\n{} {}\n255\n", width, height)?;
+    writer.write_all(rgb)
+}
+
+/// A number type the data writers store, `f32` or `f64`.
+pub trait Element: Copy + private::Sealed {
+    /// The NumPy type description.
+    const NPY_TYPE: &'static str;
+    /// The NRRD type name.
+    const NRRD_TYPE: &'static str;
+    const SIZE: usize;
+
+    fn write_le(self, out: &mut Vec<u8>);
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+impl Element for f32 {
+    const NPY_TYPE: &'static str = "<f4";
+    const NRRD_TYPE: &'static str = "float";
+    const SIZE: usize = 4;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend(&self.to_le_bytes());
+    }
+
+    fn read_le(bytes: &[u8]) -> f32 {
+        f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+}
+
+impl Element for f64 {
+    const NPY_TYPE: &'static str = "<f8";
+    const NRRD_TYPE: &'static str = "double";
+    const SIZE: usize = 8;
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend(&self.to_le_bytes());
+    }
+
+    fn read_le(bytes: &[u8]) -> f64 {
+        let mut le = [0; 8];
+        le.copy_from_slice(&bytes[..8]);
+        f64::from_le_bytes(le)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Writes a version 1.0 `.npy` array of the given shape, slowest axis first.
+pub fn write_npy<W: Write, T: Element>(
+    mut writer: W,
+    data: &[T],
+    shape: &[usize],
+) -> io::Result<()> {
+    check_shape(data.len(), shape)?;
+    let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({},), }}",
+        T::NPY_TYPE,
+        dims.join(", ")
+    );
+    // The data starts on a multiple of 64 bytes, after a newline
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+    writer.write_all(b"\x93NUMPY\x01\x00")?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(&le_bytes(data))
+}
+
+/// Writes a `.npy` file, see `write_npy`.
+pub fn save_npy<P: AsRef<Path>, T: Element>(
+    path: P,
+    data: &[T],
+    shape: &[usize],
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy(&mut writer, data, shape)?;
+    writer.flush()
+}
+
+/// Reads a `.npy` array of `T` in C order, and its shape.
+pub fn read_npy<R: Read, T: Element>(mut reader: R) -> io::Result<(Vec<T>, Vec<usize>)> {
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != b"\x93NUMPY" {
+        return Err(invalid_data("not a .npy file"));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        _ => return Err(invalid_data("unsupported .npy version")),
+    };
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr =
+        npy_field(&header, "descr").ok_or_else(|| invalid_data("no type in .npy header"))?;
+    if descr.trim_matches(|c| c == '\'' || c == '"') != T::NPY_TYPE {
+        return Err(invalid_data(&format!(
+            "the array holds {}, not {}",
+            descr,
+            T::NPY_TYPE
+        )));
+    }
+    if npy_field(&header, "fortran_order") != Some("False") {
+        return Err(invalid_data("only C order arrays are supported"));
+    }
+    let shape = npy_field(&header, "shape")
+        .and_then(|tuple| {
+            tuple
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(|d| d.parse::<usize>().ok())
+                .collect::<Option<Vec<usize>>>()
+        })
+        .ok_or_else(|| invalid_data("no shape in .npy header"))?;
+    let data = read_elements(reader, &shape)?;
+    Ok((data, shape))
+}
+
+/// Reads a `.npy` file, see `read_npy`.
+pub fn load_npy<P: AsRef<Path>, T: Element>(path: P) -> io::Result<(Vec<T>, Vec<usize>)> {
+    read_npy(BufReader::new(File::open(path)?))
+}
+
+/// Writes the values little endian, without a header, and a detached NRRD header next to
+/// them with the extension `nhdr`. Returns the path of the header.
+pub fn save_raw<P: AsRef<Path>, T: Element>(
+    path: P,
+    data: &[T],
+    shape: &[usize],
+) -> io::Result<PathBuf> {
+    check_shape(data.len(), shape)?;
+    let path = path.as_ref();
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&le_bytes(data))?;
+    writer.flush()?;
+
+    // NRRD lists the sizes fastest axis first
+    let sizes: Vec<String> = shape.iter().rev().map(|d| d.to_string()).collect();
+    let header_path = path.with_extension("nhdr");
+    let data_file = path.file_name().unwrap_or_default().to_string_lossy();
+    fs::write(
+        &header_path,
+        format!(
+            "NRRD0004\ntype: {}\ndimension: {}\nsizes: {}\nendian: little\nencoding: raw\ndata file: {}\n",
+            T::NRRD_TYPE,
+            shape.len(),
+            sizes.join(" "),
+            data_file
+        ),
+    )?;
+    Ok(header_path)
+}
+
+/// Reads a volume written by `save_raw` from its header, and its shape, slowest axis first.
+pub fn load_raw<P: AsRef<Path>, T: Element>(header_path: P) -> io::Result<(Vec<T>, Vec<usize>)> {
+    let header_path = header_path.as_ref();
+    let header = fs::read_to_string(header_path)?;
+    if !header.starts_with("NRRD") {
+        return Err(invalid_data("not a NRRD header"));
+    }
+    let field = |name: &str| {
+        header.lines().find_map(|line| {
+            let (key, value) = line.split_at(line.find(':')?);
+            if key.trim() == name {
+                Some(value[1..].trim())
+            } else {
+                None
+            }
+        })
+    };
+    if field("type") != Some(T::NRRD_TYPE) {
+        return Err(invalid_data(&format!(
+            "the volume does not hold {}",
+            T::NRRD_TYPE
+        )));
+    }
+    if field("encoding") != Some("raw") || field("endian").unwrap_or("little") != "little" {
+        return Err(invalid_data("only raw little endian volumes are supported"));
+    }
+    let mut shape = field("sizes")
+        .and_then(|sizes| {
+            sizes
+                .split_whitespace()
+                .map(|d| d.parse::<usize>().ok())
+                .collect::<Option<Vec<usize>>>()
+        })
+        .ok_or_else(|| invalid_data("no sizes in NRRD header"))?;
+    shape.reverse();
+    let data_file =
+        field("data file").ok_or_else(|| invalid_data("no data file in NRRD header"))?;
+    let data_path = header_path.with_file_name(data_file);
+    let data = read_elements(BufReader::new(File::open(data_path)?), &shape)?;
+    Ok((data, shape))
+}
+
+fn check_shape(len: usize, shape: &[usize]) -> io::Result<()> {
+    if shape.iter().try_fold(1usize, |n, d| n.checked_mul(*d)) != Some(len) {
+        return Err(invalid_input(
+            "the buffer does not have as many values as the shape",
+        ));
+    }
+    Ok(())
+}
+
+fn le_bytes<T: Element>(data: &[T]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.len() * T::SIZE);
+    data.iter().for_each(|v| v.write_le(&mut bytes));
+    bytes
+}
+
+/// Reads the values of an array of `shape`, which comes from a header that may be corrupt.
+fn read_elements<R: Read, T: Element>(reader: R, shape: &[usize]) -> io::Result<Vec<T>> {
+    let byte_len = shape
+        .iter()
+        .try_fold(T::SIZE, |len, d| len.checked_mul(*d))
+        .ok_or_else(|| invalid_data("the shape is too large"))?;
+    // Grown as the data arrives, so a shape larger than the file is never allocated
+    let mut bytes = Vec::new();
+    reader.take(byte_len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != byte_len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the file holds fewer values than its shape",
+        ));
+    }
+    Ok(bytes.chunks(T::SIZE).map(T::read_le).collect())
+}
+
+/// The value of `key` in a `.npy` header dictionary, up to the next comma outside brackets.
+fn npy_field<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{}'", key))? + key.len() + 2;
+    let rest = header[start..].trim_start().strip_prefix(':')?.trim_start();
+    let mut depth = 0;
+    let end = rest
+        .char_indices()
+        .find(|&(_, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            depth == 0 && (c == ',' || c == '}')
+        })
+        .map_or(rest.len(), |(i, _)| i);
+    Some(rest[..end].trim())
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.to_string())
+}
+
+/// `v` from [0, 1] to [0, max].
+fn quantize(v: f32, max: u32) -> u32 {
+    (v.clamp(0.0, 1.0) * max as f32).round() as u32
+}
+
+/// Big endian 16 bit samples, as PGM and PNG store them.
+fn samples_16(data: &[f32]) -> Vec<u8> {
+    data.iter()
+        .flat_map(|v| (quantize(*v, 65535) as u16).to_be_bytes())
+        .collect()
+}
+
+fn write_png_16<W: Write>(
+    mut writer: W,
+    data: &[f32],
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    writer.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(&(width as u32).to_be_bytes());
+    header.extend(&(height as u32).to_be_bytes());
+    // 16 bit grayscale, deflate, adaptive filtering, no interlacing
+    header.extend(&[16, 0, 0, 0, 0]);
+    write_chunk(&mut writer, b"IHDR", &header)?;
+
+    // Every row starts with its filter type, 0 for none
+    let samples = samples_16(data);
+    let mut raw = Vec::with_capacity(samples.len() + height);
+    for row in samples.chunks((width * 2).max(1)).take(height) {
+        raw.push(0);
+        raw.extend(row);
+    }
+    write_chunk(&mut writer, b"IDAT", &zlib_stored(&raw))?;
+    write_chunk(&mut writer, b"IEND", &[])
+}
+
+fn write_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    let crc = crc32(crc32(!0, kind), data);
+    writer.write_all(&(!crc).to_be_bytes())
+}
+
+/// A zlib stream of uncompressed deflate blocks, so no compression library is needed.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = 65535;
+    let mut out = Vec::with_capacity(data.len() + data.len() / BLOCK * 5 + 11);
+    out.extend(&[0x78, 0x01]);
+    let mut blocks = data.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(&len.to_le_bytes());
+        out.extend(&(!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 bytes is the most that can be summed before b can overflow
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+/// Continues a CRC-32 over `data`, start with `!0` and invert the result.
+fn crc32(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |c, byte| {
+        CRC_TABLE[((c ^ *byte as u32) & 0xff) as usize] ^ (c >> 8)
+    })
+}
@@ -0,0 +1,280 @@
+//! f32 noise far from the origin, sampled in f64.
+//!
+//! The f32 block helpers add the sample index to the offset and multiply by the frequency in
+//! f32, so a million samples out neighbouring samples round to the same position and the
+//! noise turns into stairs. From `AlgorithmVersion::V3` positions `FAR` samples or more from
+//! the origin, or `FAR` cells at their finest octave, are worked out in f64 and sampled with
+//! the f64 kernels, which hash their cells like the f32 ones, before the values are rounded
+//! to f32. That takes about twice as long as the f32 kernels. Positions closer to the origin
+//! keep the f32 kernels, bit for bit, so a position gets the same value whichever block it is
+//! generated in.
+//!
+//! Blocks that lie entirely on one side of `FAR` are sampled with one kind of kernel; blocks
+//! that straddle it decide a vector lane at a time. Noise graphs follow their transforms to
+//! find how far their sources sample. Displacement moves a position by at most the amplitude
+//! of its offsets, so graphs displaced by unbounded offsets, such as cellular distances, always
+//! sample in f64. Custom `Sample` noise has no f64 kernels and keeps its f32 positions at any
+//! distance.
+
+use simdeez::prelude::*;
+
+use crate::dimensional_being::{DimensionalBeing, Frequency};
+use crate::noise_helpers_32::Sample32;
+use crate::noise_helpers_64::Sample64;
+use crate::{AlgorithmVersion, CellReturnType, NoiseDimensions, NoiseType};
+
+/// Positions closer to the origin than this, in samples or cells, round to within 2^-12 of
+/// a sample or cell in f32.
+const FAR: f64 = 4096.0;
+
+/// Noise that knows which of its sample positions are far.
+pub(crate) trait FarPositions: DimensionalBeing {
+    /// Whether a source samples far from the origin at a position `reach` samples from it
+    /// along each axis.
+    fn reaches_far(&self, reach: [f64; 4]) -> bool;
+}
+
+impl FarPositions for NoiseType {
+    fn reaches_far(&self, reach: [f64; 4]) -> bool {
+        is_far(reach, finest_frequency(self))
+    }
+}
+
+/// Whether `version` samples far blocks in f64.
+pub(crate) fn samples_far_blocks(version: AlgorithmVersion) -> bool {
+    !matches!(version, AlgorithmVersion::V1 | AlgorithmVersion::V2)
+}
+
+/// How far the block of `dim` reaches from the origin along each axis, in samples.
+pub(crate) fn block_reach(dim: &NoiseDimensions) -> [f64; 4] {
+    let start = [dim.x, dim.y, dim.z, dim.w];
+    let lengths = [dim.width, dim.height, dim.depth, dim.time];
+    let mut reach = [0.0; 4];
+    for axis in 0..dim.dim {
+        let first = start[axis] as f64;
+        let last = first + lengths[axis].saturating_sub(1) as f64;
+        reach[axis] = first.abs().max(last.abs());
+    }
+    reach
+}
+
+/// How close the block of `dim` comes to the origin along each axis, in samples.
+fn block_nearest(dim: &NoiseDimensions) -> [f64; 4] {
+    let start = [dim.x, dim.y, dim.z, dim.w];
+    let lengths = [dim.width, dim.height, dim.depth, dim.time];
+    let mut nearest = [0.0; 4];
+    for axis in 0..dim.dim {
+        let first = start[axis] as f64;
+        let last = first + lengths[axis].saturating_sub(1) as f64;
+        if first > 0.0 || last < 0.0 {
+            nearest[axis] = first.abs().min(last.abs());
+        }
+    }
+    nearest
+}
+
+/// Whether positions up to `reach` samples from the origin are far, at `frequency` cells
+/// per sample.
+pub(crate) fn is_far(reach: [f64; 4], frequency: [f64; 4]) -> bool {
+    reach
+        .iter()
+        .zip(&frequency)
+        .any(|(r, f)| r * f.abs().max(1.0) >= FAR)
+}
+
+/// The cells per sample of the finest octave of `noise_type` along each axis.
+pub(crate) fn finest_frequency(noise_type: &NoiseType) -> [f64; 4] {
+    let scale = match noise_type {
+        NoiseType::Fbm(s) => octave_scale(s.lacunarity, s.octaves),
+        NoiseType::Ridge(s) => octave_scale(s.lacunarity, s.octaves),
+        NoiseType::Turbulence(s) => octave_scale(s.lacunarity, s.octaves),
+        _ => 1.0,
+    };
+    let mut frequency = [0.0; 4];
+    for (f, base) in frequency.iter_mut().zip(&noise_type.get_frequency()) {
+        *f = *base as f64 * scale;
+    }
+    frequency
+}
+
+/// An upper bound of the magnitude of the values of `noise_type`, infinite for cellular
+/// distances, which depend on the jitter and search radius.
+pub(crate) fn amplitude(noise_type: &NoiseType) -> f64 {
+    // Simplex noise stays within about 1.001
+    const SIMPLEX: f64 = 2.0;
+    let octaves = |gain: f32, octaves: u8| {
+        (0..octaves.max(1))
+            .map(|k| (gain as f64).abs().powi(k as i32))
+            .sum::<f64>()
+    };
+    match noise_type {
+        NoiseType::Gradient(_) => SIMPLEX,
+        NoiseType::Fbm(s) => SIMPLEX * octaves(s.gain, s.octaves),
+        NoiseType::Turbulence(s) => SIMPLEX * octaves(s.gain, s.octaves),
+        // Every octave adds 1 minus its weighted magnitude
+        NoiseType::Ridge(s) => s.octaves.max(1) as f64 + SIMPLEX * octaves(s.gain, s.octaves),
+        NoiseType::White(_) => 1.0,
+        NoiseType::Cellular(s) if matches!(s.return_type, CellReturnType::CellValue) => 1.0,
+        NoiseType::Cellular(_) | NoiseType::Cellular2(_) => f64::INFINITY,
+    }
+}
+
+/// How much finer than the first octave the last one samples.
+fn octave_scale(lacunarity: f32, octaves: u8) -> f64 {
+    (lacunarity as f64)
+        .abs()
+        .max(1.0)
+        .powi(octaves.max(1) as i32 - 1)
+}
+
+/// Whether some positions of the block of `settings` are far, so it is sampled with
+/// `for_each_vector_f64`.
+pub(crate) fn is_far_block<Settings: FarPositions>(settings: &Settings) -> bool {
+    let dim = settings.get_dimensions();
+    samples_far_blocks(dim.version) && settings.reaches_far(block_reach(&dim))
+}
+
+/// Whether the block of `noise_type` is sampled with `for_each_vector_f64`.
+pub(crate) fn is_far_noise(noise_type: &NoiseType) -> bool {
+    is_far_block(noise_type)
+}
+
+/// Calls `f` with the samples of a block a vector at a time, like `for_each_vector_f32`, but
+/// with the far positions worked out in f64 and sampled with the f64 kernels.
+#[inline(always)]
+pub(crate) unsafe fn for_each_vector_f64<S, Settings, F>(settings: &Settings, mut f: F)
+where
+    S: Simd,
+    Settings: Sample32<S> + Sample64<S> + Frequency + FarPositions,
+    F: FnMut(S::Vf32, usize),
+{
+    let dim = settings.get_dimensions();
+    // Blocks that do not come near enough to the origin are far everywhere
+    let mixed = !settings.reaches_far(block_nearest(&dim));
+    let frequency = settings.get_frequency();
+    let start = [dim.x as f64, dim.y as f64, dim.z as f64, dim.w as f64];
+    let width = dim.width;
+    let height = if dim.dim > 1 { dim.height } else { 1 };
+    let depth = if dim.dim > 2 { dim.depth } else { 1 };
+    let time = if dim.dim > 3 { dim.time } else { 1 };
+
+    let index = |axis: usize, i: usize| start[axis] + i as f64;
+    let position = |axis: usize, i: usize| index(axis, i) * frequency[axis] as f64;
+    let (wide, narrow) = (S::Vf32::WIDTH, S::Vf64::WIDTH);
+    for frame in 0..time {
+        let w = S::Vf64::set1(position(3, frame));
+        for layer in 0..depth {
+            let z = S::Vf64::set1(position(2, layer));
+            for row in 0..height {
+                let y = S::Vf64::set1(position(1, row));
+                // The reach of the row along the other axes, in samples
+                let mut reach = [0.0; 4];
+                for (axis, i) in [(1, row), (2, layer), (3, frame)] {
+                    if axis < dim.dim {
+                        reach[axis] = index(axis, i).abs();
+                    }
+                }
+                for column in (0..width).step_by(wide) {
+                    let lanes = wide.min(width - column);
+                    let mut far_lanes = (1u32 << lanes) - 1;
+                    if mixed {
+                        for j in 0..lanes {
+                            reach[0] = index(0, column + j).abs();
+                            if !settings.reaches_far(reach) {
+                                far_lanes &= !(1 << j);
+                            }
+                        }
+                    }
+
+                    let mut values = S::Vf32::zeroes();
+                    if far_lanes != (1 << lanes) - 1 {
+                        // The positions and kernels the f32 block helpers use
+                        let mut x = S::Vf32::zeroes();
+                        for j in 0..lanes {
+                            x[j] = index(0, column + j) as f32;
+                        }
+                        let f32_at = |axis: usize, i: usize| {
+                            S::Vf32::set1(index(axis, i) as f32) * S::Vf32::set1(frequency[axis])
+                        };
+                        let x = x * S::Vf32::set1(frequency[0]);
+                        values = match dim.dim {
+                            1 => Sample32::<S>::sample_1d(settings, x),
+                            2 => Sample32::<S>::sample_2d(settings, x, f32_at(1, row)),
+                            3 => Sample32::<S>::sample_3d(
+                                settings,
+                                x,
+                                f32_at(1, row),
+                                f32_at(2, layer),
+                            ),
+                            _ => Sample32::<S>::sample_4d(
+                                settings,
+                                x,
+                                f32_at(1, row),
+                                f32_at(2, layer),
+                                f32_at(3, frame),
+                            ),
+                        };
+                    }
+                    for part in (0..lanes).step_by(narrow) {
+                        let part_lanes = narrow.min(lanes - part);
+                        if (far_lanes >> part) & ((1 << part_lanes) - 1) == 0 {
+                            continue;
+                        }
+                        let mut x = S::Vf64::zeroes();
+                        for j in 0..narrow {
+                            x[j] = position(0, column + part + j);
+                        }
+                        let v = match dim.dim {
+                            1 => Sample64::<S>::sample_1d(settings, x),
+                            2 => Sample64::<S>::sample_2d(settings, x, y),
+                            3 => Sample64::<S>::sample_3d(settings, x, y, z),
+                            _ => Sample64::<S>::sample_4d(settings, x, y, z, w),
+                        };
+                        for j in 0..part_lanes {
+                            if far_lanes & (1 << (part + j)) != 0 {
+                                values[part + j] = v[j] as f32;
+                            }
+                        }
+                    }
+                    f(values, lanes);
+                }
+            }
+        }
+    }
+}
+
+/// The block of `settings` and its min and max, like the f32 block helpers return them,
+/// sampled with `for_each_vector_f64`.
+#[inline(always)]
+pub(crate) unsafe fn get_far_noise<S, Settings>(settings: &Settings) -> (Vec<f32>, f32, f32)
+where
+    S: Simd,
+    Settings: Sample32<S> + Sample64<S> + Frequency + FarPositions,
+{
+    let dim = settings.get_dimensions();
+    let lengths = [dim.width, dim.height, dim.depth, dim.time];
+    let mut result = Vec::with_capacity(lengths[..dim.dim].iter().product());
+    let mut min_s = S::Vf32::set1(f32::MAX);
+    let mut max_s = S::Vf32::set1(f32::MIN);
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for_each_vector_f64::<S, _, _>(settings, |f, lanes| {
+        if lanes == S::Vf32::WIDTH {
+            min_s = min_s.min(f);
+            max_s = max_s.max(f);
+        } else {
+            for j in 0..lanes {
+                min = min.min(f[j]);
+                max = max.max(f[j]);
+            }
+        }
+        for j in 0..lanes {
+            result.push(f[j]);
+        }
+    });
+    for i in 0..S::Vf32::WIDTH {
+        min = min.min(min_s[i]);
+        max = max.max(max_s[i]);
+    }
+    (result, min, max)
+}
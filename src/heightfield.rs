@@ -0,0 +1,155 @@
+//! Surface properties of 2d noise used as a heightfield.
+//!
+//! The maps are built from central differences between neighbouring values. `x` runs along
+//! a row and `y` along increasing rows, so with the first value in the top left corner `y`
+//! points down the image, as in DirectX style normal maps. Flip the green channel for
+//! OpenGL. `strength` scales the heights before the differences are taken, a height
+//! difference of one over one sample is a 45 degree slope.
+
+/// What the neighbours of the values on the edges of the buffer are.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// The edge values are repeated, for single images.
+    Clamp,
+    /// The buffer wraps around, so the maps of tileable noise tile as well.
+    Wrap,
+}
+
+/// Unit surface normals, one `[x, y, z]` vector per value, with `z` pointing out of the
+/// surface.
+pub fn normal_map(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    strength: f32,
+    edges: EdgeMode,
+) -> Vec<[f32; 3]> {
+    gradients(data, width, height, strength, edges)
+        .map(|(dx, dy)| {
+            let length = (dx * dx + dy * dy + 1.0).sqrt();
+            [-dx / length, -dy / length, 1.0 / length]
+        })
+        .collect()
+}
+
+/// The normals of `normal_map` packed as RGB bytes, `0` for -1 and `255` for 1.
+pub fn normal_map_rgb8(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    strength: f32,
+    edges: EdgeMode,
+) -> Vec<u8> {
+    normal_map(data, width, height, strength, edges)
+        .iter()
+        .flatten()
+        .map(|c| ((c * 0.5 + 0.5) * 255.0).round() as u8)
+        .collect()
+}
+
+/// The slope at every value, as the angle to the horizontal in radians, from 0 on flat
+/// ground to just under pi / 2 on cliffs.
+pub fn slope_map(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    strength: f32,
+    edges: EdgeMode,
+) -> Vec<f32> {
+    gradients(data, width, height, strength, edges)
+        .map(|(dx, dy)| (dx * dx + dy * dy).sqrt().atan())
+        .collect()
+}
+
+/// The curvature at every value, the discrete laplacian of the scaled heights. It is
+/// negative on peaks and ridges, positive in pits and valleys, and zero on planes.
+pub fn curvature_map(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    strength: f32,
+    edges: EdgeMode,
+) -> Vec<f32> {
+    let neighbours = Neighbours::new(data, width, height, edges);
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let [left, right, up, down] = neighbours.around(x, y);
+            (left + right + up + down - 4.0 * data[y * width + x]) * strength
+        })
+        .collect()
+}
+
+fn gradients<'a>(
+    data: &'a [f32],
+    width: usize,
+    height: usize,
+    strength: f32,
+    edges: EdgeMode,
+) -> impl Iterator<Item = (f32, f32)> + 'a {
+    let neighbours = Neighbours::new(data, width, height, edges);
+    (0..height)
+        .flat_map(move |y| (0..width).map(move |x| (x, y)))
+        .map(move |(x, y)| {
+            let [left, right, up, down] = neighbours.around(x, y);
+            let (x_span, y_span) = neighbours.spans(x, y);
+            (
+                (right - left) / x_span * strength,
+                (down - up) / y_span * strength,
+            )
+        })
+}
+
+struct Neighbours<'a> {
+    data: &'a [f32],
+    width: usize,
+    height: usize,
+    edges: EdgeMode,
+}
+
+impl<'a> Neighbours<'a> {
+    fn new(data: &'a [f32], width: usize, height: usize, edges: EdgeMode) -> Neighbours<'a> {
+        assert_eq!(
+            data.len(),
+            width * height,
+            "the buffer is not width * height long"
+        );
+        Neighbours {
+            data,
+            width,
+            height,
+            edges,
+        }
+    }
+
+    /// The values left, right, above and below `x`, `y`.
+    fn around(&self, x: usize, y: usize) -> [f32; 4] {
+        let (left, right) = self.step(x, self.width);
+        let (up, down) = self.step(y, self.height);
+        let row = y * self.width;
+        [
+            self.data[row + left],
+            self.data[row + right],
+            self.data[up * self.width + x],
+            self.data[down * self.width + x],
+        ]
+    }
+
+    /// The distances between the neighbours of `x`, `y` along x and y, which are shorter
+    /// than two samples on clamped edges.
+    fn spans(&self, x: usize, y: usize) -> (f32, f32) {
+        let span = |i: usize, len: usize| match self.step(i, len) {
+            (before, after) if self.edges == EdgeMode::Clamp => (after - before).max(1) as f32,
+            _ => 2.0,
+        };
+        (span(x, self.width), span(y, self.height))
+    }
+
+    /// The indices before and after `i` on an axis of length `len`.
+    fn step(&self, i: usize, len: usize) -> (usize, usize) {
+        match self.edges {
+            EdgeMode::Clamp => (i.saturating_sub(1), (i + 1).min(len - 1)),
+            EdgeMode::Wrap => ((i + len - 1) % len, (i + 1) % len),
+        }
+    }
+}
@@ -18,6 +18,8 @@ use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
+use crate::noise::white_32;
+use crate::noise::white_64;
 use crate::noise_helpers_32;
 use crate::noise_helpers_64;
 use crate::shared::scale_noise;
@@ -361,6 +363,79 @@ turbulence!(
     try_transmute_avx2
 );
 
+white!(
+    "1d",
+    white_1d,
+    __m256,
+    SimdTransmuteF32::try_transmute_from_avx2,
+    i32,
+    white_32,
+    try_transmute_avx2
+);
+white!(
+    "2d",
+    white_2d,
+    __m256,
+    SimdTransmuteF32::try_transmute_from_avx2,
+    i32,
+    white_32,
+    try_transmute_avx2
+);
+white!(
+    "3d",
+    white_3d,
+    __m256,
+    SimdTransmuteF32::try_transmute_from_avx2,
+    i32,
+    white_32,
+    try_transmute_avx2
+);
+white!(
+    "4d",
+    white_4d,
+    __m256,
+    SimdTransmuteF32::try_transmute_from_avx2,
+    i32,
+    white_32,
+    try_transmute_avx2
+);
+white!(
+    "1d",
+    white_1d_f64,
+    __m256d,
+    SimdTransmuteF64::try_transmute_from_avx2,
+    i64,
+    white_64,
+    try_transmute_avx2
+);
+white!(
+    "2d",
+    white_2d_f64,
+    __m256d,
+    SimdTransmuteF64::try_transmute_from_avx2,
+    i64,
+    white_64,
+    try_transmute_avx2
+);
+white!(
+    "3d",
+    white_3d_f64,
+    __m256d,
+    SimdTransmuteF64::try_transmute_from_avx2,
+    i64,
+    white_64,
+    try_transmute_avx2
+);
+white!(
+    "4d",
+    white_4d_f64,
+    __m256d,
+    SimdTransmuteF64::try_transmute_from_avx2,
+    i64,
+    white_64,
+    try_transmute_avx2
+);
+
 get_noise!(get_1d_noise, get_1d_noise, f32, noise_helpers_32);
 get_noise!(get_2d_noise, get_2d_noise, f32, noise_helpers_32);
 get_noise!(get_3d_noise, get_3d_noise, f32, noise_helpers_32);
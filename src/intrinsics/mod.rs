@@ -1,10 +1,5 @@
 macro_rules! cellular {
     ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 2d cellular/voroni noise
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -26,11 +21,6 @@ macro_rules! cellular {
         }
     };
     ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 3d cellular/voroni noise
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -57,22 +47,12 @@ macro_rules! cellular {
 
 macro_rules! simplex {
     ("1d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 1d simplex noise, results are not scaled.
         pub unsafe fn $fn_name<S: simdeez::Simd>(x: $f_type, seed: $seed_type) -> $f_type {
             $mod::simplex_1d::<S>($transmute_from(x), seed).$transmute_to()
         }
     };
     ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 2d simplex noise, results are not scaled.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -83,11 +63,6 @@ macro_rules! simplex {
         }
     };
     ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 3d simplex noise, results are not scaled.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -105,11 +80,6 @@ macro_rules! simplex {
         }
     };
     ("4d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 4d simplex noise, results are not scaled.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -132,11 +102,6 @@ macro_rules! simplex {
 
 macro_rules! fbm {
     ("1d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 1d fractal brownian motion.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -156,11 +121,6 @@ macro_rules! fbm {
         }
     };
     ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 2d fractal brownian motion.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -182,11 +142,6 @@ macro_rules! fbm {
         }
     };
     ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 3d fractal brownian motion.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -210,11 +165,6 @@ macro_rules! fbm {
         }
     };
     ("4d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 4d fractal brownian motion.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -242,11 +192,6 @@ macro_rules! fbm {
 }
 macro_rules! ridge {
     ("1d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 1d ridge noise.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -266,11 +211,6 @@ macro_rules! ridge {
         }
     };
     ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 2d ridge noise.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -292,11 +232,6 @@ macro_rules! ridge {
         }
     };
     ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 3d ridge noise.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -320,11 +255,6 @@ macro_rules! ridge {
         }
     };
     ("4d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 4d ridge noise.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -353,11 +283,6 @@ macro_rules! ridge {
 
 macro_rules! turbulence {
     ("1d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 1d turbulence.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -377,11 +302,6 @@ macro_rules! turbulence {
         }
     };
     ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 2d turbulence.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -403,11 +323,6 @@ macro_rules! turbulence {
         }
     };
     ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 3d turbulence.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -431,11 +346,6 @@ macro_rules! turbulence {
         }
     };
     ("4d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
-        #[cfg(any(
-            target_feature = "sse2",
-            target_feature = "sse4.1",
-            target_feature = "avx2"
-        ))]
         /// Get a single value of 4d turbulence.
         pub unsafe fn $fn_name<S: simdeez::Simd>(
             x: $f_type,
@@ -462,6 +372,61 @@ macro_rules! turbulence {
     };
 }
 
+macro_rules! white {
+    ("1d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
+        /// Get a single value of 1d white noise, in the range [-1, 1].
+        pub unsafe fn $fn_name<S: simdeez::Simd>(x: $f_type, seed: $seed_type) -> $f_type {
+            $mod::white_1d::<S>($transmute_from(x), seed).$transmute_to()
+        }
+    };
+    ("2d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
+        /// Get a single value of 2d white noise, in the range [-1, 1].
+        pub unsafe fn $fn_name<S: simdeez::Simd>(
+            x: $f_type,
+            y: $f_type,
+            seed: $seed_type,
+        ) -> $f_type {
+            $mod::white_2d::<S>($transmute_from(x), $transmute_from(y), seed).$transmute_to()
+        }
+    };
+    ("3d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
+        /// Get a single value of 3d white noise, in the range [-1, 1].
+        pub unsafe fn $fn_name<S: simdeez::Simd>(
+            x: $f_type,
+            y: $f_type,
+            z: $f_type,
+            seed: $seed_type,
+        ) -> $f_type {
+            $mod::white_3d::<S>(
+                $transmute_from(x),
+                $transmute_from(y),
+                $transmute_from(z),
+                seed,
+            )
+            .$transmute_to()
+        }
+    };
+    ("4d", $fn_name: ident, $f_type: ty, $transmute_from: path, $seed_type: ty, $mod: ident, $transmute_to: ident) => {
+        /// Get a single value of 4d white noise, in the range [-1, 1].
+        pub unsafe fn $fn_name<S: simdeez::Simd>(
+            x: $f_type,
+            y: $f_type,
+            z: $f_type,
+            w: $f_type,
+            seed: $seed_type,
+        ) -> $f_type {
+            $mod::white_4d::<S>(
+                $transmute_from(x),
+                $transmute_from(y),
+                $transmute_from(z),
+                $transmute_from(w),
+                seed,
+            )
+            .$transmute_to()
+        }
+    };
+}
+
 macro_rules! get_noise {
     ($call: ident, $fn_name: ident, $f_type: ty, $mod: ident) => {
         /// Gets a width sized block of noise, unscaled.
@@ -491,7 +456,11 @@ macro_rules! get_noise_scaled {
     };
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod avx2;
+pub mod portable;
 pub mod scalar;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod sse2;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod sse41;
@@ -0,0 +1,452 @@
+//! Noise functions on a portable SIMD engine.
+//!
+//! The `Portable` engine is built on plain arrays the compiler vectorizes for the target, so
+//! it works on any CPU, also where there is no instruction set specific module. It is slower
+//! than the x86 modules, how much faster than scalar it is depends on the target.
+//!
+//! Use it as the `S` of the functions here, or of the generic noise functions:
+//! `get_3d_noise::<Portable>(&noise_type)`.
+//!
+//! When using the `get_` functions, you will get a performance boost when width
+//! is evenly divisble by 8, and when it is not small relative height and depth.
+
+mod engine;
+
+pub use engine::{
+    F32x8, F64x4, I16x16, I32x8, I64x4, I8x32, Lanes, Portable, SimdTransmutePortableF32,
+    SimdTransmutePortableF64,
+};
+
+use crate::noise::cell_32;
+use crate::noise::cell_64;
+use crate::noise::fbm_32;
+use crate::noise::fbm_64;
+use crate::noise::ridge_32;
+use crate::noise::ridge_64;
+use crate::noise::simplex_32;
+use crate::noise::simplex_64;
+use crate::noise::turbulence_32;
+use crate::noise::turbulence_64;
+use crate::noise::white_32;
+use crate::noise::white_64;
+use crate::noise_helpers_32;
+use crate::noise_helpers_64;
+use crate::shared::scale_noise;
+use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
+
+use std::f32;
+
+cellular!(
+    "2d",
+    cellular_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    cell_32,
+    try_transmute_portable
+);
+cellular!(
+    "3d",
+    cellular_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    cell_32,
+    try_transmute_portable
+);
+cellular!(
+    "2d",
+    cellular_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    cell_64,
+    try_transmute_portable
+);
+cellular!(
+    "3d",
+    cellular_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    cell_64,
+    try_transmute_portable
+);
+
+simplex!(
+    "1d",
+    simplex_1d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    simplex_32,
+    try_transmute_portable
+);
+simplex!(
+    "2d",
+    simplex_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    simplex_32,
+    try_transmute_portable
+);
+simplex!(
+    "3d",
+    simplex_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    simplex_32,
+    try_transmute_portable
+);
+simplex!(
+    "4d",
+    simplex_4d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    simplex_32,
+    try_transmute_portable
+);
+simplex!(
+    "1d",
+    simplex_1d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    simplex_64,
+    try_transmute_portable
+);
+simplex!(
+    "2d",
+    simplex_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    simplex_64,
+    try_transmute_portable
+);
+simplex!(
+    "3d",
+    simplex_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    simplex_64,
+    try_transmute_portable
+);
+simplex!(
+    "4d",
+    simplex_4d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    simplex_64,
+    try_transmute_portable
+);
+
+fbm!(
+    "1d",
+    fbm_1d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    fbm_32,
+    try_transmute_portable
+);
+fbm!(
+    "2d",
+    fbm_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    fbm_32,
+    try_transmute_portable
+);
+fbm!(
+    "3d",
+    fbm_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    fbm_32,
+    try_transmute_portable
+);
+fbm!(
+    "4d",
+    fbm_4d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    fbm_32,
+    try_transmute_portable
+);
+fbm!(
+    "1d",
+    fbm_1d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    fbm_64,
+    try_transmute_portable
+);
+fbm!(
+    "2d",
+    fbm_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    fbm_64,
+    try_transmute_portable
+);
+fbm!(
+    "3d",
+    fbm_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    fbm_64,
+    try_transmute_portable
+);
+fbm!(
+    "4d",
+    fbm_4d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    fbm_64,
+    try_transmute_portable
+);
+
+ridge!(
+    "1d",
+    ridge_1d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    ridge_32,
+    try_transmute_portable
+);
+ridge!(
+    "2d",
+    ridge_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    ridge_32,
+    try_transmute_portable
+);
+ridge!(
+    "3d",
+    ridge_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    ridge_32,
+    try_transmute_portable
+);
+ridge!(
+    "4d",
+    ridge_4d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    ridge_32,
+    try_transmute_portable
+);
+ridge!(
+    "1d",
+    ridge_1d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    ridge_64,
+    try_transmute_portable
+);
+ridge!(
+    "2d",
+    ridge_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    ridge_64,
+    try_transmute_portable
+);
+ridge!(
+    "3d",
+    ridge_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    ridge_64,
+    try_transmute_portable
+);
+ridge!(
+    "4d",
+    ridge_4d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    ridge_64,
+    try_transmute_portable
+);
+
+turbulence!(
+    "1d",
+    turbulence_1d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    turbulence_32,
+    try_transmute_portable
+);
+turbulence!(
+    "2d",
+    turbulence_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    turbulence_32,
+    try_transmute_portable
+);
+turbulence!(
+    "3d",
+    turbulence_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    turbulence_32,
+    try_transmute_portable
+);
+turbulence!(
+    "4d",
+    turbulence_4d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    turbulence_32,
+    try_transmute_portable
+);
+turbulence!(
+    "1d",
+    turbulence_1d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    turbulence_64,
+    try_transmute_portable
+);
+turbulence!(
+    "2d",
+    turbulence_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    turbulence_64,
+    try_transmute_portable
+);
+turbulence!(
+    "3d",
+    turbulence_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    turbulence_64,
+    try_transmute_portable
+);
+turbulence!(
+    "4d",
+    turbulence_4d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    turbulence_64,
+    try_transmute_portable
+);
+
+white!(
+    "1d",
+    white_1d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    white_32,
+    try_transmute_portable
+);
+white!(
+    "2d",
+    white_2d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    white_32,
+    try_transmute_portable
+);
+white!(
+    "3d",
+    white_3d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    white_32,
+    try_transmute_portable
+);
+white!(
+    "4d",
+    white_4d,
+    F32x8,
+    SimdTransmutePortableF32::try_transmute_from_portable,
+    i32,
+    white_32,
+    try_transmute_portable
+);
+white!(
+    "1d",
+    white_1d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    white_64,
+    try_transmute_portable
+);
+white!(
+    "2d",
+    white_2d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    white_64,
+    try_transmute_portable
+);
+white!(
+    "3d",
+    white_3d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    white_64,
+    try_transmute_portable
+);
+white!(
+    "4d",
+    white_4d_f64,
+    F64x4,
+    SimdTransmutePortableF64::try_transmute_from_portable,
+    i64,
+    white_64,
+    try_transmute_portable
+);
+
+get_noise!(get_1d_noise, get_1d_noise, f32, noise_helpers_32);
+get_noise!(get_2d_noise, get_2d_noise, f32, noise_helpers_32);
+get_noise!(get_3d_noise, get_3d_noise, f32, noise_helpers_32);
+get_noise!(get_4d_noise, get_4d_noise, f32, noise_helpers_32);
+get_noise!(get_1d_noise, get_1d_noise_64, f64, noise_helpers_64);
+get_noise!(get_2d_noise, get_2d_noise_64, f64, noise_helpers_64);
+get_noise!(get_3d_noise, get_3d_noise_64, f64, noise_helpers_64);
+get_noise!(get_4d_noise, get_4d_noise_64, f64, noise_helpers_64);
+get_noise_scaled!(get_1d_noise, get_1d_scaled_noise, f32);
+get_noise_scaled!(get_2d_noise, get_2d_scaled_noise, f32);
+get_noise_scaled!(get_3d_noise, get_3d_scaled_noise, f32);
+get_noise_scaled!(get_4d_noise, get_4d_scaled_noise, f32);
@@ -0,0 +1,891 @@
+//! A simdeez engine on plain arrays.
+//!
+//! Every operation is a loop over the lanes, which the compiler vectorizes for whatever target
+//! it builds for. The lanes are as wide as AVX2, and the results follow the scalar engine,
+//! except that floats are rounded to the nearest integer with ties to even, like the x86
+//! instruction sets do.
+
+use core::ops::*;
+
+use simdeez::prelude::*;
+use simdeez::{
+    SimdTransmuteF32, SimdTransmuteF64, SimdTransmuteI16, SimdTransmuteI32, SimdTransmuteI64,
+    SimdTransmuteI8,
+};
+
+use self::lane::{FloatLane, IntLane, Lane};
+
+/// The portable engine, use it as the `S` of the generic noise functions.
+pub struct Portable;
+
+impl Simd for Portable {
+    type Vi8 = I8x32;
+    type Vi16 = I16x16;
+    type Vi32 = I32x8;
+    type Vi64 = I64x4;
+    type Vf32 = F32x8;
+    type Vf64 = F64x4;
+
+    #[inline(always)]
+    fn invoke<R>(f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+/// A vector of `N` lanes of `T`.
+#[derive(Copy, Clone, Debug)]
+#[repr(C, align(32))]
+pub struct Lanes<T, const N: usize>(pub [T; N]);
+
+pub type I8x32 = Lanes<i8, 32>;
+pub type I16x16 = Lanes<i16, 16>;
+pub type I32x8 = Lanes<i32, 8>;
+pub type I64x4 = Lanes<i64, 4>;
+pub type F32x8 = Lanes<f32, 8>;
+pub type F64x4 = Lanes<f64, 4>;
+
+impl<T: Lane, const N: usize> Lanes<T, N> {
+    #[inline(always)]
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Lanes(core::array::from_fn(|i| f(self.0[i])))
+    }
+
+    #[inline(always)]
+    fn zip(self, rhs: Self, f: impl Fn(T, T) -> T) -> Self {
+        Lanes(core::array::from_fn(|i| f(self.0[i], rhs.0[i])))
+    }
+
+    #[inline(always)]
+    fn zip_bits(self, rhs: Self, f: impl Fn(T::Bits, T::Bits) -> T::Bits) -> Self {
+        self.zip(rhs, |a, b| T::from_bits(f(a.to_bits(), b.to_bits())))
+    }
+
+    #[inline(always)]
+    fn compare(self, rhs: Self, f: impl Fn(T, T) -> bool) -> Self {
+        self.zip(rhs, |a, b| T::mask(f(a, b)))
+    }
+}
+
+/// Lane by lane conversion, `U` has to have `N` lanes too.
+#[inline(always)]
+fn convert<T: Copy, U, const N: usize>(a: [T; N], f: impl Fn(T) -> U) -> [U; N] {
+    core::array::from_fn(|i| f(a[i]))
+}
+
+/// Widens the lower and the upper half of `a` separately.
+#[inline(always)]
+fn widen<T: Copy, U, const N: usize, const M: usize>(
+    a: [T; N],
+    f: impl Fn(T) -> U,
+) -> ([U; M], [U; M]) {
+    (
+        core::array::from_fn(|i| f(a[i])),
+        core::array::from_fn(|i| f(a[i + M])),
+    )
+}
+
+impl<T: Lane, const N: usize> SimdConsts for Lanes<T, N> {
+    type Scalar = T;
+    type HorizontalAddScalar = T::Sum;
+    const WIDTH: usize = N;
+    type ArrayRepresentation = [T; N];
+    type UnderlyingType = [T; N];
+    type Engine = Portable;
+}
+
+impl<T: Lane, const N: usize> SimdBaseIo for Lanes<T, N> {
+    #[inline(always)]
+    fn zeroes() -> Self {
+        Lanes([T::default(); N])
+    }
+
+    #[inline(always)]
+    fn set1(x: T) -> Self {
+        Lanes([x; N])
+    }
+
+    #[inline(always)]
+    unsafe fn load_from_array(array: [T; N]) -> Self {
+        Lanes(array)
+    }
+
+    #[inline(always)]
+    unsafe fn load_from_ptr_unaligned(ptr: *const T) -> Self {
+        Lanes(core::ptr::read_unaligned(ptr as *const [T; N]))
+    }
+
+    #[inline(always)]
+    unsafe fn copy_to_ptr_unaligned(self, ptr: *mut T) {
+        core::ptr::write_unaligned(ptr as *mut [T; N], self.0)
+    }
+
+    #[inline(always)]
+    unsafe fn load_from_ptr_aligned(ptr: *const T) -> Self {
+        Self::load_from_ptr_unaligned(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn copy_to_ptr_aligned(self, ptr: *mut T) {
+        self.copy_to_ptr_unaligned(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn underlying_value(self) -> [T; N] {
+        self.0
+    }
+
+    #[inline(always)]
+    unsafe fn underlying_value_mut(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+
+    #[inline(always)]
+    unsafe fn from_underlying_value(value: [T; N]) -> Self {
+        Lanes(value)
+    }
+}
+
+impl<T: Lane, const N: usize> SimdBaseOps for Lanes<T, N> {
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_add)
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_sub)
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_mul)
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: Self) -> Self {
+        self.zip_bits(rhs, |a, b| a & b)
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: Self) -> Self {
+        self.zip_bits(rhs, |a, b| a | b)
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: Self) -> Self {
+        self.zip_bits(rhs, |a, b| a ^ b)
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> Self {
+        self.map(|a| T::from_bits(!a.to_bits()))
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        self.map(T::lane_abs)
+    }
+
+    #[inline(always)]
+    fn and_not(self, rhs: Self) -> Self {
+        self.zip_bits(rhs, |a, b| a & !b)
+    }
+
+    #[inline(always)]
+    fn blendv(self, a: Self, b: Self) -> Self {
+        Lanes(core::array::from_fn(|i| {
+            if self.0[i].to_bits() == T::Bits::default() {
+                a.0[i]
+            } else {
+                b.0[i]
+            }
+        }))
+    }
+
+    #[inline(always)]
+    fn cmp_eq(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a == b)
+    }
+
+    #[inline(always)]
+    fn cmp_neq(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a != b)
+    }
+
+    #[inline(always)]
+    fn cmp_lt(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a < b)
+    }
+
+    #[inline(always)]
+    fn cmp_lte(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a <= b)
+    }
+
+    #[inline(always)]
+    fn cmp_gt(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a > b)
+    }
+
+    #[inline(always)]
+    fn cmp_gte(self, rhs: Self) -> Self {
+        self.compare(rhs, |a, b| a >= b)
+    }
+
+    #[inline(always)]
+    fn max(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_max)
+    }
+
+    #[inline(always)]
+    fn min(self, rhs: Self) -> Self {
+        self.zip(rhs, T::lane_min)
+    }
+
+    #[inline(always)]
+    fn horizontal_add(self) -> T::Sum {
+        T::sum(&self.0)
+    }
+}
+
+impl<T: IntLane, const N: usize> SimdInt for Lanes<T, N> {
+    #[inline(always)]
+    fn shl(self, rhs: i32) -> Self {
+        self.map(|a| a.lane_shl(rhs))
+    }
+
+    #[inline(always)]
+    fn shr(self, rhs: i32) -> Self {
+        self.map(|a| a.lane_shr(rhs))
+    }
+
+    #[inline(always)]
+    fn horizontal_unsigned_add(self) -> i64 {
+        self.0
+            .iter()
+            .map(|a| a.unsigned_i64())
+            .fold(0, i64::wrapping_add)
+    }
+
+    #[inline(always)]
+    fn from_i64(value: i64) -> Self {
+        Self::set1(T::from_i64(value))
+    }
+}
+
+impl<T: FloatLane, const N: usize> SimdFloat for Lanes<T, N> {
+    #[inline(always)]
+    fn div(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| a / b)
+    }
+
+    #[inline(always)]
+    fn ceil(self) -> Self {
+        self.map(T::lane_ceil)
+    }
+
+    #[inline(always)]
+    fn floor(self) -> Self {
+        self.map(T::lane_floor)
+    }
+
+    #[inline(always)]
+    fn round(self) -> Self {
+        self.map(T::lane_round)
+    }
+
+    #[inline(always)]
+    fn fast_ceil(self) -> Self {
+        self.ceil()
+    }
+
+    #[inline(always)]
+    fn fast_floor(self) -> Self {
+        self.floor()
+    }
+
+    #[inline(always)]
+    fn fast_round(self) -> Self {
+        self.round()
+    }
+
+    #[inline(always)]
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    #[inline(always)]
+    fn mul_sub(self, a: Self, b: Self) -> Self {
+        self * a - b
+    }
+
+    #[inline(always)]
+    fn neg_mul_add(self, a: Self, b: Self) -> Self {
+        b - self * a
+    }
+
+    #[inline(always)]
+    fn neg_mul_sub(self, a: Self, b: Self) -> Self {
+        -self * a - b
+    }
+
+    #[inline(always)]
+    fn sqrt(self) -> Self {
+        self.map(T::lane_sqrt)
+    }
+
+    #[inline(always)]
+    fn rsqrt(self) -> Self {
+        self.map(|a| T::from_f64(1.0) / a.lane_sqrt())
+    }
+
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        Self::set1(T::from_f64(value))
+    }
+}
+
+impl SimdInt8 for I8x32 {
+    #[inline(always)]
+    fn extend_to_i16(self) -> (I16x16, I16x16) {
+        let (a, b) = widen(self.0, |a| a as i16);
+        (Lanes(a), Lanes(b))
+    }
+
+    #[inline(always)]
+    fn unsigned_extend_to_i16(self) -> (I16x16, I16x16) {
+        let (a, b) = widen(self.0, |a| a as u8 as i16);
+        (Lanes(a), Lanes(b))
+    }
+
+    #[inline(always)]
+    fn get_mask(self) -> u32 {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (i, a)| mask | (((*a as u8 >> 7) as u32) << i))
+    }
+
+    #[inline(always)]
+    fn is_truthy(self) -> bool {
+        self.get_mask() == u32::MAX
+    }
+}
+
+impl SimdInt16 for I16x16 {
+    #[inline(always)]
+    fn extend_to_i32(self) -> (I32x8, I32x8) {
+        let (a, b) = widen(self.0, |a| a as i32);
+        (Lanes(a), Lanes(b))
+    }
+
+    #[inline(always)]
+    fn unsigned_extend_to_i32(self) -> (I32x8, I32x8) {
+        let (a, b) = widen(self.0, |a| a as u16 as i32);
+        (Lanes(a), Lanes(b))
+    }
+}
+
+impl SimdInt32 for I32x8 {
+    #[inline(always)]
+    fn bitcast_f32(self) -> F32x8 {
+        Lanes(convert(self.0, |a| f32::from_bits(a as u32)))
+    }
+
+    #[inline(always)]
+    fn cast_f32(self) -> F32x8 {
+        Lanes(convert(self.0, |a| a as f32))
+    }
+
+    #[inline(always)]
+    fn extend_to_i64(self) -> (I64x4, I64x4) {
+        let (a, b) = widen(self.0, |a| a as i64);
+        (Lanes(a), Lanes(b))
+    }
+
+    #[inline(always)]
+    fn unsigned_extend_to_i64(self) -> (I64x4, I64x4) {
+        let (a, b) = widen(self.0, |a| a as u32 as i64);
+        (Lanes(a), Lanes(b))
+    }
+}
+
+impl SimdInt64 for I64x4 {
+    #[inline(always)]
+    fn bitcast_f64(self) -> F64x4 {
+        Lanes(convert(self.0, |a| f64::from_bits(a as u64)))
+    }
+
+    #[inline(always)]
+    fn cast_f64(self) -> F64x4 {
+        Lanes(convert(self.0, |a| a as f64))
+    }
+
+    #[inline(always)]
+    fn partial_horizontal_add(self) -> i64 {
+        self.horizontal_add()
+    }
+}
+
+impl SimdFloat32 for F32x8 {
+    #[inline(always)]
+    fn bitcast_i32(self) -> I32x8 {
+        Lanes(convert(self.0, |a| a.to_bits() as i32))
+    }
+
+    #[inline(always)]
+    fn cast_i32(self) -> I32x8 {
+        // Out of range values become i32::MIN, as on x86
+        Lanes(convert(self.0, |a| {
+            if a.abs() < 2147483648.0 {
+                a.round_ties_even() as i32
+            } else {
+                i32::MIN
+            }
+        }))
+    }
+
+    #[inline(always)]
+    fn fast_inverse(self) -> Self {
+        self.map(|a| 1.0 / a)
+    }
+}
+
+impl SimdFloat64 for F64x4 {
+    #[inline(always)]
+    fn bitcast_i64(self) -> I64x4 {
+        Lanes(convert(self.0, |a| a.to_bits() as i64))
+    }
+
+    #[inline(always)]
+    fn cast_i64(self) -> I64x4 {
+        // The x86 engines round these one lane at a time, ties away from zero
+        Lanes(convert(self.0, |a| a.round() as i64))
+    }
+}
+
+impl SimdTransmuteI8 for I8x32 {}
+impl SimdTransmuteI16 for I16x16 {}
+impl SimdTransmuteI32 for I32x8 {}
+impl SimdTransmuteI64 for I64x4 {}
+impl SimdTransmuteF32 for F32x8 {}
+impl SimdTransmuteF64 for F64x4 {}
+
+/// Converts an engine's f32 vectors from and to the portable ones, like simdeez's
+/// `try_transmute_*` functions do for the other instruction sets.
+pub trait SimdTransmutePortableF32: Sized {
+    /// Panics if the vector is not 8 lanes wide.
+    fn try_transmute_portable(&self) -> F32x8;
+
+    /// Panics if the vector is not 8 lanes wide.
+    fn try_transmute_from_portable(portable: F32x8) -> Self;
+}
+
+/// Converts an engine's f64 vectors from and to the portable ones.
+pub trait SimdTransmutePortableF64: Sized {
+    /// Panics if the vector is not 4 lanes wide.
+    fn try_transmute_portable(&self) -> F64x4;
+
+    /// Panics if the vector is not 4 lanes wide.
+    fn try_transmute_from_portable(portable: F64x4) -> Self;
+}
+
+impl<V: SimdFloat32> SimdTransmutePortableF32 for V {
+    #[inline(always)]
+    fn try_transmute_portable(&self) -> F32x8 {
+        assert_eq!(
+            V::WIDTH,
+            8,
+            "Invalid transmute: tried to transmute non-portable into portable"
+        );
+        let mut portable = F32x8::zeroes();
+        unsafe { self.copy_to_ptr_unaligned(portable.0.as_mut_ptr()) };
+        portable
+    }
+
+    #[inline(always)]
+    fn try_transmute_from_portable(portable: F32x8) -> Self {
+        assert_eq!(
+            V::WIDTH,
+            8,
+            "Invalid transmute: tried to transmute portable into non-portable"
+        );
+        unsafe { V::load_from_ptr_unaligned(portable.0.as_ptr()) }
+    }
+}
+
+impl<V: SimdFloat64> SimdTransmutePortableF64 for V {
+    #[inline(always)]
+    fn try_transmute_portable(&self) -> F64x4 {
+        assert_eq!(
+            V::WIDTH,
+            4,
+            "Invalid transmute: tried to transmute non-portable into portable"
+        );
+        let mut portable = F64x4::zeroes();
+        unsafe { self.copy_to_ptr_unaligned(portable.0.as_mut_ptr()) };
+        portable
+    }
+
+    #[inline(always)]
+    fn try_transmute_from_portable(portable: F64x4) -> Self {
+        assert_eq!(
+            V::WIDTH,
+            4,
+            "Invalid transmute: tried to transmute portable into non-portable"
+        );
+        unsafe { V::load_from_ptr_unaligned(portable.0.as_ptr()) }
+    }
+}
+
+impl<T: Lane, const N: usize> Index<usize> for Lanes<T, N> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T: Lane, const N: usize> IndexMut<usize> for Lanes<T, N> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Lane, const N: usize> Neg for Lanes<T, N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::zeroes() - self
+    }
+}
+
+impl<T: Lane, const N: usize> Not for Lanes<T, N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn not(self) -> Self {
+        self.bit_not()
+    }
+}
+
+/// The binary operators, with a vector or a scalar on the right hand side.
+macro_rules! impl_operator {
+    ($bound: ident, $op: ident, $op_fn: ident, $assign: ident, $assign_fn: ident, $call: path) => {
+        impl<T: $bound, const N: usize> $op<Self> for Lanes<T, N> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $op_fn(self, rhs: Self) -> Self {
+                $call(self, rhs)
+            }
+        }
+
+        impl<T: $bound, const N: usize> $op<T> for Lanes<T, N> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $op_fn(self, rhs: T) -> Self {
+                $call(self, Self::set1(rhs))
+            }
+        }
+
+        impl<T: $bound, const N: usize> $assign<Self> for Lanes<T, N> {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $call(*self, rhs);
+            }
+        }
+
+        impl<T: $bound, const N: usize> $assign<T> for Lanes<T, N> {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: T) {
+                *self = $call(*self, Self::set1(rhs));
+            }
+        }
+    };
+}
+
+impl_operator!(Lane, Add, add, AddAssign, add_assign, SimdBaseOps::add);
+impl_operator!(Lane, Sub, sub, SubAssign, sub_assign, SimdBaseOps::sub);
+impl_operator!(Lane, Mul, mul, MulAssign, mul_assign, SimdBaseOps::mul);
+impl_operator!(
+    Lane,
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    SimdBaseOps::bit_and
+);
+impl_operator!(
+    Lane,
+    BitOr,
+    bitor,
+    BitOrAssign,
+    bitor_assign,
+    SimdBaseOps::bit_or
+);
+impl_operator!(
+    Lane,
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    SimdBaseOps::bit_xor
+);
+impl_operator!(FloatLane, Div, div, DivAssign, div_assign, SimdFloat::div);
+
+impl<T: IntLane, const N: usize> Shl<i32> for Lanes<T, N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn shl(self, rhs: i32) -> Self {
+        SimdInt::shl(self, rhs)
+    }
+}
+
+impl<T: IntLane, const N: usize> Shr<i32> for Lanes<T, N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn shr(self, rhs: i32) -> Self {
+        SimdInt::shr(self, rhs)
+    }
+}
+
+impl<T: IntLane, const N: usize> ShlAssign<i32> for Lanes<T, N> {
+    #[inline(always)]
+    fn shl_assign(&mut self, rhs: i32) {
+        *self = SimdInt::shl(*self, rhs);
+    }
+}
+
+impl<T: IntLane, const N: usize> ShrAssign<i32> for Lanes<T, N> {
+    #[inline(always)]
+    fn shr_assign(&mut self, rhs: i32) {
+        *self = SimdInt::shr(*self, rhs);
+    }
+}
+
+/// What a single lane can do. The traits are public, in a private module, so they can bound
+/// the public impls without being implementable outside of this file.
+mod lane {
+    use core::fmt::Debug;
+    use core::ops::*;
+
+    pub trait Lane: Copy + Debug + Default + PartialOrd + Send + Sync + 'static {
+        type Sum: Copy + Debug + Send + Sync + 'static;
+        type Bits: Copy
+            + Default
+            + PartialEq
+            + BitAnd<Output = Self::Bits>
+            + BitOr<Output = Self::Bits>
+            + BitXor<Output = Self::Bits>
+            + Not<Output = Self::Bits>;
+
+        fn to_bits(self) -> Self::Bits;
+        fn from_bits(bits: Self::Bits) -> Self;
+        fn lane_add(self, rhs: Self) -> Self;
+        fn lane_sub(self, rhs: Self) -> Self;
+        fn lane_mul(self, rhs: Self) -> Self;
+        fn lane_abs(self) -> Self;
+        fn lane_min(self, rhs: Self) -> Self;
+        fn lane_max(self, rhs: Self) -> Self;
+        fn sum(lanes: &[Self]) -> Self::Sum;
+
+        /// All bits set when true, like the comparisons of the instruction sets.
+        #[inline(always)]
+        fn mask(set: bool) -> Self {
+            if set {
+                Self::from_bits(!Self::Bits::default())
+            } else {
+                Self::from_bits(Self::Bits::default())
+            }
+        }
+    }
+
+    pub trait IntLane: Lane<Sum = i64> {
+        /// Shifts in zeroes, and shifts everything out when shifting by the width or more.
+        fn lane_shl(self, rhs: i32) -> Self;
+        fn lane_shr(self, rhs: i32) -> Self;
+        fn unsigned_i64(self) -> i64;
+        fn from_i64(value: i64) -> Self;
+    }
+
+    pub trait FloatLane: Lane<Sum = Self> + Div<Output = Self> {
+        fn lane_ceil(self) -> Self;
+        fn lane_floor(self) -> Self;
+        /// Ties to even, like the rounding instructions.
+        fn lane_round(self) -> Self;
+        fn lane_sqrt(self) -> Self;
+        fn from_f64(value: f64) -> Self;
+    }
+
+    macro_rules! int_lane {
+        ($int: ty, $unsigned: ty) => {
+            impl Lane for $int {
+                type Sum = i64;
+                type Bits = $unsigned;
+
+                #[inline(always)]
+                fn to_bits(self) -> $unsigned {
+                    self as $unsigned
+                }
+
+                #[inline(always)]
+                fn from_bits(bits: $unsigned) -> $int {
+                    bits as $int
+                }
+
+                #[inline(always)]
+                fn lane_add(self, rhs: $int) -> $int {
+                    self.wrapping_add(rhs)
+                }
+
+                #[inline(always)]
+                fn lane_sub(self, rhs: $int) -> $int {
+                    self.wrapping_sub(rhs)
+                }
+
+                #[inline(always)]
+                fn lane_mul(self, rhs: $int) -> $int {
+                    self.wrapping_mul(rhs)
+                }
+
+                #[inline(always)]
+                fn lane_abs(self) -> $int {
+                    self.wrapping_abs()
+                }
+
+                #[inline(always)]
+                fn lane_min(self, rhs: $int) -> $int {
+                    Ord::min(self, rhs)
+                }
+
+                #[inline(always)]
+                fn lane_max(self, rhs: $int) -> $int {
+                    Ord::max(self, rhs)
+                }
+
+                #[inline(always)]
+                fn sum(lanes: &[$int]) -> i64 {
+                    lanes.iter().fold(0, |sum, a| sum.wrapping_add(*a as i64))
+                }
+            }
+
+            impl IntLane for $int {
+                #[inline(always)]
+                fn lane_shl(self, rhs: i32) -> $int {
+                    (self as $unsigned).checked_shl(rhs as u32).unwrap_or(0) as $int
+                }
+
+                #[inline(always)]
+                fn lane_shr(self, rhs: i32) -> $int {
+                    (self as $unsigned).checked_shr(rhs as u32).unwrap_or(0) as $int
+                }
+
+                #[inline(always)]
+                fn unsigned_i64(self) -> i64 {
+                    self as $unsigned as i64
+                }
+
+                #[inline(always)]
+                fn from_i64(value: i64) -> $int {
+                    value as $int
+                }
+            }
+        };
+    }
+
+    int_lane!(i8, u8);
+    int_lane!(i16, u16);
+    int_lane!(i32, u32);
+    int_lane!(i64, u64);
+
+    macro_rules! float_lane {
+        ($float: ty, $bits: ty) => {
+            impl Lane for $float {
+                type Sum = $float;
+                type Bits = $bits;
+
+                #[inline(always)]
+                fn to_bits(self) -> $bits {
+                    <$float>::to_bits(self)
+                }
+
+                #[inline(always)]
+                fn from_bits(bits: $bits) -> $float {
+                    <$float>::from_bits(bits)
+                }
+
+                #[inline(always)]
+                fn lane_add(self, rhs: $float) -> $float {
+                    self + rhs
+                }
+
+                #[inline(always)]
+                fn lane_sub(self, rhs: $float) -> $float {
+                    self - rhs
+                }
+
+                #[inline(always)]
+                fn lane_mul(self, rhs: $float) -> $float {
+                    self * rhs
+                }
+
+                #[inline(always)]
+                fn lane_abs(self) -> $float {
+                    self.abs()
+                }
+
+                #[inline(always)]
+                fn lane_min(self, rhs: $float) -> $float {
+                    self.min(rhs)
+                }
+
+                #[inline(always)]
+                fn lane_max(self, rhs: $float) -> $float {
+                    self.max(rhs)
+                }
+
+                #[inline(always)]
+                fn sum(lanes: &[$float]) -> $float {
+                    lanes.iter().sum()
+                }
+            }
+
+            impl FloatLane for $float {
+                #[inline(always)]
+                fn lane_ceil(self) -> $float {
+                    self.ceil()
+                }
+
+                #[inline(always)]
+                fn lane_floor(self) -> $float {
+                    self.floor()
+                }
+
+                #[inline(always)]
+                fn lane_round(self) -> $float {
+                    self.round_ties_even()
+                }
+
+                #[inline(always)]
+                fn lane_sqrt(self) -> $float {
+                    self.sqrt()
+                }
+
+                #[inline(always)]
+                fn from_f64(value: f64) -> $float {
+                    value as $float
+                }
+            }
+        };
+    }
+
+    float_lane!(f32, u32);
+    float_lane!(f64, u64);
+}
@@ -12,6 +12,8 @@ use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
+use crate::noise::white_32;
+use crate::noise::white_64;
 use crate::noise_helpers_32;
 use crate::noise_helpers_64;
 use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
@@ -350,6 +352,79 @@ turbulence!(
     try_transmute_scalar
 );
 
+white!(
+    "1d",
+    white_1d,
+    f32,
+    SimdTransmuteF32::try_transmute_from_scalar,
+    i32,
+    white_32,
+    try_transmute_scalar
+);
+white!(
+    "2d",
+    white_2d,
+    f32,
+    SimdTransmuteF32::try_transmute_from_scalar,
+    i32,
+    white_32,
+    try_transmute_scalar
+);
+white!(
+    "3d",
+    white_3d,
+    f32,
+    SimdTransmuteF32::try_transmute_from_scalar,
+    i32,
+    white_32,
+    try_transmute_scalar
+);
+white!(
+    "4d",
+    white_4d,
+    f32,
+    SimdTransmuteF32::try_transmute_from_scalar,
+    i32,
+    white_32,
+    try_transmute_scalar
+);
+white!(
+    "1d",
+    white_1d_f64,
+    f64,
+    SimdTransmuteF64::try_transmute_from_scalar,
+    i64,
+    white_64,
+    try_transmute_scalar
+);
+white!(
+    "2d",
+    white_2d_f64,
+    f64,
+    SimdTransmuteF64::try_transmute_from_scalar,
+    i64,
+    white_64,
+    try_transmute_scalar
+);
+white!(
+    "3d",
+    white_3d_f64,
+    f64,
+    SimdTransmuteF64::try_transmute_from_scalar,
+    i64,
+    white_64,
+    try_transmute_scalar
+);
+white!(
+    "4d",
+    white_4d_f64,
+    f64,
+    SimdTransmuteF64::try_transmute_from_scalar,
+    i64,
+    white_64,
+    try_transmute_scalar
+);
+
 get_noise!(get_1d_noise, get_1d_noise, f32, noise_helpers_32);
 get_noise!(get_2d_noise, get_2d_noise, f32, noise_helpers_32);
 get_noise!(get_3d_noise, get_3d_noise, f32, noise_helpers_32);
@@ -16,6 +16,8 @@ use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
+use crate::noise::white_32;
+use crate::noise::white_64;
 use crate::noise_helpers_32;
 use crate::noise_helpers_64;
 use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
@@ -360,6 +362,79 @@ turbulence!(
     try_transmute_sse2
 );
 
+white!(
+    "1d",
+    white_1d,
+    __m128,
+    SimdTransmuteF32::try_transmute_from_sse2,
+    i32,
+    white_32,
+    try_transmute_sse2
+);
+white!(
+    "2d",
+    white_2d,
+    __m128,
+    SimdTransmuteF32::try_transmute_from_sse2,
+    i32,
+    white_32,
+    try_transmute_sse2
+);
+white!(
+    "3d",
+    white_3d,
+    __m128,
+    SimdTransmuteF32::try_transmute_from_sse2,
+    i32,
+    white_32,
+    try_transmute_sse2
+);
+white!(
+    "4d",
+    white_4d,
+    __m128,
+    SimdTransmuteF32::try_transmute_from_sse2,
+    i32,
+    white_32,
+    try_transmute_sse2
+);
+white!(
+    "1d",
+    white_1d_f64,
+    __m128d,
+    SimdTransmuteF64::try_transmute_from_sse2,
+    i64,
+    white_64,
+    try_transmute_sse2
+);
+white!(
+    "2d",
+    white_2d_f64,
+    __m128d,
+    SimdTransmuteF64::try_transmute_from_sse2,
+    i64,
+    white_64,
+    try_transmute_sse2
+);
+white!(
+    "3d",
+    white_3d_f64,
+    __m128d,
+    SimdTransmuteF64::try_transmute_from_sse2,
+    i64,
+    white_64,
+    try_transmute_sse2
+);
+white!(
+    "4d",
+    white_4d_f64,
+    __m128d,
+    SimdTransmuteF64::try_transmute_from_sse2,
+    i64,
+    white_64,
+    try_transmute_sse2
+);
+
 get_noise!(get_1d_noise, get_1d_noise, f32, noise_helpers_32);
 get_noise!(get_2d_noise, get_2d_noise, f32, noise_helpers_32);
 get_noise!(get_3d_noise, get_3d_noise, f32, noise_helpers_32);
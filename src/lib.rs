@@ -5,11 +5,32 @@
 //!
 //!## Features
 //!
-//!* SSE2, SSE41, and AVX2 instruction sets, along with non SIMD fallback
+//!* SSE2, SSE41, and AVX2 instruction sets, a portable SIMD engine for other CPUs, along with non SIMD fallback
 //!* Runtime detection picks the best available instruction set
+//!* Bit-identical output on every instruction set with the `deterministic` feature
 //!* Simplex noise, fractal brownian motion, turbulence, and ridge
 //!* 1D, 2D, 3D, and 4D
 //!* Cellular / Voroni Noise  2D and 3D
+//!* White noise 1D, 2D, 3D, and 4D
+//!* Noise graphs combining several noise types per SIMD vector
+//!* Rotated, scaled and domain warped noise through graph transforms
+//!* Terrace, curve, exponent and clamp modifiers
+//!* Your own SIMD noise kernels through the `Sample` trait
+//!* Serialize and deserialize settings with the `serde` feature
+//!* Plain text presets with `NoiseType::from_preset` and `to_preset`
+//!* Versioned algorithms, so saved settings keep generating the same noise
+//!* Tileable blue noise textures 2D and 3D
+//!* Cellular feature points and Poisson-disk point sets 2D and 3D
+//!* Chunked generation of endless worlds with `ChunkedGenerator`
+//!* Smooth f32 noise millions of samples from the origin with `AlgorithmVersion::V3`
+//!* Marching cubes meshes of 3D noise and heightfield meshes of 2D noise with the `mesh` feature
+//!
+//!## Upgrading to 4.0
+//!
+//! Settings built with `NoiseBuilder` or `Settings::default`, and settings deserialised with
+//! serde or read from a preset without a version tag, generate with
+//! `AlgorithmVersion::LATEST`, which is `V3`. See `AlgorithmVersion` for what changed, and
+//! call `with_version(AlgorithmVersion::V2)` to keep the 3.x output.
 //!
 //!## Benchmarks
 //! See [Github](https://github.com/verpeteren/rust-simd-noise)
@@ -42,6 +63,10 @@
 //!
 //! ```
 //!
+//! `active_backend` tells which instruction set is used, `force_backend` makes every call use
+//! another one, and `Backend::get_2d_noise` and friends generate with one specific backend,
+//! checking the cpu supports it first.
+//!
 //! ## Call noise functions directly
 //! Sometimes you need something other than a block, like the points on the surface of a sphere.
 //! Sometimes you may want to use SSE41 even with AVX2 is available
@@ -86,79 +111,135 @@
 //! ```
 
 extern crate simdeez;
+mod algorithm_version;
+#[macro_use]
+mod backend;
+mod blue_noise;
+mod chunked;
+mod color_ramp;
 mod dimensional_being;
+pub mod export;
+mod far_blocks;
+mod heightfield;
 pub mod intrinsics;
+#[cfg(feature = "mesh")]
+pub mod mesh;
+mod modifier;
 pub mod noise;
 mod noise_builder;
 mod noise_dimensions;
 mod noise_helpers_32;
 mod noise_helpers_64;
+mod noise_node;
 mod noise_type;
+mod points;
+pub mod preset;
+mod quantize;
+mod sample;
 mod shared;
 
 use shared::get_scaled_noise;
 use simdeez::prelude::*;
 
+pub use algorithm_version::AlgorithmVersion;
+pub use backend::{active_backend, force_backend, Backend, UnsupportedBackend};
+pub use blue_noise::{blue_noise_2d, blue_noise_3d};
+pub use chunked::ChunkedGenerator;
+pub use color_ramp::{ColorRamp, RampInterpolation};
 use dimensional_being::DimensionalBeing;
+pub use heightfield::{curvature_map, normal_map, normal_map_rgb8, slope_map, EdgeMode};
+pub use modifier::{modify_buffer, Modifier};
 pub use noise::cell2_return_type::Cell2ReturnType;
 pub use noise::cell_distance_function::CellDistanceFunction;
+pub use noise::cell_lattice::CellLattice;
 pub use noise::cell_return_type::CellReturnType;
 pub use noise_builder::NoiseBuilder;
 pub use noise_dimensions::NoiseDimensions;
+pub use noise_node::NoiseNode;
 pub use noise_type::NoiseType;
+pub use points::{
+    feature_points_2d, feature_points_3d, poisson_disk_2d, poisson_disk_3d, FeaturePoint,
+    NoFeaturePoints,
+};
+pub use preset::PresetError;
+pub use sample::{get_sample_noise, get_sample_noise_generic, Sample};
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_1d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
         noise_helpers_32::get_1d_noise::<S>(noise_type)
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_2d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
         noise_helpers_32::get_2d_noise::<S>(noise_type)
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_3d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
         noise_helpers_32::get_3d_noise::<S>(noise_type)
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_4d_noise(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
         noise_helpers_32::get_4d_noise::<S>(noise_type)
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
         unsafe { get_scaled_noise::<S, _>(noise_type, get_1d_noise) }
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
         unsafe { get_scaled_noise::<S, _>(noise_type, get_2d_noise) }
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
         unsafe { get_scaled_noise::<S, _>(noise_type, get_3d_noise) }
     }
 );
 
-simd_runtime_generate!(
+backend_runtime_generate!(
     pub fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
         unsafe { get_scaled_noise::<S, _>(noise_type, get_4d_noise) }
     }
 );
 
+backend_runtime_generate!(
+    pub(crate) fn get_1d_node_noise(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+        noise_helpers_32::get_1d_node_noise::<S>(node)
+    }
+);
+
+backend_runtime_generate!(
+    pub(crate) fn get_2d_node_noise(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+        noise_helpers_32::get_2d_node_noise::<S>(node)
+    }
+);
+
+backend_runtime_generate!(
+    pub(crate) fn get_3d_node_noise(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+        noise_helpers_32::get_3d_node_noise::<S>(node)
+    }
+);
+
+backend_runtime_generate!(
+    pub(crate) fn get_4d_node_noise(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+        noise_helpers_32::get_4d_node_noise::<S>(node)
+    }
+);
+
 mod settings;
 pub use settings::{
     Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, RidgeSettings, Settings,
-    SimplexSettings, TurbulenceSettings,
+    SimplexSettings, TurbulenceSettings, WhiteNoiseSettings,
 };
 
 /*
@@ -0,0 +1,374 @@
+//! Triangle meshes of noise, written as OBJ or PLY. Enabled with the `mesh` feature.
+//!
+//! `heightfield_mesh` turns a 2d block into a terrain grid for quick previews in any
+//! modelling tool.
+//!
+//! ```rust,no_run
+//! use simdnoise::mesh::heightfield_mesh;
+//! use simdnoise::*;
+//!
+//! let noise = NoiseBuilder::ridge_2d(256, 256).generate_scaled(0.0, 1.0);
+//! heightfield_mesh(&noise, 256, 256, 40.0).save_obj("ridges.obj").unwrap();
+//! ```
+//!
+//! `marching_cubes` extracts the surface where a 3d block crosses a threshold. Values at or
+//! above the threshold are inside, so with density noise the mesh wraps the solid parts.
+//! Vertices are in samples, the first value of the block at the origin, and the normals
+//! come from the gradient of the noise rather than from the triangles.
+//!
+//! ```rust,no_run
+//! use simdnoise::mesh::marching_cubes;
+//! use simdnoise::*;
+//!
+//! let noise = NoiseBuilder::fbm_3d(64, 64, 64).with_freq(0.05).generate_scaled(-1.0, 1.0);
+//! let mesh = marching_cubes(&noise, 64, 64, 64, 0.0);
+//! mesh.save_obj("caves.obj").unwrap();
+//! ```
+//!
+//! Terrain too large for one block is meshed in cubic chunks. A chunk's block has
+//! `chunk_samples(size)` samples per axis, starting at `chunk_origin`; the extra samples
+//! are the face it shares with the next chunk and a border for the gradients. Vertices on
+//! the faces two chunks share then come out at exactly the same position with the same
+//! normal in both, and `Mesh::weld` merges them.
+//!
+//! ```rust,no_run
+//! use simdnoise::mesh::{chunk_origin, chunk_samples, marching_cubes_chunk, Mesh};
+//! use simdnoise::*;
+//!
+//! let size = 32;
+//! let n = chunk_samples(size);
+//! let mut chunks = Vec::new();
+//! for chunk in [[0, 0, 0], [1, 0, 0], [1, 1, 0]].iter() {
+//!     let [x, y, z] = chunk_origin(*chunk, size);
+//!     let noise = NoiseBuilder::fbm_3d_offset(x as f32, n, y as f32, n, z as f32, n)
+//!         .with_freq(0.05)
+//!         .with_seed(3)
+//!         .generate()
+//!         .0;
+//!     chunks.push(marching_cubes_chunk(&noise, *chunk, size, 0.0));
+//! }
+//! Mesh::weld(&chunks).save_ply("world.ply").unwrap();
+//! ```
+
+mod tables;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use self::tables::{EDGES, TRIANGLES};
+use crate::{normal_map, EdgeMode};
+
+/// An indexed triangle mesh, three indices per triangle, counter-clockwise seen from the
+/// side the normals point to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Texture coordinates, one per vertex, or none at all.
+    pub uvs: Vec<[f32; 2]>,
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn new() -> Mesh {
+        Mesh::default()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// The triangles of all `chunks` in one mesh. Vertices at exactly the same position are
+    /// merged into the first of them, which welds chunks along the faces they share.
+    pub fn weld(chunks: &[Mesh]) -> Mesh {
+        let mut mesh = Mesh::new();
+        let mut existing: HashMap<[u32; 3], u32> = HashMap::new();
+        for chunk in chunks {
+            let remap: Vec<u32> = chunk
+                .positions
+                .iter()
+                .zip(&chunk.normals)
+                .enumerate()
+                .map(|(v, (p, n))| {
+                    *existing.entry(position_key(p)).or_insert_with(|| {
+                        mesh.positions.push(*p);
+                        mesh.normals.push(*n);
+                        mesh.uvs.extend(chunk.uvs.get(v));
+                        mesh.positions.len() as u32 - 1
+                    })
+                })
+                .collect();
+            mesh.indices
+                .extend(chunk.indices.iter().map(|&i| remap[i as usize]));
+        }
+        mesh
+    }
+
+    /// Writes the mesh as Wavefront OBJ text.
+    pub fn write_obj<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "# simdnoise mesh")?;
+        for p in &self.positions {
+            writeln!(writer, "v {} {} {}", p[0], p[1], p[2])?;
+        }
+        for n in &self.normals {
+            writeln!(writer, "vn {} {} {}", n[0], n[1], n[2])?;
+        }
+        for uv in &self.uvs {
+            writeln!(writer, "vt {} {}", uv[0], uv[1])?;
+        }
+        // OBJ counts from one
+        for t in self.indices.chunks_exact(3) {
+            let [a, b, c] = [t[0] + 1, t[1] + 1, t[2] + 1];
+            if self.uvs.is_empty() {
+                writeln!(writer, "f {0}//{0} {1}//{1} {2}//{2}", a, b, c)?;
+            } else {
+                writeln!(writer, "f {0}/{0}/{0} {1}/{1}/{1} {2}/{2}/{2}", a, b, c)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the mesh as binary little endian PLY, texture coordinates as `s` and `t`.
+    pub fn write_ply<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(
+            writer,
+            "ply\nformat binary_little_endian 1.0\ncomment simdnoise mesh\n\
+             element vertex {}\n\
+             property float x\nproperty float y\nproperty float z\n\
+             property float nx\nproperty float ny\nproperty float nz\n",
+            self.vertex_count()
+        )?;
+        if !self.uvs.is_empty() {
+            write!(writer, "property float s\nproperty float t\n")?;
+        }
+        write!(
+            writer,
+            "element face {}\nproperty list uchar uint vertex_indices\nend_header\n",
+            self.triangle_count()
+        )?;
+        let vertex_size = if self.uvs.is_empty() { 24 } else { 32 };
+        let mut bytes =
+            Vec::with_capacity(self.vertex_count() * vertex_size + self.triangle_count() * 13);
+        for (i, (p, n)) in self.positions.iter().zip(&self.normals).enumerate() {
+            p.iter()
+                .chain(n.iter())
+                .chain(self.uvs.get(i).into_iter().flatten())
+                .for_each(|v| bytes.extend(&v.to_le_bytes()));
+        }
+        for t in self.indices.chunks_exact(3) {
+            bytes.push(3);
+            t.iter().for_each(|i| bytes.extend(&i.to_le_bytes()));
+        }
+        writer.write_all(&bytes)
+    }
+
+    /// Writes the mesh to an OBJ file, see `write_obj`.
+    pub fn save_obj<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_obj(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Writes the mesh to a PLY file, see `write_ply`.
+    pub fn save_ply<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ply(&mut writer)?;
+        writer.flush()
+    }
+}
+
+/// The surface of a `width` by `height` by `depth` block at `threshold`.
+pub fn marching_cubes(
+    data: &[f32],
+    width: usize,
+    height: usize,
+    depth: usize,
+    threshold: f32,
+) -> Mesh {
+    assert_eq!(
+        data.len(),
+        width * height * depth,
+        "the buffer is not width * height * depth long"
+    );
+    let volume = Volume {
+        data,
+        size: [width, height, depth],
+        origin: [0, 0, 0],
+    };
+    let cells = |n: usize| (0, n.saturating_sub(1));
+    volume.extract([cells(width), cells(height), cells(depth)], threshold)
+}
+
+/// A grid of two triangles per square of four values of a `width` by `height` heightfield.
+/// The grid is y up: `x` runs along a row, `z` along increasing rows and the values,
+/// multiplied by `scale`, are the heights. The texture coordinates cover the grid once with
+/// the first row at the top of the texture, so images written by `export` line up.
+pub fn heightfield_mesh(data: &[f32], width: usize, height: usize, scale: f32) -> Mesh {
+    assert_eq!(
+        data.len(),
+        width * height,
+        "the buffer is not width * height long"
+    );
+    let mut mesh = Mesh::new();
+    let normals = normal_map(data, width, height, scale, EdgeMode::Clamp);
+    let (u_step, v_step) = (
+        1.0 / (width.max(2) - 1) as f32,
+        1.0 / (height.max(2) - 1) as f32,
+    );
+    for row in 0..height {
+        for x in 0..width {
+            let i = row * width + x;
+            mesh.positions.push([x as f32, data[i] * scale, row as f32]);
+            // The normal map's y runs along the rows and its z is up
+            let [nx, ny, nz] = normals[i];
+            mesh.normals.push([nx, nz, ny]);
+            mesh.uvs
+                .push([x as f32 * u_step, 1.0 - row as f32 * v_step]);
+        }
+    }
+    for row in 1..height {
+        for x in 1..width {
+            let (d, c) = ((row * width + x) as u32, (row * width + x - 1) as u32);
+            let (b, a) = (d - width as u32, c - width as u32);
+            mesh.indices.extend_from_slice(&[a, c, b, b, c, d]);
+        }
+    }
+    mesh
+}
+
+/// The samples per axis of the block of a chunk `size` cells across: the cells, the face
+/// shared with the next chunk and one sample on either side for the gradients.
+pub fn chunk_samples(size: usize) -> usize {
+    size + 3
+}
+
+/// The world sample the block of `chunk` starts at, as `x`, `y` and `z`. It is the same as
+/// `ChunkedGenerator::block_origin` with a padding of 1.
+pub fn chunk_origin(chunk: [i64; 3], size: usize) -> [i64; 3] {
+    let origin = |c: i64| c * size as i64 - 1;
+    [origin(chunk[0]), origin(chunk[1]), origin(chunk[2])]
+}
+
+/// The surface of one chunk of a larger world at `threshold`. `data` is the chunk's block,
+/// `chunk_samples(size)` samples along each axis generated from `chunk_origin(chunk, size)`.
+/// Vertices are placed in world samples, so chunk `[1, 0, 0]` starts at `x = size`.
+pub fn marching_cubes_chunk(data: &[f32], chunk: [i64; 3], size: usize, threshold: f32) -> Mesh {
+    let n = chunk_samples(size);
+    assert_eq!(
+        data.len(),
+        n * n * n,
+        "the buffer is not chunk_samples(size) cubed long"
+    );
+    let volume = Volume {
+        data,
+        size: [n, n, n],
+        origin: chunk_origin(chunk, size),
+    };
+    volume.extract([(1, size + 1); 3], threshold)
+}
+
+/// The key two positions share when they are bit for bit the same.
+fn position_key(p: &[f32; 3]) -> [u32; 3] {
+    [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()]
+}
+
+/// A block of samples, x changing fastest, whose first sample is at `origin` in the world.
+struct Volume<'a> {
+    data: &'a [f32],
+    size: [usize; 3],
+    origin: [i64; 3],
+}
+
+impl<'a> Volume<'a> {
+    fn index(&self, p: [usize; 3]) -> usize {
+        (p[2] * self.size[1] + p[1]) * self.size[0] + p[0]
+    }
+
+    fn value(&self, p: [usize; 3]) -> f32 {
+        self.data[self.index(p)]
+    }
+
+    /// Central differences, one sided at the ends of the block.
+    fn gradient(&self, p: [usize; 3]) -> [f32; 3] {
+        let mut gradient = [0.0; 3];
+        for (axis, g) in gradient.iter_mut().enumerate() {
+            let (mut lo, mut hi) = (p, p);
+            lo[axis] = p[axis].saturating_sub(1);
+            hi[axis] = (p[axis] + 1).min(self.size[axis] - 1);
+            if hi[axis] > lo[axis] {
+                *g = (self.value(hi) - self.value(lo)) / (hi[axis] - lo[axis]) as f32;
+            }
+        }
+        gradient
+    }
+
+    /// Meshes the cells from `cells.0` up to, not including, `cells.1` along each axis.
+    fn extract(&self, cells: [(usize, usize); 3], threshold: f32) -> Mesh {
+        let mut mesh = Mesh::new();
+        // The vertex on each edge, by the edge's lower sample and axis
+        let mut vertices = vec![u32::MAX; self.data.len() * 3];
+        for z in cells[2].0..cells[2].1 {
+            for y in cells[1].0..cells[1].1 {
+                for x in cells[0].0..cells[0].1 {
+                    let corner = |c: usize| [x + (c & 1), y + (c >> 1 & 1), z + (c >> 2 & 1)];
+                    let mut case = 0;
+                    for c in 0..8 {
+                        if self.value(corner(c)) >= threshold {
+                            case |= 1 << c;
+                        }
+                    }
+                    for &edge in TRIANGLES[case].iter().take_while(|&&e| e >= 0) {
+                        let (a, b) = EDGES[edge as usize];
+                        let (a, b) = (corner(a), corner(b));
+                        let key = self.index(a) * 3 + edge as usize / 4;
+                        if vertices[key] == u32::MAX {
+                            vertices[key] = mesh.positions.len() as u32;
+                            self.push_vertex(&mut mesh, a, b, edge as usize / 4, threshold);
+                        }
+                        mesh.indices.push(vertices[key]);
+                    }
+                }
+            }
+        }
+        mesh
+    }
+
+    /// Adds the vertex where the edge from `a` up to `b` along `axis` crosses `threshold`.
+    /// Edges are always interpolated from their lower end, so chunks sharing an edge place
+    /// its vertex identically.
+    fn push_vertex(
+        &self,
+        mesh: &mut Mesh,
+        a: [usize; 3],
+        b: [usize; 3],
+        axis: usize,
+        threshold: f32,
+    ) {
+        let (va, vb) = (self.value(a), self.value(b));
+        let t = (threshold - va) / (vb - va);
+        let mut position = [0.0; 3];
+        for (i, p) in position.iter_mut().enumerate() {
+            *p = (self.origin[i] + a[i] as i64) as f32;
+        }
+        position[axis] += t;
+
+        // The noise falls towards the outside
+        let (ga, gb) = (self.gradient(a), self.gradient(b));
+        let mut normal = [0.0; 3];
+        for (i, n) in normal.iter_mut().enumerate() {
+            *n = -(ga[i] + (gb[i] - ga[i]) * t);
+        }
+        let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+        if length > 0.0 {
+            normal.iter_mut().for_each(|n| *n /= length);
+        }
+        mesh.positions.push(position);
+        mesh.normals.push(normal);
+    }
+}
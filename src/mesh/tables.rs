@@ -0,0 +1,284 @@
+//! Lookup tables for marching cubes.
+//!
+//! Corner `c` of a cell is at `(c & 1, c >> 1 & 1, c >> 2 & 1)`. Bit `c` of a case is set when
+//! that corner is inside. Faces with two diagonal inside corners always keep the inside
+//! corners apart, so neighbouring cells agree on the faces they share and the surface is
+//! closed. Triangles wind counter-clockwise seen from outside.
+
+/// The corners at both ends of each edge, lower corner first. Edges 0-3 run along x, 4-7
+/// along y and 8-11 along z.
+pub(crate) const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// The edges the triangles of each case are spanned between, three per triangle, ended by -1.
+#[rustfmt::skip]
+pub(crate) const TRIANGLES: [[i8; 16]; 256] = [
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 10, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 9, 1, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 11, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 11, 4, 11, 1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 8, 5, 8, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 10, 0, 10, 4, -1, -1, -1, -1, -1, -1, -1],
+    [9, 11, 10, 9, 10, 8, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 9, 5, 2, 5, 4, 2, 4, 6, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 2, 1, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 10, 4, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 2, 1, 2, 9, 1, 9, 5, -1, -1, -1, -1],
+    [5, 11, 1, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 0, 5, 11, 1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 1, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 9, 4, 9, 11, 4, 11, 1, -1, -1, -1, -1],
+    [2, 8, 6, 5, 11, 10, 5, 10, 4, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 6, 5, 6, 2, 5, 2, 0, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 10, 0, 10, 4, 2, 8, 6, -1, -1, -1, -1],
+    [2, 9, 11, 2, 11, 10, 2, 10, 6, -1, -1, -1, -1, -1, -1, -1],
+    [7, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 5, 4, 7, 4, 8, 7, 8, 2, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 0, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 5, 1, 10, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 2, 1, 2, 7, 1, 7, 5, -1, -1, -1, -1],
+    [5, 11, 1, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 11, 1, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 11, 0, 11, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 2, 4, 2, 7, 4, 7, 11, 4, 11, 1, -1, -1, -1, -1],
+    [7, 9, 2, 5, 11, 10, 5, 10, 4, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 8, 5, 8, 0, 7, 9, 2, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 11, 0, 11, 10, 0, 10, 4, -1, -1, -1, -1],
+    [7, 11, 10, 7, 10, 8, 7, 8, 2, -1, -1, -1, -1, -1, -1, -1],
+    [7, 9, 8, 7, 8, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 7, 4, 7, 9, 4, 9, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 6, 0, 6, 7, 0, 7, 5, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 7, 4, 7, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, 7, 9, 8, 7, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 7, 1, 7, 9, 1, 9, 0, -1, -1, -1, -1],
+    [0, 8, 6, 0, 6, 7, 0, 7, 5, 1, 10, 4, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 7, 1, 7, 5, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 1, 7, 9, 8, 7, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 7, 4, 7, 9, 4, 9, 0, 5, 11, 1, -1, -1, -1, -1],
+    [0, 8, 6, 0, 6, 7, 0, 7, 11, 0, 11, 1, -1, -1, -1, -1],
+    [4, 6, 7, 4, 7, 11, 4, 11, 1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 4, 7, 9, 8, 7, 8, 6, -1, -1, -1, -1],
+    [5, 11, 10, 5, 10, 6, 5, 6, 7, 5, 7, 9, 5, 9, 0, -1],
+    [0, 8, 6, 0, 6, 7, 0, 7, 11, 0, 11, 10, 0, 10, 4, -1],
+    [7, 11, 10, 7, 10, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [6, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [6, 10, 3, 4, 8, 9, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 8, 1, 8, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 3, 6, 1, 6, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 8, 1, 8, 9, 1, 9, 5, -1, -1, -1, -1],
+    [5, 11, 1, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 11, 1, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 1, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 11, 4, 11, 1, 6, 10, 3, -1, -1, -1, -1],
+    [6, 4, 5, 6, 5, 11, 6, 11, 3, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 3, 5, 3, 6, 5, 6, 8, 5, 8, 0, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 3, 0, 3, 6, 0, 6, 4, -1, -1, -1, -1],
+    [6, 8, 9, 6, 9, 11, 6, 11, 3, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 10, 2, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 3, 4, 3, 2, 4, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 2, 8, 10, 2, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [2, 9, 5, 2, 5, 4, 2, 4, 10, 2, 10, 3, -1, -1, -1, -1],
+    [1, 3, 2, 1, 2, 8, 1, 8, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 2, 1, 2, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 3, 2, 1, 2, 8, 1, 8, 4, -1, -1, -1, -1],
+    [1, 3, 2, 1, 2, 9, 1, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 1, 2, 8, 10, 2, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 3, 4, 3, 2, 4, 2, 0, 5, 11, 1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 1, 2, 8, 10, 2, 10, 3, -1, -1, -1, -1],
+    [4, 10, 3, 4, 3, 2, 4, 2, 9, 4, 9, 11, 4, 11, 1, -1],
+    [2, 8, 4, 2, 4, 5, 2, 5, 11, 2, 11, 3, -1, -1, -1, -1],
+    [5, 11, 3, 5, 3, 2, 5, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 11, 0, 11, 3, 0, 3, 2, 0, 2, 8, 0, 8, 4, -1],
+    [2, 9, 11, 2, 11, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 9, 2, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 7, 9, 2, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 5, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [7, 5, 4, 7, 4, 8, 7, 8, 2, 6, 10, 3, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 4, 7, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 8, 1, 8, 0, 7, 9, 2, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 5, 1, 3, 6, 1, 6, 4, -1, -1, -1, -1],
+    [1, 3, 6, 1, 6, 8, 1, 8, 2, 1, 2, 7, 1, 7, 5, -1],
+    [5, 11, 1, 7, 9, 2, 6, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 11, 1, 7, 9, 2, 6, 10, 3, -1, -1, -1, -1],
+    [0, 2, 7, 0, 7, 11, 0, 11, 1, 6, 10, 3, -1, -1, -1, -1],
+    [4, 8, 2, 4, 2, 7, 4, 7, 11, 4, 11, 1, 6, 10, 3, -1],
+    [7, 9, 2, 6, 4, 5, 6, 5, 11, 6, 11, 3, -1, -1, -1, -1],
+    [5, 11, 3, 5, 3, 6, 5, 6, 8, 5, 8, 0, 7, 9, 2, -1],
+    [0, 2, 7, 0, 7, 11, 0, 11, 3, 0, 3, 6, 0, 6, 4, -1],
+    [7, 11, 3, 7, 3, 6, 7, 6, 8, 7, 8, 2, -1, -1, -1, -1],
+    [7, 9, 8, 7, 8, 10, 7, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 3, 4, 3, 7, 4, 7, 9, 4, 9, 0, -1, -1, -1, -1],
+    [0, 8, 10, 0, 10, 3, 0, 3, 7, 0, 7, 5, -1, -1, -1, -1],
+    [7, 5, 4, 7, 4, 10, 7, 10, 3, -1, -1, -1, -1, -1, -1, -1],
+    [1, 3, 7, 1, 7, 9, 1, 9, 8, 1, 8, 4, -1, -1, -1, -1],
+    [1, 3, 7, 1, 7, 9, 1, 9, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 4, 0, 4, 1, 0, 1, 3, 0, 3, 7, 0, 7, 5, -1],
+    [1, 3, 7, 1, 7, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 11, 1, 7, 9, 8, 7, 8, 10, 7, 10, 3, -1, -1, -1, -1],
+    [4, 10, 3, 4, 3, 7, 4, 7, 9, 4, 9, 0, 5, 11, 1, -1],
+    [0, 8, 10, 0, 10, 3, 0, 3, 7, 0, 7, 11, 0, 11, 1, -1],
+    [4, 10, 3, 4, 3, 7, 4, 7, 11, 4, 11, 1, -1, -1, -1, -1],
+    [7, 9, 8, 7, 8, 4, 7, 4, 5, 7, 5, 11, 7, 11, 3, -1],
+    [5, 11, 3, 5, 3, 7, 5, 7, 9, 5, 9, 0, -1, -1, -1, -1],
+    [0, 8, 4, 7, 11, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [7, 11, 3, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 11, 7, 4, 8, 9, 4, 9, 5, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 0, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 10, 4, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 9, 1, 9, 5, 3, 11, 7, -1, -1, -1, -1],
+    [5, 7, 3, 5, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 7, 3, 5, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 7, 4, 7, 3, 4, 3, 1, -1, -1, -1, -1],
+    [3, 10, 4, 3, 4, 5, 3, 5, 7, -1, -1, -1, -1, -1, -1, -1],
+    [5, 7, 3, 5, 3, 10, 5, 10, 8, 5, 8, 0, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 3, 0, 3, 10, 0, 10, 4, -1, -1, -1, -1],
+    [3, 10, 8, 3, 8, 9, 3, 9, 7, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 6, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 0, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 2, 8, 6, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [2, 9, 5, 2, 5, 4, 2, 4, 6, 3, 11, 7, -1, -1, -1, -1],
+    [1, 10, 4, 2, 8, 6, 3, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 2, 1, 2, 0, 3, 11, 7, -1, -1, -1, -1],
+    [0, 9, 5, 1, 10, 4, 2, 8, 6, 3, 11, 7, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 2, 1, 2, 9, 1, 9, 5, 3, 11, 7, -1],
+    [5, 7, 3, 5, 3, 1, 2, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 0, 5, 7, 3, 5, 3, 1, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 3, 0, 3, 1, 2, 8, 6, -1, -1, -1, -1],
+    [4, 6, 2, 4, 2, 9, 4, 9, 7, 4, 7, 3, 4, 3, 1, -1],
+    [2, 8, 6, 3, 10, 4, 3, 4, 5, 3, 5, 7, -1, -1, -1, -1],
+    [5, 7, 3, 5, 3, 10, 5, 10, 6, 5, 6, 2, 5, 2, 0, -1],
+    [0, 9, 7, 0, 7, 3, 0, 3, 10, 0, 10, 4, 2, 8, 6, -1],
+    [2, 9, 7, 2, 7, 3, 2, 3, 10, 2, 10, 6, -1, -1, -1, -1],
+    [3, 11, 9, 3, 9, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 3, 11, 9, 3, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [0, 2, 3, 0, 3, 11, 0, 11, 5, -1, -1, -1, -1, -1, -1, -1],
+    [3, 11, 5, 3, 5, 4, 3, 4, 8, 3, 8, 2, -1, -1, -1, -1],
+    [1, 10, 4, 3, 11, 9, 3, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 0, 3, 11, 9, 3, 9, 2, -1, -1, -1, -1],
+    [0, 2, 3, 0, 3, 11, 0, 11, 5, 1, 10, 4, -1, -1, -1, -1],
+    [1, 10, 8, 1, 8, 2, 1, 2, 3, 1, 3, 11, 1, 11, 5, -1],
+    [5, 9, 2, 5, 2, 3, 5, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 9, 2, 5, 2, 3, 5, 3, 1, -1, -1, -1, -1],
+    [0, 2, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 2, 4, 2, 3, 4, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 10, 4, 3, 4, 5, 3, 5, 9, 3, 9, 2, -1, -1, -1, -1],
+    [5, 9, 2, 5, 2, 3, 5, 3, 10, 5, 10, 8, 5, 8, 0, -1],
+    [0, 2, 3, 0, 3, 10, 0, 10, 4, -1, -1, -1, -1, -1, -1, -1],
+    [3, 10, 8, 3, 8, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 11, 9, 3, 9, 8, 3, 8, 6, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 3, 4, 3, 11, 4, 11, 9, 4, 9, 0, -1, -1, -1, -1],
+    [0, 8, 6, 0, 6, 3, 0, 3, 11, 0, 11, 5, -1, -1, -1, -1],
+    [3, 11, 5, 3, 5, 4, 3, 4, 6, -1, -1, -1, -1, -1, -1, -1],
+    [1, 10, 4, 3, 11, 9, 3, 9, 8, 3, 8, 6, -1, -1, -1, -1],
+    [1, 10, 6, 1, 6, 3, 1, 3, 11, 1, 11, 9, 1, 9, 0, -1],
+    [0, 8, 6, 0, 6, 3, 0, 3, 11, 0, 11, 5, 1, 10, 4, -1],
+    [1, 10, 6, 1, 6, 3, 1, 3, 11, 1, 11, 5, -1, -1, -1, -1],
+    [5, 9, 8, 5, 8, 6, 5, 6, 3, 5, 3, 1, -1, -1, -1, -1],
+    [4, 6, 3, 4, 3, 1, 4, 1, 5, 4, 5, 9, 4, 9, 0, -1],
+    [0, 8, 6, 0, 6, 3, 0, 3, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 6, 3, 4, 3, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [3, 10, 4, 3, 4, 5, 3, 5, 9, 3, 9, 8, 3, 8, 6, -1],
+    [5, 9, 0, 3, 10, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 6, 0, 6, 3, 0, 3, 10, 0, 10, 4, -1, -1, -1, -1],
+    [3, 10, 6, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [6, 10, 11, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 6, 10, 11, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 6, 10, 11, 6, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 5, 6, 10, 11, 6, 11, 7, -1, -1, -1, -1],
+    [1, 11, 7, 1, 7, 6, 1, 6, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 11, 7, 1, 7, 6, 1, 6, 8, 1, 8, 0, -1, -1, -1, -1],
+    [0, 9, 5, 1, 11, 7, 1, 7, 6, 1, 6, 4, -1, -1, -1, -1],
+    [1, 11, 7, 1, 7, 6, 1, 6, 8, 1, 8, 9, 1, 9, 5, -1],
+    [5, 7, 6, 5, 6, 10, 5, 10, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 7, 6, 5, 6, 10, 5, 10, 1, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 6, 0, 6, 10, 0, 10, 1, -1, -1, -1, -1],
+    [4, 8, 9, 4, 9, 7, 4, 7, 6, 4, 6, 10, 4, 10, 1, -1],
+    [5, 7, 6, 5, 6, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 7, 6, 5, 6, 8, 5, 8, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 6, 0, 6, 4, -1, -1, -1, -1, -1, -1, -1],
+    [6, 8, 9, 6, 9, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 10, 2, 10, 11, 2, 11, 7, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 11, 4, 11, 7, 4, 7, 2, 4, 2, 0, -1, -1, -1, -1],
+    [0, 9, 5, 2, 8, 10, 2, 10, 11, 2, 11, 7, -1, -1, -1, -1],
+    [2, 9, 5, 2, 5, 4, 2, 4, 10, 2, 10, 11, 2, 11, 7, -1],
+    [1, 11, 7, 1, 7, 2, 1, 2, 8, 1, 8, 4, -1, -1, -1, -1],
+    [1, 11, 7, 1, 7, 2, 1, 2, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 5, 1, 11, 7, 1, 7, 2, 1, 2, 8, 1, 8, 4, -1],
+    [1, 11, 7, 1, 7, 2, 1, 2, 9, 1, 9, 5, -1, -1, -1, -1],
+    [5, 7, 2, 5, 2, 8, 5, 8, 10, 5, 10, 1, -1, -1, -1, -1],
+    [4, 10, 1, 4, 1, 5, 4, 5, 7, 4, 7, 2, 4, 2, 0, -1],
+    [0, 9, 7, 0, 7, 2, 0, 2, 8, 0, 8, 10, 0, 10, 1, -1],
+    [4, 10, 1, 2, 9, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [2, 8, 4, 2, 4, 5, 2, 5, 7, -1, -1, -1, -1, -1, -1, -1],
+    [5, 7, 2, 5, 2, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 9, 7, 0, 7, 2, 0, 2, 8, 0, 8, 4, -1, -1, -1, -1],
+    [2, 9, 7, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [6, 10, 11, 6, 11, 9, 6, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 0, 6, 10, 11, 6, 11, 9, 6, 9, 2, -1, -1, -1, -1],
+    [0, 2, 6, 0, 6, 10, 0, 10, 11, 0, 11, 5, -1, -1, -1, -1],
+    [6, 10, 11, 6, 11, 5, 6, 5, 4, 6, 4, 8, 6, 8, 2, -1],
+    [1, 11, 9, 1, 9, 2, 1, 2, 6, 1, 6, 4, -1, -1, -1, -1],
+    [1, 11, 9, 1, 9, 2, 1, 2, 6, 1, 6, 8, 1, 8, 0, -1],
+    [0, 2, 6, 0, 6, 4, 0, 4, 1, 0, 1, 11, 0, 11, 5, -1],
+    [1, 11, 5, 6, 8, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 2, 5, 2, 6, 5, 6, 10, 5, 10, 1, -1, -1, -1, -1],
+    [4, 8, 0, 5, 9, 2, 5, 2, 6, 5, 6, 10, 5, 10, 1, -1],
+    [0, 2, 6, 0, 6, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 8, 2, 4, 2, 6, 4, 6, 10, 4, 10, 1, -1, -1, -1, -1],
+    [6, 4, 5, 6, 5, 9, 6, 9, 2, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 2, 5, 2, 6, 5, 6, 8, 5, 8, 0, -1, -1, -1, -1],
+    [0, 2, 6, 0, 6, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [6, 8, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [8, 10, 11, 8, 11, 9, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 11, 4, 11, 9, 4, 9, 0, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 10, 0, 10, 11, 0, 11, 5, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 11, 4, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [1, 11, 9, 1, 9, 8, 1, 8, 4, -1, -1, -1, -1, -1, -1, -1],
+    [1, 11, 9, 1, 9, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 4, 0, 4, 1, 0, 1, 11, 0, 11, 5, -1, -1, -1, -1],
+    [1, 11, 5, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 8, 5, 8, 10, 5, 10, 1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 1, 4, 1, 5, 4, 5, 9, 4, 9, 0, -1, -1, -1, -1],
+    [0, 8, 10, 0, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [4, 10, 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 8, 5, 8, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [5, 9, 0, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [0, 8, 4, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+    [-1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1],
+];
@@ -0,0 +1,203 @@
+//! Output modifiers, the post processing most noise goes through before it is used.
+//!
+//! A `Modifier` maps every noise value on its own. Apply them while generating, with
+//! `NoiseNode::modify`, or afterwards to any buffer with `modify_buffer`. Both evaluate the
+//! modifiers a SIMD vector at a time.
+
+use simdeez::prelude::*;
+
+use crate::noise::ops::{pow_32, pow_64};
+use crate::shared::modify_noise;
+
+/// Maps noise values to new values.
+///
+/// ```rust
+/// use simdnoise::*;
+///
+/// // Modified while generating
+/// let plateaus = NoiseNode::from(NoiseBuilder::fbm_2d(64, 64).with_freq(0.02).wrap())
+///     .terrace(vec![-1.0, -0.2, 0.3, 1.0], false);
+/// let (noise, min, max) = plateaus.generate();
+///
+/// // Modified afterwards
+/// let mut peaks = NoiseBuilder::ridge_2d(64, 64).generate_scaled(0.0, 1.0);
+/// modify_buffer(&mut peaks, &[Modifier::Exponent(2.0), Modifier::Clamp { min: 0.1, max: 0.9 }]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Modifier {
+    /// Flattens the values into steps. Between two neighbouring points the output rises
+    /// slowly from the lower point and steeply into the higher one, or the other way around
+    /// when `invert` is set. Values outside the points are clamped to the first and last
+    /// point. The points must be ascending, and there must be at least two.
+    Terrace { points: Vec<f32>, invert: bool },
+    /// Maps the values through a piecewise-linear curve of `[input, output]` control points,
+    /// holding the first and last output outside the curve. The inputs must be ascending,
+    /// and there must be at least two points.
+    Curve(Vec<[f32; 2]>),
+    /// Raises the magnitude of the values to the given power, keeping their sign. Whole
+    /// exponents and halves, like 2 or 2.5, are computed a vector at a time; others fall
+    /// back to `powf` lane by lane, which is several times slower.
+    Exponent(f32),
+    /// Limits the values to the range from `min` to `max`.
+    Clamp { min: f32, max: f32 },
+}
+
+impl Modifier {
+    pub fn validate(&self) {
+        match self {
+            Modifier::Terrace { points, .. } => {
+                if points.len() < 2 || !ascending(points.iter().copied()) {
+                    panic!("terrace modifier needs at least two ascending points");
+                }
+            }
+            Modifier::Curve(points) => {
+                if points.len() < 2 || !ascending(points.iter().map(|p| p[0])) {
+                    panic!("curve modifier needs at least two points with ascending inputs");
+                }
+                if points.iter().any(|p| !p[1].is_finite()) {
+                    panic!("invalid curve output in modifier");
+                }
+            }
+            Modifier::Exponent(exponent) => {
+                if !exponent.is_finite() {
+                    panic!("invalid exponent in modifier");
+                }
+            }
+            Modifier::Clamp { min, max } => {
+                if min.is_nan() || max.is_nan() || min > max {
+                    panic!("invalid clamp range in modifier");
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn apply_f32<S: Simd>(&self, v: S::Vf32) -> S::Vf32 {
+        match self {
+            Modifier::Terrace { points, invert } => {
+                let last = points.len() - 1;
+                let v = v
+                    .max(S::Vf32::set1(points[0]))
+                    .min(S::Vf32::set1(points[last]));
+                let mut lo = S::Vf32::set1(points[0]);
+                let mut hi = S::Vf32::set1(points[1]);
+                for i in 1..last {
+                    let above = v.cmp_gte(S::Vf32::set1(points[i]));
+                    lo = above.blendv(lo, S::Vf32::set1(points[i]));
+                    hi = above.blendv(hi, S::Vf32::set1(points[i + 1]));
+                }
+                let t = (v - lo) / (hi - lo);
+                if *invert {
+                    let t = S::Vf32::set1(1.0) - t;
+                    hi + (lo - hi) * t * t
+                } else {
+                    lo + (hi - lo) * t * t
+                }
+            }
+            Modifier::Curve(points) => {
+                let last = points.len() - 1;
+                let v = v
+                    .max(S::Vf32::set1(points[0][0]))
+                    .min(S::Vf32::set1(points[last][0]));
+                let mut lo = [S::Vf32::set1(points[0][0]), S::Vf32::set1(points[0][1])];
+                let mut hi = [S::Vf32::set1(points[1][0]), S::Vf32::set1(points[1][1])];
+                for i in 1..last {
+                    let above = v.cmp_gte(S::Vf32::set1(points[i][0]));
+                    for k in 0..2 {
+                        lo[k] = above.blendv(lo[k], S::Vf32::set1(points[i][k]));
+                        hi[k] = above.blendv(hi[k], S::Vf32::set1(points[i + 1][k]));
+                    }
+                }
+                let t = (v - lo[0]) / (hi[0] - lo[0]);
+                lo[1] + (hi[1] - lo[1]) * t
+            }
+            Modifier::Exponent(exponent) => {
+                let sign = v & S::Vf32::set1(-0.0);
+                pow_32::<S>(v.abs(), *exponent) | sign
+            }
+            Modifier::Clamp { min, max } => v.max(S::Vf32::set1(*min)).min(S::Vf32::set1(*max)),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn apply_f64<S: Simd>(&self, v: S::Vf64) -> S::Vf64 {
+        match self {
+            Modifier::Terrace { points, invert } => {
+                let last = points.len() - 1;
+                let point = |i: usize| S::Vf64::set1(points[i] as f64);
+                let v = v.max(point(0)).min(point(last));
+                let mut lo = point(0);
+                let mut hi = point(1);
+                for i in 1..last {
+                    let above = v.cmp_gte(point(i));
+                    lo = above.blendv(lo, point(i));
+                    hi = above.blendv(hi, point(i + 1));
+                }
+                let t = (v - lo) / (hi - lo);
+                if *invert {
+                    let t = S::Vf64::set1(1.0) - t;
+                    hi + (lo - hi) * t * t
+                } else {
+                    lo + (hi - lo) * t * t
+                }
+            }
+            Modifier::Curve(points) => {
+                let last = points.len() - 1;
+                let point = |i: usize, k: usize| S::Vf64::set1(points[i][k] as f64);
+                let v = v.max(point(0, 0)).min(point(last, 0));
+                let mut lo = [point(0, 0), point(0, 1)];
+                let mut hi = [point(1, 0), point(1, 1)];
+                for i in 1..last {
+                    let above = v.cmp_gte(point(i, 0));
+                    for k in 0..2 {
+                        lo[k] = above.blendv(lo[k], point(i, k));
+                        hi[k] = above.blendv(hi[k], point(i + 1, k));
+                    }
+                }
+                let t = (v - lo[0]) / (hi[0] - lo[0]);
+                lo[1] + (hi[1] - lo[1]) * t
+            }
+            Modifier::Exponent(exponent) => {
+                let sign = v & S::Vf64::set1(-0.0);
+                pow_64::<S>(v.abs(), *exponent as f64) | sign
+            }
+            Modifier::Clamp { min, max } => v
+                .max(S::Vf64::set1(*min as f64))
+                .min(S::Vf64::set1(*max as f64)),
+        }
+    }
+
+    /// An upper bound of the magnitude of the values, for values up to `amplitude`.
+    pub(crate) fn amplitude(&self, amplitude: f64) -> f64 {
+        let largest = |values: &mut dyn Iterator<Item = f32>| {
+            values.map(|v| (v as f64).abs()).fold(0.0, f64::max)
+        };
+        match self {
+            Modifier::Terrace { points, .. } => largest(&mut points.iter().copied()),
+            Modifier::Curve(points) => largest(&mut points.iter().map(|p| p[1])),
+            Modifier::Exponent(exponent) => amplitude.powf(*exponent as f64),
+            Modifier::Clamp { min, max } => largest(&mut [*min, *max].iter().copied()),
+        }
+    }
+}
+
+fn ascending(mut values: impl Iterator<Item = f32>) -> bool {
+    let mut previous = match values.next() {
+        Some(v) if v.is_finite() => v,
+        _ => return false,
+    };
+    values.all(|v| {
+        let ok = v.is_finite() && v > previous;
+        previous = v;
+        ok
+    })
+}
+
+backend_runtime_generate!(
+    /// Applies the modifiers, in order, to every value in the buffer, using the active
+    /// backend.
+    pub fn modify_buffer(data: &mut [f32], modifiers: &[Modifier]) {
+        modifiers.iter().for_each(|m| m.validate());
+        modify_noise::<S>(modifiers, data);
+    }
+);
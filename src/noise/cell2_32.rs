@@ -1,8 +1,12 @@
 use super::cellular_32::{hash_2d, hash_3d, BIT_10_MASK_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32};
+use crate::noise::ops::{round_i32, rsqrt_32};
 use crate::{Cell2ReturnType, CellDistanceFunction};
 
 use simdeez::prelude::*;
 
+/// Highest F-index (`index0` / `index1`) the cellular2 functions can return.
+pub const MAX_DISTANCE_INDEX: usize = 15;
+
 #[inline(always)]
 pub fn cellular2_2d<S: Simd>(
     x: S::Vf32,
@@ -14,10 +18,40 @@ pub fn cellular2_2d<S: Simd>(
     index1: usize,
     seed: i32,
 ) -> S::Vf32 {
-    let mut distance: [S::Vf32; 4] = [S::Vf32::set1(999999.0); 4];
+    cellular2_2d_radius::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        1,
+        seed,
+    )
+}
+
+/// Like `cellular2_2d`, but visits every cell within `search_radius` cells of the sample
+/// point, so high `jitter` values and higher F-indices still see the true nearest points.
+#[inline(always)]
+pub fn cellular2_2d_radius<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
+    let mut distance: [S::Vf32; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf32::set1(999999.0); MAX_DISTANCE_INDEX + 1];
 
-    let mut xc = x.cast_i32() - S::Vi32::set1(1);
-    let mut yc_base = y.cast_i32() - S::Vi32::set1(1);
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(radius);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(radius);
 
     let mut xcf = xc.cast_f32() - x;
     let ycf_base = yc_base.cast_f32() - y;
@@ -25,15 +59,15 @@ pub fn cellular2_2d<S: Simd>(
     xc = xc * S::Vi32::set1(X_PRIME_32);
     yc_base = yc_base * S::Vi32::set1(Y_PRIME_32);
 
-    for _x in 0..3 {
+    for _x in 0..span {
         let mut ycf = ycf_base;
         let mut yc = yc_base;
-        for _y in 0..3 {
+        for _y in 0..span {
             let hash = hash_2d::<S>(seed, xc, yc);
             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
             let mut yd =
                 ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
-            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
             xd = (xd * inv_mag) + xcf;
             yd = (yd * inv_mag) + ycf;
 
@@ -80,11 +114,41 @@ pub fn cellular2_3d<S: Simd>(
     index1: usize,
     seed: i32,
 ) -> S::Vf32 {
-    let mut distance: [S::Vf32; 4] = [S::Vf32::set1(999999.0); 4];
+    cellular2_3d_with_z_prime::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        Z_PRIME_32,
+    )
+}
 
-    let mut xc = x.cast_i32() - S::Vi32::set1(1);
-    let mut yc_base = y.cast_i32() - S::Vi32::set1(1);
-    let mut zc_base = z.cast_i32() - S::Vi32::set1(1);
+/// Like `cellular2_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn cellular2_3d_with_z_prime<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+    z_prime: i32,
+) -> S::Vf32 {
+    let mut distance: [S::Vf32; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf32::set1(999999.0); MAX_DISTANCE_INDEX + 1];
+
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(1);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(1);
+    let mut zc_base = round_i32::<S>(z) - S::Vi32::set1(1);
 
     let mut xcf = xc.cast_f32() - x;
     let ycf_base = yc_base.cast_f32() - y;
@@ -92,7 +156,7 @@ pub fn cellular2_3d<S: Simd>(
 
     xc = xc * S::Vi32::set1(X_PRIME_32);
     yc_base = yc_base * S::Vi32::set1(Y_PRIME_32);
-    zc_base = zc_base * S::Vi32::set1(Z_PRIME_32);
+    zc_base = zc_base * S::Vi32::set1(z_prime);
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -108,7 +172,7 @@ pub fn cellular2_3d<S: Simd>(
                     - S::Vf32::set1(511.5);
                 let mut zd = ((hash >> 20) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                     - S::Vf32::set1(511.5);
-                let inv_mag = jitter * ((xd * xd) + ((yd * yd) + (zd * zd))).rsqrt();
+                let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
                 xd = (xd * inv_mag) + xcf;
                 yd = (yd * inv_mag) + ycf;
                 zd = (zd * inv_mag) + zcf;
@@ -129,7 +193,93 @@ pub fn cellular2_3d<S: Simd>(
                     i -= 1;
                 }
                 zcf = ycf + S::Vf32::set1(1.0);
-                zc = yc + S::Vi32::set1(Z_PRIME_32);
+                zc = yc + S::Vi32::set1(z_prime);
+            }
+            ycf = ycf + S::Vf32::set1(1.0);
+            yc = yc + S::Vi32::set1(Y_PRIME_32);
+        }
+        xcf = xcf + S::Vf32::set1(1.0);
+        xc = xc + S::Vi32::set1(X_PRIME_32);
+    }
+
+    match return_type {
+        Cell2ReturnType::Distance2 => distance[index1],
+        Cell2ReturnType::Distance2Add => distance[index0] + distance[index1],
+        Cell2ReturnType::Distance2Sub => distance[index0] - distance[index1],
+        Cell2ReturnType::Distance2Mul => distance[index0] * distance[index1],
+        Cell2ReturnType::Distance2Div => distance[index0] / distance[index1],
+    }
+}
+
+/// Like `cellular2_3d`, but visits every cell within `search_radius` cells of the sample
+/// point. Unlike `cellular2_3d`, which keeps its original neighbourhood walk so existing
+/// output stays stable, this steps through the z axis one cell at a time.
+#[inline(always)]
+pub fn cellular2_3d_radius<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
+    let mut distance: [S::Vf32; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf32::set1(999999.0); MAX_DISTANCE_INDEX + 1];
+
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(radius);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(radius);
+    let mut zc_base = round_i32::<S>(z) - S::Vi32::set1(radius);
+
+    let mut xcf = xc.cast_f32() - x;
+    let ycf_base = yc_base.cast_f32() - y;
+    let zcf_base = zc_base.cast_f32() - z;
+
+    xc = xc * S::Vi32::set1(X_PRIME_32);
+    yc_base = yc_base * S::Vi32::set1(Y_PRIME_32);
+    zc_base = zc_base * S::Vi32::set1(Z_PRIME_32);
+
+    for _x in 0..span {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..span {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..span {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd =
+                    (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => (xd.abs() + yd.abs()) + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let mut i = index1;
+                while i > 0 {
+                    distance[i] = distance[i].min(new_distance).max(distance[i - 1]);
+                    distance[0] = distance[0].min(new_distance);
+                    i -= 1;
+                }
+                zcf = zcf + S::Vf32::set1(1.0);
+                zc = zc + S::Vi32::set1(Z_PRIME_32);
             }
             ycf = ycf + S::Vf32::set1(1.0);
             yc = yc + S::Vi32::set1(Y_PRIME_32);
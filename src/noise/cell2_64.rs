@@ -1,5 +1,7 @@
+use super::cell2_32::MAX_DISTANCE_INDEX;
 use super::cellular_32::{BIT_10_MASK_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
 use super::cellular_64::{hash_2d, hash_3d};
+use crate::noise::ops::rsqrt_64;
 use crate::{Cell2ReturnType, CellDistanceFunction};
 
 use simdeez::prelude::*;
@@ -15,10 +17,40 @@ pub fn cellular2_2d<S: Simd>(
     index1: usize,
     seed: i64,
 ) -> S::Vf64 {
-    let mut distance: [S::Vf64; 4] = [S::Vf64::set1(999999.0); 4];
+    cellular2_2d_radius::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        1,
+        seed,
+    )
+}
 
-    let mut xc = x.cast_i64() - S::Vi64::set1(1);
-    let mut yc_base = y.cast_i64() - S::Vi64::set1(1);
+/// Like `cellular2_2d`, but visits every cell within `search_radius` cells of the sample
+/// point, so high `jitter` values and higher F-indices still see the true nearest points.
+#[inline(always)]
+pub fn cellular2_2d_radius<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
+    let mut distance: [S::Vf64; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf64::set1(999999.0); MAX_DISTANCE_INDEX + 1];
+
+    let mut xc = x.cast_i64() - S::Vi64::set1(radius);
+    let mut yc_base = y.cast_i64() - S::Vi64::set1(radius);
 
     let mut xcf = xc.cast_f64() - x;
     let ycf_base = yc_base.cast_f64() - y;
@@ -26,15 +58,15 @@ pub fn cellular2_2d<S: Simd>(
     xc = xc * S::Vi64::set1(X_PRIME_64);
     yc_base = yc_base * S::Vi64::set1(Y_PRIME_64);
 
-    for _x in 0..3 {
+    for _x in 0..span {
         let mut ycf = ycf_base;
         let mut yc = yc_base;
-        for _y in 0..3 {
+        for _y in 0..span {
             let hash = hash_2d::<S>(seed, xc, yc);
             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
             let mut yd =
                 ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
-            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
             xd = (xd * inv_mag) + xcf;
             yd = (yd * inv_mag) + ycf;
 
@@ -81,7 +113,37 @@ pub fn cellular2_3d<S: Simd>(
     index1: usize,
     seed: i64,
 ) -> S::Vf64 {
-    let mut distance: [S::Vf64; 4] = [S::Vf64::set1(999999.0); 4];
+    cellular2_3d_with_z_prime::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        Z_PRIME_64,
+    )
+}
+
+/// Like `cellular2_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn cellular2_3d_with_z_prime<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    seed: i64,
+    z_prime: i64,
+) -> S::Vf64 {
+    let mut distance: [S::Vf64; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf64::set1(999999.0); MAX_DISTANCE_INDEX + 1];
 
     let mut xc = x.cast_i64() - S::Vi64::set1(1);
     let mut yc_base = y.cast_i64() - S::Vi64::set1(1);
@@ -93,7 +155,7 @@ pub fn cellular2_3d<S: Simd>(
 
     xc = xc * S::Vi64::set1(X_PRIME_64);
     yc_base = yc_base * S::Vi64::set1(Y_PRIME_64);
-    zc_base = zc_base * S::Vi64::set1(Z_PRIME_64);
+    zc_base = zc_base * S::Vi64::set1(z_prime);
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -109,7 +171,7 @@ pub fn cellular2_3d<S: Simd>(
                     - S::Vf64::set1(511.5);
                 let mut zd = ((hash >> 20) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                     - S::Vf64::set1(511.5);
-                let inv_mag = jitter * ((xd * xd) + ((yd * yd) + (zd * zd))).rsqrt();
+                let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
                 xd = (xd * inv_mag) + xcf;
                 yd = (yd * inv_mag) + ycf;
                 zd = (zd * inv_mag) + zcf;
@@ -130,7 +192,93 @@ pub fn cellular2_3d<S: Simd>(
                     i -= 1;
                 }
                 zcf = ycf + S::Vf64::set1(1.0);
-                zc = yc + S::Vi64::set1(Z_PRIME_64);
+                zc = yc + S::Vi64::set1(z_prime);
+            }
+            ycf = ycf + S::Vf64::set1(1.0);
+            yc = yc + S::Vi64::set1(Y_PRIME_64);
+        }
+        xcf = xcf + S::Vf64::set1(1.0);
+        xc = xc + S::Vi64::set1(X_PRIME_64);
+    }
+
+    match return_type {
+        Cell2ReturnType::Distance2 => distance[index1],
+        Cell2ReturnType::Distance2Add => distance[index0] + distance[index1],
+        Cell2ReturnType::Distance2Sub => distance[index0] - distance[index1],
+        Cell2ReturnType::Distance2Mul => distance[index0] * distance[index1],
+        Cell2ReturnType::Distance2Div => distance[index0] / distance[index1],
+    }
+}
+
+/// Like `cellular2_3d`, but visits every cell within `search_radius` cells of the sample
+/// point. Unlike `cellular2_3d`, which keeps its original neighbourhood walk so existing
+/// output stays stable, this steps through the z axis one cell at a time.
+#[inline(always)]
+pub fn cellular2_3d_radius<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
+    let mut distance: [S::Vf64; MAX_DISTANCE_INDEX + 1] =
+        [S::Vf64::set1(999999.0); MAX_DISTANCE_INDEX + 1];
+
+    let mut xc = x.cast_i64() - S::Vi64::set1(radius);
+    let mut yc_base = y.cast_i64() - S::Vi64::set1(radius);
+    let mut zc_base = z.cast_i64() - S::Vi64::set1(radius);
+
+    let mut xcf = xc.cast_f64() - x;
+    let ycf_base = yc_base.cast_f64() - y;
+    let zcf_base = zc_base.cast_f64() - z;
+
+    xc = xc * S::Vi64::set1(X_PRIME_64);
+    yc_base = yc_base * S::Vi64::set1(Y_PRIME_64);
+    zc_base = zc_base * S::Vi64::set1(Z_PRIME_64);
+
+    for _x in 0..span {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..span {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..span {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd =
+                    (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => xd.abs() + yd.abs() + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let mut i = index1;
+                while i > 0 {
+                    distance[i] = distance[i].min(new_distance).max(distance[i - 1]);
+                    distance[0] = distance[0].min(new_distance);
+                    i -= 1;
+                }
+                zcf = zcf + S::Vf64::set1(1.0);
+                zc = zc + S::Vi64::set1(Z_PRIME_64);
             }
             ycf = ycf + S::Vf64::set1(1.0);
             yc = yc + S::Vi64::set1(Y_PRIME_64);
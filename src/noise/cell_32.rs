@@ -1,7 +1,8 @@
 use super::cellular_32::{
     hash_2d, hash_3d, BIT_10_MASK_32, HASH_2_FLOAT_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32,
 };
-use crate::{CellDistanceFunction, CellReturnType};
+use crate::noise::ops::{round_i32, rsqrt_32};
+use crate::{CellDistanceFunction, CellLattice, CellReturnType};
 
 use simdeez::prelude::*;
 
@@ -14,9 +15,27 @@ pub fn cellular_2d<S: Simd>(
     jitter: S::Vf32,
     seed: i32,
 ) -> S::Vf32 {
+    cellular_2d_radius::<S>(x, y, distance_function, return_type, jitter, 1, seed)
+}
+
+/// Like `cellular_2d`, but visits every cell within `search_radius` cells of the sample
+/// point instead of only the direct neighbours. A radius of 2 (a 5x5 neighbourhood) is
+/// needed to always find the nearest feature point once `jitter` grows beyond about 0.5.
+#[inline(always)]
+pub fn cellular_2d_radius<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
     let mut distance = S::Vf32::set1(999999.0);
-    let mut xc = x.cast_i32() - S::Vi32::set1(1);
-    let mut yc_base = y.cast_i32() - S::Vi32::set1(1);
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(radius);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(radius);
 
     let mut xcf = xc.cast_f32() - x;
     let ycf_base = yc_base.cast_f32() - y;
@@ -27,17 +46,17 @@ pub fn cellular_2d<S: Simd>(
         CellReturnType::Distance => {
             match distance_function {
                 CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut xd2 = xd * xd;
-                            let inv_mag = jitter * (xd2 + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>(xd2 + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
                             xd2 = xd * xd;
@@ -52,16 +71,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -76,16 +95,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -110,16 +129,16 @@ pub fn cellular_2d<S: Simd>(
             let mut cell_value = S::Vf32::zeroes();
             match distance_function {
                 CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -137,16 +156,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -164,16 +183,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                                 - S::Vf32::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -209,13 +228,38 @@ pub fn cellular_3d<S: Simd>(
     return_type: CellReturnType,
     jitter: S::Vf32,
     seed: i32,
+) -> S::Vf32 {
+    cellular_3d_with_z_prime::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        Z_PRIME_32,
+    )
+}
+
+/// Like `cellular_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn cellular_3d_with_z_prime<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    seed: i32,
+    z_prime: i32,
 ) -> S::Vf32 {
     let mut distance = S::Vf32::set1(999999.0);
     let mut cell_value = S::Vf32::zeroes();
 
-    let mut xc = x.cast_i32() - S::Vi32::set1(1);
-    let mut yc_base = y.cast_i32() - S::Vi32::set1(1);
-    let mut zc_base = z.cast_i32() - S::Vi32::set1(1);
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(1);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(1);
+    let mut zc_base = round_i32::<S>(z) - S::Vi32::set1(1);
 
     let mut xcf = xc.cast_f32() - x;
     let ycf_base = yc_base.cast_f32() - y;
@@ -223,7 +267,7 @@ pub fn cellular_3d<S: Simd>(
 
     xc = xc * S::Vi32::set1(X_PRIME_32);
     yc_base = yc_base * S::Vi32::set1(Y_PRIME_32);
-    zc_base = zc_base * S::Vi32::set1(Z_PRIME_32);
+    zc_base = zc_base * S::Vi32::set1(z_prime);
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -239,7 +283,7 @@ pub fn cellular_3d<S: Simd>(
                     - S::Vf32::set1(511.5);
                 let mut zd = ((hash >> 20) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
                     - S::Vf32::set1(511.5);
-                let inv_mag = jitter * ((xd * xd) + ((yd * yd) + (zd * zd))).rsqrt();
+                let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
                 xd = (xd * inv_mag) + xcf;
                 yd = (yd * inv_mag) + ycf;
                 zd = (zd * inv_mag) + zcf;
@@ -258,7 +302,7 @@ pub fn cellular_3d<S: Simd>(
                 distance = new_distance.min(distance);
                 cell_value = closer.blendv(cell_value, new_cell_value);
                 zcf = ycf + S::Vf32::set1(1.0);
-                zc = yc + S::Vi32::set1(Z_PRIME_32);
+                zc = yc + S::Vi32::set1(z_prime);
             }
             ycf = ycf + S::Vf32::set1(1.0);
             yc = yc + S::Vi32::set1(Y_PRIME_32);
@@ -272,3 +316,247 @@ pub fn cellular_3d<S: Simd>(
         CellReturnType::CellValue => cell_value,
     }
 }
+
+/// Like `cellular_3d`, but visits every cell within `search_radius` cells of the sample
+/// point. Unlike `cellular_3d`, which keeps its original neighbourhood walk so existing
+/// output stays stable, this steps through the z axis one cell at a time.
+#[inline(always)]
+pub fn cellular_3d_radius<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf32::set1(999999.0);
+    let mut cell_value = S::Vf32::zeroes();
+
+    let mut xc = round_i32::<S>(x) - S::Vi32::set1(radius);
+    let mut yc_base = round_i32::<S>(y) - S::Vi32::set1(radius);
+    let mut zc_base = round_i32::<S>(z) - S::Vi32::set1(radius);
+
+    let mut xcf = xc.cast_f32() - x;
+    let ycf_base = yc_base.cast_f32() - y;
+    let zcf_base = zc_base.cast_f32() - z;
+
+    xc = xc * S::Vi32::set1(X_PRIME_32);
+    yc_base = yc_base * S::Vi32::set1(Y_PRIME_32);
+    zc_base = zc_base * S::Vi32::set1(Z_PRIME_32);
+
+    for _x in 0..span {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..span {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..span {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd =
+                    (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_cell_value = S::Vf32::set1(HASH_2_FLOAT_32) * hash.cast_f32();
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => (xd.abs() + yd.abs()) + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let closer = new_distance.cmp_lt(distance);
+                distance = new_distance.min(distance);
+                cell_value = closer.blendv(cell_value, new_cell_value);
+                zcf = zcf + S::Vf32::set1(1.0);
+                zc = zc + S::Vi32::set1(Z_PRIME_32);
+            }
+            ycf = ycf + S::Vf32::set1(1.0);
+            yc = yc + S::Vi32::set1(Y_PRIME_32);
+        }
+        xcf = xcf + S::Vf32::set1(1.0);
+        xc = xc + S::Vi32::set1(X_PRIME_32);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+/// Cellular noise with the feature points placed on `lattice` instead of the integer grid.
+/// `search_radius` is measured in lattice cells.
+#[inline(always)]
+pub fn cellular_2d_lattice<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    lattice: CellLattice,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let (basis, inverse) = lattice.basis_2d();
+    let m = |v: f64| S::Vf32::set1(v as f32);
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf32::set1(999999.0);
+    let mut cell_value = S::Vf32::zeroes();
+
+    let u = (x * m(inverse[0][0])) + (y * m(inverse[0][1]));
+    let v = (x * m(inverse[1][0])) + (y * m(inverse[1][1]));
+
+    let mut uc = round_i32::<S>(u) - S::Vi32::set1(radius);
+    let vc_base = round_i32::<S>(v) - S::Vi32::set1(radius);
+
+    let mut ucf = uc.cast_f32();
+    let vcf_base = vc_base.cast_f32();
+
+    uc = uc * S::Vi32::set1(X_PRIME_32);
+    let vc_base = vc_base * S::Vi32::set1(Y_PRIME_32);
+
+    for _u in 0..span {
+        let mut vcf = vcf_base;
+        let mut vc = vc_base;
+        for _v in 0..span {
+            let hash = hash_2d::<S>(seed, uc, vc);
+            let mut xd = (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
+            let mut yd =
+                ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
+            let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + (yd * yd));
+            let xcf = (ucf * m(basis[0][0])) + (vcf * m(basis[0][1])) - x;
+            let ycf = (ucf * m(basis[1][0])) + (vcf * m(basis[1][1])) - y;
+            xd = (xd * inv_mag) + xcf;
+            yd = (yd * inv_mag) + ycf;
+
+            let new_cell_value = S::Vf32::set1(HASH_2_FLOAT_32) * hash.cast_f32();
+            let new_distance = match distance_function {
+                CellDistanceFunction::Euclidean => (xd * xd) + (yd * yd),
+                CellDistanceFunction::Manhattan => xd.abs() + yd.abs(),
+                CellDistanceFunction::Natural => {
+                    let euc = (xd * xd) + (yd * yd);
+                    let man = xd.abs() + yd.abs();
+                    euc + man
+                }
+            };
+            let closer = new_distance.cmp_lt(distance);
+            distance = new_distance.min(distance);
+            cell_value = closer.blendv(cell_value, new_cell_value);
+
+            vcf = vcf + S::Vf32::set1(1.0);
+            vc = vc + S::Vi32::set1(Y_PRIME_32);
+        }
+        ucf = ucf + S::Vf32::set1(1.0);
+        uc = uc + S::Vi32::set1(X_PRIME_32);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+/// Cellular noise with the feature points placed on `lattice` instead of the integer grid.
+/// `search_radius` is measured in lattice cells.
+#[inline(always)]
+pub fn cellular_3d_lattice<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    lattice: CellLattice,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    search_radius: usize,
+    seed: i32,
+) -> S::Vf32 {
+    let (basis, inverse) = lattice.basis_3d();
+    let m = |v: f64| S::Vf32::set1(v as f32);
+    let radius = search_radius as i32;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf32::set1(999999.0);
+    let mut cell_value = S::Vf32::zeroes();
+
+    let u = (x * m(inverse[0][0])) + (y * m(inverse[0][1])) + (z * m(inverse[0][2]));
+    let v = (x * m(inverse[1][0])) + (y * m(inverse[1][1])) + (z * m(inverse[1][2]));
+    let w = (x * m(inverse[2][0])) + (y * m(inverse[2][1])) + (z * m(inverse[2][2]));
+
+    let mut uc = round_i32::<S>(u) - S::Vi32::set1(radius);
+    let vc_base = round_i32::<S>(v) - S::Vi32::set1(radius);
+    let wc_base = round_i32::<S>(w) - S::Vi32::set1(radius);
+
+    let mut ucf = uc.cast_f32();
+    let vcf_base = vc_base.cast_f32();
+    let wcf_base = wc_base.cast_f32();
+
+    uc = uc * S::Vi32::set1(X_PRIME_32);
+    let vc_base = vc_base * S::Vi32::set1(Y_PRIME_32);
+    let wc_base = wc_base * S::Vi32::set1(Z_PRIME_32);
+
+    for _u in 0..span {
+        let mut vcf = vcf_base;
+        let mut vc = vc_base;
+        for _v in 0..span {
+            let mut wcf = wcf_base;
+            let mut wc = wc_base;
+            for _w in 0..span {
+                let hash = hash_3d::<S>(seed, uc, vc, wc);
+                let mut xd =
+                    (hash & S::Vi32::set1(BIT_10_MASK_32)).cast_f32() - S::Vf32::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi32::set1(BIT_10_MASK_32)).cast_f32()
+                    - S::Vf32::set1(511.5);
+                let inv_mag = jitter * rsqrt_32::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                let xcf =
+                    (ucf * m(basis[0][0])) + (vcf * m(basis[0][1])) + (wcf * m(basis[0][2])) - x;
+                let ycf =
+                    (ucf * m(basis[1][0])) + (vcf * m(basis[1][1])) + (wcf * m(basis[1][2])) - y;
+                let zcf =
+                    (ucf * m(basis[2][0])) + (vcf * m(basis[2][1])) + (wcf * m(basis[2][2])) - z;
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_cell_value = S::Vf32::set1(HASH_2_FLOAT_32) * hash.cast_f32();
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => (xd.abs() + yd.abs()) + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let closer = new_distance.cmp_lt(distance);
+                distance = new_distance.min(distance);
+                cell_value = closer.blendv(cell_value, new_cell_value);
+
+                wcf = wcf + S::Vf32::set1(1.0);
+                wc = wc + S::Vi32::set1(Z_PRIME_32);
+            }
+            vcf = vcf + S::Vf32::set1(1.0);
+            vc = vc + S::Vi32::set1(Y_PRIME_32);
+        }
+        ucf = ucf + S::Vf32::set1(1.0);
+        uc = uc + S::Vi32::set1(X_PRIME_32);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
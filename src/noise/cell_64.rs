@@ -1,9 +1,21 @@
 use super::cellular_32::{BIT_10_MASK_64, HASH_2_FLOAT_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
-use super::cellular_64::{hash_2d, hash_3d};
-use crate::{CellDistanceFunction, CellReturnType};
+use super::cellular_64::{hash_2d, hash_3d, hash_to_float};
+use crate::noise::ops::rsqrt_64;
+use crate::{AlgorithmVersion, CellDistanceFunction, CellLattice, CellReturnType};
 
 use simdeez::prelude::*;
 
+/// The value of the cell with `hash`, from the low 32 bits of the hash if `low_hash_bits`
+/// is set, see `AlgorithmVersion::low_hash_bits`.
+#[inline(always)]
+fn hash_to_cell_value<S: Simd>(hash: S::Vi64, low_hash_bits: bool) -> S::Vf64 {
+    if low_hash_bits {
+        hash_to_float::<S>(hash)
+    } else {
+        S::Vf64::set1(HASH_2_FLOAT_64) * hash.cast_f64()
+    }
+}
+
 #[inline(always)]
 pub fn cellular_2d<S: Simd>(
     x: S::Vf64,
@@ -13,9 +25,52 @@ pub fn cellular_2d<S: Simd>(
     jitter: S::Vf64,
     seed: i64,
 ) -> S::Vf64 {
+    cellular_2d_radius::<S>(x, y, distance_function, return_type, jitter, 1, seed)
+}
+
+/// Like `cellular_2d`, but visits every cell within `search_radius` cells of the sample
+/// point instead of only the direct neighbours. A radius of 2 (a 5x5 neighbourhood) is
+/// needed to always find the nearest feature point once `jitter` grows beyond about 0.5.
+#[inline(always)]
+pub fn cellular_2d_radius<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    cellular_2d_radius_with_hash_bits::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        search_radius,
+        seed,
+        AlgorithmVersion::LATEST.low_hash_bits(),
+    )
+}
+
+/// Like `cellular_2d_radius`, but maps the cell values as `low_hash_bits` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn cellular_2d_radius_with_hash_bits<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    search_radius: usize,
+    seed: i64,
+    low_hash_bits: bool,
+) -> S::Vf64 {
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
     let mut distance = S::Vf64::set1(999999.0);
-    let mut xc = x.cast_i64() - S::Vi64::set1(1);
-    let mut yc_base = y.cast_i64() - S::Vi64::set1(1);
+    let mut xc = x.cast_i64() - S::Vi64::set1(radius);
+    let mut yc_base = y.cast_i64() - S::Vi64::set1(radius);
 
     let mut xcf = xc.cast_f64() - x;
     let ycf_base = yc_base.cast_f64() - y;
@@ -26,17 +81,17 @@ pub fn cellular_2d<S: Simd>(
         CellReturnType::Distance => {
             match distance_function {
                 CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut xd2 = xd * xd;
-                            let inv_mag = jitter * (xd2 + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>(xd2 + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
                             xd2 = xd * xd;
@@ -51,16 +106,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -75,16 +130,16 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
@@ -109,20 +164,20 @@ pub fn cellular_2d<S: Simd>(
             let mut cell_value = S::Vf64::zeroes();
             match distance_function {
                 CellDistanceFunction::Euclidean => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
-                            let new_cell_value = S::Vf64::set1(HASH_2_FLOAT_64) * hash.cast_f64();
+                            let new_cell_value = hash_to_cell_value::<S>(hash, low_hash_bits);
                             let new_distance = (xd * xd) + (yd * yd);
                             let closer = new_distance.cmp_lt(distance);
                             distance = new_distance.min(distance);
@@ -136,20 +191,20 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Manhattan => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
-                            let new_cell_value = S::Vf64::set1(HASH_2_FLOAT_64) * hash.cast_f64();
+                            let new_cell_value = hash_to_cell_value::<S>(hash, low_hash_bits);
                             let new_distance = xd.abs() + yd.abs();
                             let closer = new_distance.cmp_lt(distance);
                             distance = new_distance.min(distance);
@@ -163,20 +218,20 @@ pub fn cellular_2d<S: Simd>(
                     }
                 }
                 CellDistanceFunction::Natural => {
-                    for _x in 0..3 {
+                    for _x in 0..span {
                         let mut ycf = ycf_base;
                         let mut yc = yc_base;
-                        for _y in 0..3 {
+                        for _y in 0..span {
                             let hash = hash_2d::<S>(seed, xc, yc);
                             let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
                             let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                                 - S::Vf64::set1(511.5);
-                            let inv_mag = jitter * ((xd * xd) + (yd * yd)).rsqrt();
+                            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
                             xd = (xd * inv_mag) + xcf;
                             yd = (yd * inv_mag) + ycf;
 
-                            let new_cell_value = S::Vf64::set1(HASH_2_FLOAT_64) * hash.cast_f64();
+                            let new_cell_value = hash_to_cell_value::<S>(hash, low_hash_bits);
                             let new_distance = {
                                 let euc = (xd * xd) + (yd * yd);
                                 let man = xd.abs() + yd.abs();
@@ -208,6 +263,33 @@ pub fn cellular_3d<S: Simd>(
     return_type: CellReturnType,
     jitter: S::Vf64,
     seed: i64,
+) -> S::Vf64 {
+    cellular_3d_with_z_prime::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        Z_PRIME_64,
+        AlgorithmVersion::LATEST.low_hash_bits(),
+    )
+}
+
+/// Like `cellular_3d`, but hashes the z cell coordinates with `z_prime` and maps the cell
+/// values as `low_hash_bits` says, so older `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn cellular_3d_with_z_prime<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    seed: i64,
+    z_prime: i64,
+    low_hash_bits: bool,
 ) -> S::Vf64 {
     let mut distance = S::Vf64::set1(999999.0);
     let mut cell_value = S::Vf64::zeroes();
@@ -222,7 +304,7 @@ pub fn cellular_3d<S: Simd>(
 
     xc = xc * S::Vi64::set1(X_PRIME_64);
     yc_base = yc_base * S::Vi64::set1(Y_PRIME_64);
-    zc_base = zc_base * S::Vi64::set1(Z_PRIME_64);
+    zc_base = zc_base * S::Vi64::set1(z_prime);
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -238,12 +320,12 @@ pub fn cellular_3d<S: Simd>(
                     - S::Vf64::set1(511.5);
                 let mut zd = ((hash >> 20) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
                     - S::Vf64::set1(511.5);
-                let inv_mag = jitter * ((xd * xd) + ((yd * yd) + (zd * zd))).rsqrt();
+                let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
                 xd = (xd * inv_mag) + xcf;
                 yd = (yd * inv_mag) + ycf;
                 zd = (zd * inv_mag) + zcf;
 
-                let new_cell_value = S::Vf64::set1(HASH_2_FLOAT_64) * hash.cast_f64();
+                let new_cell_value = hash_to_cell_value::<S>(hash, low_hash_bits);
                 let new_distance = match distance_function {
                     CellDistanceFunction::Euclidean => (xd * xd) + (yd * yd) + (zd * zd),
                     CellDistanceFunction::Manhattan => xd.abs() + yd.abs() + zd.abs(),
@@ -257,7 +339,7 @@ pub fn cellular_3d<S: Simd>(
                 distance = new_distance.min(distance);
                 cell_value = closer.blendv(cell_value, new_cell_value);
                 zcf = ycf + S::Vf64::set1(1.0);
-                zc = yc + S::Vi64::set1(Z_PRIME_64);
+                zc = yc + S::Vi64::set1(z_prime);
             }
             ycf = ycf + S::Vf64::set1(1.0);
             yc = yc + S::Vi64::set1(Y_PRIME_64);
@@ -271,3 +353,247 @@ pub fn cellular_3d<S: Simd>(
         CellReturnType::CellValue => cell_value,
     }
 }
+
+/// Like `cellular_3d`, but visits every cell within `search_radius` cells of the sample
+/// point. Unlike `cellular_3d`, which keeps its original neighbourhood walk so existing
+/// output stays stable, this steps through the z axis one cell at a time.
+#[inline(always)]
+pub fn cellular_3d_radius<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf64::set1(999999.0);
+    let mut cell_value = S::Vf64::zeroes();
+
+    let mut xc = x.cast_i64() - S::Vi64::set1(radius);
+    let mut yc_base = y.cast_i64() - S::Vi64::set1(radius);
+    let mut zc_base = z.cast_i64() - S::Vi64::set1(radius);
+
+    let mut xcf = xc.cast_f64() - x;
+    let ycf_base = yc_base.cast_f64() - y;
+    let zcf_base = zc_base.cast_f64() - z;
+
+    xc = xc * S::Vi64::set1(X_PRIME_64);
+    yc_base = yc_base * S::Vi64::set1(Y_PRIME_64);
+    zc_base = zc_base * S::Vi64::set1(Z_PRIME_64);
+
+    for _x in 0..span {
+        let mut ycf = ycf_base;
+        let mut yc = yc_base;
+        for _y in 0..span {
+            let mut zcf = zcf_base;
+            let mut zc = zc_base;
+            for _z in 0..span {
+                let hash = hash_3d::<S>(seed, xc, yc, zc);
+                let mut xd =
+                    (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_cell_value = hash_to_float::<S>(hash);
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => (xd.abs() + yd.abs()) + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let closer = new_distance.cmp_lt(distance);
+                distance = new_distance.min(distance);
+                cell_value = closer.blendv(cell_value, new_cell_value);
+                zcf = zcf + S::Vf64::set1(1.0);
+                zc = zc + S::Vi64::set1(Z_PRIME_64);
+            }
+            ycf = ycf + S::Vf64::set1(1.0);
+            yc = yc + S::Vi64::set1(Y_PRIME_64);
+        }
+        xcf = xcf + S::Vf64::set1(1.0);
+        xc = xc + S::Vi64::set1(X_PRIME_64);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+/// Cellular noise with the feature points placed on `lattice` instead of the integer grid.
+/// `search_radius` is measured in lattice cells.
+#[inline(always)]
+pub fn cellular_2d_lattice<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    lattice: CellLattice,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    let (basis, inverse) = lattice.basis_2d();
+    let m = |v: f64| S::Vf64::set1(v);
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf64::set1(999999.0);
+    let mut cell_value = S::Vf64::zeroes();
+
+    let u = (x * m(inverse[0][0])) + (y * m(inverse[0][1]));
+    let v = (x * m(inverse[1][0])) + (y * m(inverse[1][1]));
+
+    let mut uc = u.cast_i64() - S::Vi64::set1(radius);
+    let vc_base = v.cast_i64() - S::Vi64::set1(radius);
+
+    let mut ucf = uc.cast_f64();
+    let vcf_base = vc_base.cast_f64();
+
+    uc = uc * S::Vi64::set1(X_PRIME_64);
+    let vc_base = vc_base * S::Vi64::set1(Y_PRIME_64);
+
+    for _u in 0..span {
+        let mut vcf = vcf_base;
+        let mut vc = vc_base;
+        for _v in 0..span {
+            let hash = hash_2d::<S>(seed, uc, vc);
+            let mut xd = (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
+            let mut yd =
+                ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
+            let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + (yd * yd));
+            let xcf = (ucf * m(basis[0][0])) + (vcf * m(basis[0][1])) - x;
+            let ycf = (ucf * m(basis[1][0])) + (vcf * m(basis[1][1])) - y;
+            xd = (xd * inv_mag) + xcf;
+            yd = (yd * inv_mag) + ycf;
+
+            let new_cell_value = hash_to_float::<S>(hash);
+            let new_distance = match distance_function {
+                CellDistanceFunction::Euclidean => (xd * xd) + (yd * yd),
+                CellDistanceFunction::Manhattan => xd.abs() + yd.abs(),
+                CellDistanceFunction::Natural => {
+                    let euc = (xd * xd) + (yd * yd);
+                    let man = xd.abs() + yd.abs();
+                    euc + man
+                }
+            };
+            let closer = new_distance.cmp_lt(distance);
+            distance = new_distance.min(distance);
+            cell_value = closer.blendv(cell_value, new_cell_value);
+
+            vcf = vcf + S::Vf64::set1(1.0);
+            vc = vc + S::Vi64::set1(Y_PRIME_64);
+        }
+        ucf = ucf + S::Vf64::set1(1.0);
+        uc = uc + S::Vi64::set1(X_PRIME_64);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
+
+/// Cellular noise with the feature points placed on `lattice` instead of the integer grid.
+/// `search_radius` is measured in lattice cells.
+#[inline(always)]
+pub fn cellular_3d_lattice<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    lattice: CellLattice,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    search_radius: usize,
+    seed: i64,
+) -> S::Vf64 {
+    let (basis, inverse) = lattice.basis_3d();
+    let m = |v: f64| S::Vf64::set1(v);
+    let radius = search_radius as i64;
+    let span = 2 * search_radius + 1;
+    let mut distance = S::Vf64::set1(999999.0);
+    let mut cell_value = S::Vf64::zeroes();
+
+    let u = (x * m(inverse[0][0])) + (y * m(inverse[0][1])) + (z * m(inverse[0][2]));
+    let v = (x * m(inverse[1][0])) + (y * m(inverse[1][1])) + (z * m(inverse[1][2]));
+    let w = (x * m(inverse[2][0])) + (y * m(inverse[2][1])) + (z * m(inverse[2][2]));
+
+    let mut uc = u.cast_i64() - S::Vi64::set1(radius);
+    let vc_base = v.cast_i64() - S::Vi64::set1(radius);
+    let wc_base = w.cast_i64() - S::Vi64::set1(radius);
+
+    let mut ucf = uc.cast_f64();
+    let vcf_base = vc_base.cast_f64();
+    let wcf_base = wc_base.cast_f64();
+
+    uc = uc * S::Vi64::set1(X_PRIME_64);
+    let vc_base = vc_base * S::Vi64::set1(Y_PRIME_64);
+    let wc_base = wc_base * S::Vi64::set1(Z_PRIME_64);
+
+    for _u in 0..span {
+        let mut vcf = vcf_base;
+        let mut vc = vc_base;
+        for _v in 0..span {
+            let mut wcf = wcf_base;
+            let mut wc = wc_base;
+            for _w in 0..span {
+                let hash = hash_3d::<S>(seed, uc, vc, wc);
+                let mut xd =
+                    (hash & S::Vi64::set1(BIT_10_MASK_64)).cast_f64() - S::Vf64::set1(511.5);
+                let mut yd = ((hash >> 10) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let mut zd = ((hash >> 20) & S::Vi64::set1(BIT_10_MASK_64)).cast_f64()
+                    - S::Vf64::set1(511.5);
+                let inv_mag = jitter * rsqrt_64::<S>((xd * xd) + ((yd * yd) + (zd * zd)));
+                let xcf =
+                    (ucf * m(basis[0][0])) + (vcf * m(basis[0][1])) + (wcf * m(basis[0][2])) - x;
+                let ycf =
+                    (ucf * m(basis[1][0])) + (vcf * m(basis[1][1])) + (wcf * m(basis[1][2])) - y;
+                let zcf =
+                    (ucf * m(basis[2][0])) + (vcf * m(basis[2][1])) + (wcf * m(basis[2][2])) - z;
+                xd = (xd * inv_mag) + xcf;
+                yd = (yd * inv_mag) + ycf;
+                zd = (zd * inv_mag) + zcf;
+
+                let new_cell_value = hash_to_float::<S>(hash);
+                let new_distance = match distance_function {
+                    CellDistanceFunction::Euclidean => (xd * xd) + ((yd * yd) + (zd * zd)),
+                    CellDistanceFunction::Manhattan => (xd.abs() + yd.abs()) + zd.abs(),
+                    CellDistanceFunction::Natural => {
+                        let euc = (xd * xd) + ((yd * yd) + (zd * zd));
+                        let man = (xd.abs() + yd.abs()) + zd.abs();
+                        euc + man
+                    }
+                };
+                let closer = new_distance.cmp_lt(distance);
+                distance = new_distance.min(distance);
+                cell_value = closer.blendv(cell_value, new_cell_value);
+
+                wcf = wcf + S::Vf64::set1(1.0);
+                wc = wc + S::Vi64::set1(Z_PRIME_64);
+            }
+            vcf = vcf + S::Vf64::set1(1.0);
+            vc = vc + S::Vi64::set1(Y_PRIME_64);
+        }
+        ucf = ucf + S::Vf64::set1(1.0);
+        uc = uc + S::Vi64::set1(X_PRIME_64);
+    }
+
+    match return_type {
+        CellReturnType::Distance => distance,
+        CellReturnType::CellValue => cell_value,
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+/// The layout of the feature points used for cellular noise.
+///
+/// All layouts are scaled to one feature point per unit area (2D) or volume (3D), so
+/// a given frequency gives roughly the same average cell size regardless of the lattice.
+pub enum CellLattice {
+    /// Integer grid, square in 2D and cubic in 3D
+    Square,
+    /// Triangular point lattice giving hexagonal cells, 2D only
+    Hexagonal,
+    /// Body-centered cubic lattice giving truncated octahedral cells, 3D only
+    BodyCenteredCubic,
+    /// Face-centered cubic lattice giving rhombic dodecahedral cells, 3D only
+    FaceCenteredCubic,
+}
+
+impl CellLattice {
+    /// Returns true if this lattice can be used for noise with `dim` dimensions.
+    pub fn supports_dimension(&self, dim: usize) -> bool {
+        match self {
+            CellLattice::Square => dim == 2 || dim == 3,
+            CellLattice::Hexagonal => dim == 2,
+            CellLattice::BodyCenteredCubic | CellLattice::FaceCenteredCubic => dim == 3,
+        }
+    }
+
+    /// Matrix mapping lattice coordinates to noise space, and its inverse.
+    pub(crate) fn basis_2d(&self) -> ([[f64; 2]; 2], [[f64; 2]; 2]) {
+        match self {
+            CellLattice::Hexagonal => {
+                let sqrt3 = 3f64.sqrt();
+                let s = (2.0 / sqrt3).sqrt();
+                (
+                    [[s, 0.5 * s], [0.0, 0.5 * sqrt3 * s]],
+                    [[1.0 / s, -1.0 / (sqrt3 * s)], [0.0, 2.0 / (sqrt3 * s)]],
+                )
+            }
+            _ => ([[1.0, 0.0], [0.0, 1.0]], [[1.0, 0.0], [0.0, 1.0]]),
+        }
+    }
+
+    /// Matrix mapping lattice coordinates to noise space, and its inverse.
+    pub(crate) fn basis_3d(&self) -> ([[f64; 3]; 3], [[f64; 3]; 3]) {
+        let scaled = |s: f64, m: [[f64; 3]; 3]| {
+            let mut r = m;
+            for row in r.iter_mut() {
+                for v in row.iter_mut() {
+                    *v *= s;
+                }
+            }
+            r
+        };
+        let a = [[-1.0, 1.0, 1.0], [1.0, -1.0, 1.0], [1.0, 1.0, -1.0]];
+        let b = [[0.0, 1.0, 1.0], [1.0, 0.0, 1.0], [1.0, 1.0, 0.0]];
+        match self {
+            CellLattice::BodyCenteredCubic => {
+                let s = 2f64.powf(1.0 / 3.0);
+                (scaled(0.5 * s, a), scaled(1.0 / s, b))
+            }
+            CellLattice::FaceCenteredCubic => {
+                let s = 4f64.powf(1.0 / 3.0);
+                (scaled(0.5 * s, b), scaled(1.0 / s, a))
+            }
+            _ => {
+                let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+                (identity, identity)
+            }
+        }
+    }
+}
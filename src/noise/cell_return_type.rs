@@ -1,4 +1,8 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Determines what final value is returned for the cell noise
 pub enum CellReturnType {
     /// Will return solid colors in each cell
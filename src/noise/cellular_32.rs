@@ -24,6 +24,15 @@ pub const Y_PRIME_64: i64 = 31337;
 pub const Z_PRIME_32: i32 = 6971;
 pub const Z_PRIME_64: i64 = 6971;
 
+pub const W_PRIME_32: i32 = 1013;
+pub const W_PRIME_64: i64 = 1013;
+
+#[inline(always)]
+pub fn hash_1d<S: Simd>(seed: i32, x: S::Vi32) -> S::Vi32 {
+    let hash = x ^ S::Vi32::set1(seed);
+    ((hash * hash) * S::Vi32::set1(60493)) * hash
+}
+
 #[inline(always)]
 pub fn hash_2d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32) -> S::Vi32 {
     let mut hash = x ^ S::Vi32::set1(seed);
@@ -38,3 +47,12 @@ pub fn hash_3d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32, z: S::Vi32) -> S::Vi3
     hash = z ^ hash;
     ((hash * hash) * S::Vi32::set1(60493)) * hash
 }
+
+#[inline(always)]
+pub fn hash_4d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32, z: S::Vi32, w: S::Vi32) -> S::Vi32 {
+    let mut hash = x ^ S::Vi32::set1(seed);
+    hash = y ^ hash;
+    hash = z ^ hash;
+    hash = w ^ hash;
+    ((hash * hash) * S::Vi32::set1(60493)) * hash
+}
@@ -1,5 +1,13 @@
+use super::cellular_32::HASH_2_FLOAT_64;
+
 use simdeez::prelude::*;
 
+#[inline(always)]
+pub fn hash_1d<S: Simd>(seed: i64, x: S::Vi64) -> S::Vi64 {
+    let hash = x ^ S::Vi64::set1(seed);
+    ((hash * hash) * S::Vi64::set1(60493)) * hash
+}
+
 #[inline(always)]
 pub fn hash_2d<S: Simd>(seed: i64, x: S::Vi64, y: S::Vi64) -> S::Vi64 {
     let mut hash = x ^ S::Vi64::set1(seed);
@@ -14,3 +22,19 @@ pub fn hash_3d<S: Simd>(seed: i64, x: S::Vi64, y: S::Vi64, z: S::Vi64) -> S::Vi6
     hash = z ^ hash;
     ((hash * hash) * S::Vi64::set1(60493)) * hash
 }
+
+#[inline(always)]
+pub fn hash_4d<S: Simd>(seed: i64, x: S::Vi64, y: S::Vi64, z: S::Vi64, w: S::Vi64) -> S::Vi64 {
+    let mut hash = x ^ S::Vi64::set1(seed);
+    hash = y ^ hash;
+    hash = z ^ hash;
+    hash = w ^ hash;
+    ((hash * hash) * S::Vi64::set1(60493)) * hash
+}
+
+/// Maps the low 32 bits of the hash to [-1, 1], which matches the f32 version.
+#[inline(always)]
+pub fn hash_to_float<S: Simd>(hash: S::Vi64) -> S::Vf64 {
+    let low = (hash & S::Vi64::set1(0xFFFF_FFFF)) ^ S::Vi64::set1(0x8000_0000);
+    S::Vf64::set1(HASH_2_FLOAT_64) * (low - S::Vi64::set1(0x8000_0000)).cast_f64()
+}
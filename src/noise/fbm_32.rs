@@ -1,4 +1,5 @@
-use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::cellular_32::Z_PRIME_32;
+use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d_with_z_prime, simplex_4d};
 
 use simdeez::prelude::*;
 
@@ -46,6 +47,21 @@ pub fn fbm_2d<S: Simd>(
 
 #[inline(always)]
 pub fn fbm_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    fbm_3d_with_z_prime::<S>(x, y, z, lac, gain, octaves, seed, Z_PRIME_32)
+}
+
+/// Like `fbm_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn fbm_3d_with_z_prime<S: Simd>(
     mut x: S::Vf32,
     mut y: S::Vf32,
     mut z: S::Vf32,
@@ -53,8 +69,9 @@ pub fn fbm_3d<S: Simd>(
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    z_prime: i32,
 ) -> S::Vf32 {
-    let mut result = simplex_3d::<S>(x, y, z, seed);
+    let mut result = simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime);
     let mut amp = S::Vf32::set1(1.0);
 
     for _ in 1..octaves {
@@ -62,7 +79,7 @@ pub fn fbm_3d<S: Simd>(
         y = y * lac;
         z = z * lac;
         amp = amp * gain;
-        result = (simplex_3d::<S>(x, y, z, seed) * amp) + result;
+        result = (simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime) * amp) + result;
     }
 
     result
@@ -1,22 +1,47 @@
-use crate::noise::simplex_64::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::cellular_32::Z_PRIME_64;
+use crate::noise::simplex_64::{
+    simplex_1d_with_masks, simplex_2d_with_masks, simplex_3d_with_z_prime, simplex_4d,
+};
+use crate::AlgorithmVersion;
 
 use simdeez::prelude::*;
 
 #[inline(always)]
 pub fn fbm_1d<S: Simd>(
+    x: S::Vf64,
+    lacunarity: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    fbm_1d_with_masks::<S>(
+        x,
+        lacunarity,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `fbm_1d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn fbm_1d_with_masks<S: Simd>(
     mut x: S::Vf64,
     lacunarity: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
     let mut amp = S::Vf64::set1(1.0);
-    let mut result = simplex_1d::<S>(x, seed);
+    let mut result = simplex_1d_with_masks::<S>(x, seed, bitcast_masks);
 
     for _ in 1..octaves {
         x = x * lacunarity;
         amp = amp * gain;
-        result = result + simplex_1d::<S>(x, seed);
+        result = result + simplex_1d_with_masks::<S>(x, seed, bitcast_masks);
     }
 
     result
@@ -24,21 +49,44 @@ pub fn fbm_1d<S: Simd>(
 
 #[inline(always)]
 pub fn fbm_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    fbm_2d_with_masks::<S>(
+        x,
+        y,
+        lac,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `fbm_2d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn fbm_2d_with_masks<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     lac: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
-    let mut result = simplex_2d::<S>(x, y, seed);
+    let mut result = simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks);
     let mut amp = S::Vf64::set1(1.0);
 
     for _ in 1..octaves {
         x = x * lac;
         y = y * lac;
         amp = amp * gain;
-        result = (simplex_2d::<S>(x, y, seed) * amp) + result;
+        result = (simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks) * amp) + result;
     }
 
     result
@@ -46,6 +94,21 @@ pub fn fbm_2d<S: Simd>(
 
 #[inline(always)]
 pub fn fbm_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    fbm_3d_with_z_prime::<S>(x, y, z, lac, gain, octaves, seed, Z_PRIME_64)
+}
+
+/// Like `fbm_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn fbm_3d_with_z_prime<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     mut z: S::Vf64,
@@ -53,15 +116,16 @@ pub fn fbm_3d<S: Simd>(
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    z_prime: i64,
 ) -> S::Vf64 {
-    let mut result = simplex_3d::<S>(x, y, z, seed);
+    let mut result = simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime);
     let mut amp = S::Vf64::set1(1.0);
     for _ in 1..octaves {
         x = x * lac;
         y = y * lac;
         z = z * lac;
         amp = amp * gain;
-        result = (simplex_3d::<S>(x, y, z, seed) * amp) + result;
+        result = (simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime) * amp) + result;
     }
     result
 }
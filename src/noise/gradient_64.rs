@@ -2,16 +2,30 @@ use crate::noise::hash3d_64::hash3d;
 
 use simdeez::prelude::*;
 
+/// A comparison mask as a mask to blend with. Before `AlgorithmVersion::V3` the mask was
+/// converted to the number -1.0, which SSE2, blending bit by bit, mixes both sides with.
+#[inline(always)]
+fn blend_mask<S: Simd>(mask: S::Vi64, bitcast_masks: bool) -> S::Vf64 {
+    if bitcast_masks {
+        mask.bitcast_f64()
+    } else {
+        mask.cast_f64()
+    }
+}
+
 /// Generates a random integer gradient in ±7 inclusive
 ///
 /// This differs from Gustavson's well-known implementation in that gradients can be zero, and the
 /// maximum gradient is 7 rather than 8.
 #[inline(always)]
-pub fn grad1<S: Simd>(seed: i64, hash: S::Vi64) -> S::Vf64 {
+pub fn grad1<S: Simd>(seed: i64, hash: S::Vi64, bitcast_masks: bool) -> S::Vf64 {
     let h = (S::Vi64::set1(seed) ^ hash) & S::Vi64::set1(15);
     let v = (h & S::Vi64::set1(7)).cast_f64();
 
-    let h_and_8 = ((h & S::Vi64::set1(8)).cmp_eq(S::Vi64::zeroes())).cast_f64();
+    let h_and_8 = blend_mask::<S>(
+        (h & S::Vi64::set1(8)).cmp_eq(S::Vi64::zeroes()),
+        bitcast_masks,
+    );
     h_and_8.blendv(S::Vf64::zeroes() - v, v)
 }
 
@@ -20,14 +34,20 @@ pub fn grad1<S: Simd>(seed: i64, hash: S::Vi64) -> S::Vf64 {
 /// This differs from Gustavson's gradients by having a constant magnitude, providing results that
 /// are more consistent between directions.
 #[inline(always)]
-pub fn grad2<S: Simd>(seed: i64, hash: S::Vi64) -> [S::Vf64; 2] {
+pub fn grad2<S: Simd>(seed: i64, hash: S::Vi64, bitcast_masks: bool) -> [S::Vf64; 2] {
     let h = (hash ^ S::Vi64::set1(seed)) & S::Vi64::set1(7);
-    let mask = (S::Vi64::set1(4).cmp_gt(h)).cast_f64();
+    let mask = blend_mask::<S>(S::Vi64::set1(4).cmp_gt(h), bitcast_masks);
     let x_magnitude = mask.blendv(S::Vf64::set1(2.0), S::Vf64::set1(1.0));
     let y_magnitude = mask.blendv(S::Vf64::set1(1.0), S::Vf64::set1(2.0));
 
-    let h_and_1 = ((h & S::Vi64::set1(1)).cmp_eq(S::Vi64::zeroes())).cast_f64();
-    let h_and_2 = ((h & S::Vi64::set1(2)).cmp_eq(S::Vi64::zeroes())).cast_f64();
+    let h_and_1 = blend_mask::<S>(
+        (h & S::Vi64::set1(1)).cmp_eq(S::Vi64::zeroes()),
+        bitcast_masks,
+    );
+    let h_and_2 = blend_mask::<S>(
+        (h & S::Vi64::set1(2)).cmp_eq(S::Vi64::zeroes()),
+        bitcast_masks,
+    );
 
     let gx = mask
         .blendv(h_and_2, h_and_1)
@@ -4,8 +4,9 @@ pub mod cell2_return_type;
 pub mod cell_32;
 pub mod cell_64;
 pub mod cell_distance_function;
+pub mod cell_lattice;
 pub mod cell_return_type;
-mod cellular_32;
+pub(crate) mod cellular_32;
 mod cellular_64;
 pub mod fbm_32;
 pub mod fbm_64;
@@ -20,3 +21,5 @@ pub mod simplex_32;
 pub mod simplex_64;
 pub mod turbulence_32;
 pub mod turbulence_64;
+pub mod white_32;
+pub mod white_64;
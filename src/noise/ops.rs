@@ -23,3 +23,135 @@ pub unsafe fn gather_64<S: Simd>(arr: &[i64], indices: S::Vi64) -> S::Vi64 {
     }
     dst
 }
+
+/// `1 / sqrt(a)`. The instruction sets approximate this differently, so with the
+/// `deterministic` feature it is computed exactly.
+#[inline(always)]
+pub fn rsqrt_32<S: Simd>(a: S::Vf32) -> S::Vf32 {
+    #[cfg(feature = "deterministic")]
+    return S::Vf32::set1(1.0) / a.sqrt();
+    #[cfg(not(feature = "deterministic"))]
+    return a.rsqrt();
+}
+
+#[inline(always)]
+pub fn rsqrt_64<S: Simd>(a: S::Vf64) -> S::Vf64 {
+    #[cfg(feature = "deterministic")]
+    return S::Vf64::set1(1.0) / a.sqrt();
+    #[cfg(not(feature = "deterministic"))]
+    return a.rsqrt();
+}
+
+/// `c - a * b`. Only AVX2 fuses this into a single rounding, so with the `deterministic`
+/// feature it is rounded twice everywhere.
+#[inline(always)]
+pub fn neg_mul_add_32<S: Simd>(a: S::Vf32, b: S::Vf32, c: S::Vf32) -> S::Vf32 {
+    #[cfg(feature = "deterministic")]
+    return c - a * b;
+    #[cfg(not(feature = "deterministic"))]
+    return S::Vf32::neg_mul_add(a, b, c);
+}
+
+#[inline(always)]
+pub fn neg_mul_add_64<S: Simd>(a: S::Vf64, b: S::Vf64, c: S::Vf64) -> S::Vf64 {
+    #[cfg(feature = "deterministic")]
+    return c - a * b;
+    #[cfg(not(feature = "deterministic"))]
+    return S::Vf64::neg_mul_add(a, b, c);
+}
+
+/// `a` rounded to the nearest integer, ties to even, like the SIMD instruction sets convert.
+/// The scalar fallback rounds ties away from zero, so with the `deterministic` feature the
+/// rounding is done with exact float adds first.
+#[inline(always)]
+pub fn round_i32<S: Simd>(a: S::Vf32) -> S::Vi32 {
+    #[cfg(feature = "deterministic")]
+    {
+        // Adding 2^23 pushes the fraction out of the mantissa, larger floats are integers
+        let magic = (a & S::Vf32::set1(-0.0)) | S::Vf32::set1(8388608.0);
+        let rounded = (a + magic) - magic;
+        a.abs()
+            .cmp_lt(S::Vf32::set1(8388608.0))
+            .blendv(a, rounded)
+            .cast_i32()
+    }
+    #[cfg(not(feature = "deterministic"))]
+    a.cast_i32()
+}
+
+/// The largest whole part of an exponent `pow_32` and `pow_64` multiply out.
+const MAX_WHOLE_EXPONENT: f64 = 64.0;
+
+/// `a` to the power of `exponent`. Whole exponents and ones halfway between two whole
+/// numbers are multiplied out a vector at a time, with a square root for the half. Other
+/// exponents have no vector form here and are raised a lane at a time with `powf`.
+#[inline(always)]
+pub fn pow_32<S: Simd>(a: S::Vf32, exponent: f32) -> S::Vf32 {
+    let magnitude = (exponent as f64).abs();
+    let (whole, half) = (magnitude.trunc(), magnitude.fract());
+    if whole > MAX_WHOLE_EXPONENT || (half != 0.0 && half != 0.5) {
+        return pow_lanes_32::<S>(a, S::Vf32::set1(exponent));
+    }
+    let mut result = if half == 0.5 {
+        a.sqrt()
+    } else {
+        S::Vf32::set1(1.0)
+    };
+    let (mut square, mut n) = (a, whole as u32);
+    while n > 0 {
+        if n & 1 == 1 {
+            result *= square;
+        }
+        square = square * square;
+        n >>= 1;
+    }
+    if exponent < 0.0 {
+        S::Vf32::set1(1.0) / result
+    } else {
+        result
+    }
+}
+
+#[inline(always)]
+pub fn pow_64<S: Simd>(a: S::Vf64, exponent: f64) -> S::Vf64 {
+    let magnitude = exponent.abs();
+    let (whole, half) = (magnitude.trunc(), magnitude.fract());
+    if whole > MAX_WHOLE_EXPONENT || (half != 0.0 && half != 0.5) {
+        return pow_lanes_64::<S>(a, S::Vf64::set1(exponent));
+    }
+    let mut result = if half == 0.5 {
+        a.sqrt()
+    } else {
+        S::Vf64::set1(1.0)
+    };
+    let (mut square, mut n) = (a, whole as u32);
+    while n > 0 {
+        if n & 1 == 1 {
+            result *= square;
+        }
+        square = square * square;
+        n >>= 1;
+    }
+    if exponent < 0.0 {
+        S::Vf64::set1(1.0) / result
+    } else {
+        result
+    }
+}
+
+/// `a` to the power of `exponent` lane by lane, with scalar `powf`.
+#[inline(always)]
+pub fn pow_lanes_32<S: Simd>(mut a: S::Vf32, exponent: S::Vf32) -> S::Vf32 {
+    for i in 0..S::Vf32::WIDTH {
+        a[i] = a[i].powf(exponent[i]);
+    }
+    a
+}
+
+#[inline(always)]
+pub fn pow_lanes_64<S: Simd>(mut a: S::Vf64, exponent: S::Vf64) -> S::Vf64 {
+    for i in 0..S::Vf64::WIDTH {
+        a[i] = a[i].powf(exponent[i]);
+    }
+    a
+}
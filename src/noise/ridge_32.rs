@@ -1,4 +1,6 @@
-use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::cellular_32::Z_PRIME_32;
+use crate::noise::ops::neg_mul_add_32;
+use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d_with_z_prime, simplex_4d};
 
 use simdeez::prelude::*;
 
@@ -39,7 +41,7 @@ pub fn ridge_2d<S: Simd>(
         y = y * lac;
         amp = amp * gain;
         result = result
-            + S::Vf32::neg_mul_add(simplex_2d::<S>(x, y, seed).abs(), amp, S::Vf32::set1(1.0));
+            + neg_mul_add_32::<S>(simplex_2d::<S>(x, y, seed).abs(), amp, S::Vf32::set1(1.0));
     }
 
     result
@@ -47,6 +49,21 @@ pub fn ridge_2d<S: Simd>(
 
 #[inline(always)]
 pub fn ridge_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    ridge_3d_with_z_prime::<S>(x, y, z, lac, gain, octaves, seed, Z_PRIME_32)
+}
+
+/// Like `ridge_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn ridge_3d_with_z_prime<S: Simd>(
     mut x: S::Vf32,
     mut y: S::Vf32,
     mut z: S::Vf32,
@@ -54,8 +71,10 @@ pub fn ridge_3d<S: Simd>(
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    z_prime: i32,
 ) -> S::Vf32 {
-    let mut result = S::Vf32::set1(1.0) - simplex_3d::<S>(x, y, z, seed).abs();
+    let mut result =
+        S::Vf32::set1(1.0) - simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime).abs();
     let mut amp = S::Vf32::set1(1.0);
 
     for _ in 1..octaves {
@@ -64,8 +83,8 @@ pub fn ridge_3d<S: Simd>(
         z = z * lac;
         amp = amp * gain;
         result = result
-            + S::Vf32::neg_mul_add(
-                simplex_3d::<S>(x, y, z, seed).abs(),
+            + neg_mul_add_32::<S>(
+                simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime).abs(),
                 amp,
                 S::Vf32::set1(1.0),
             );
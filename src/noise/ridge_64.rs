@@ -1,22 +1,49 @@
-use crate::noise::simplex_64::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::cellular_32::Z_PRIME_64;
+use crate::noise::ops::neg_mul_add_64;
+use crate::noise::simplex_64::{
+    simplex_1d_with_masks, simplex_2d_with_masks, simplex_3d_with_z_prime, simplex_4d,
+};
+use crate::AlgorithmVersion;
 
 use simdeez::prelude::*;
 
 #[inline(always)]
 pub fn ridge_1d<S: Simd>(
+    x: S::Vf64,
+    lacunarity: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_1d_with_masks::<S>(
+        x,
+        lacunarity,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `ridge_1d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn ridge_1d_with_masks<S: Simd>(
     mut x: S::Vf64,
     lacunarity: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
     let mut amp = S::Vf64::set1(1.0);
-    let mut result = S::Vf64::set1(1.0) - simplex_1d::<S>(x, seed).abs();
+    let mut result = S::Vf64::set1(1.0) - simplex_1d_with_masks::<S>(x, seed, bitcast_masks).abs();
 
     for _ in 1..octaves {
         x = x * lacunarity;
         amp = amp * gain;
-        result = result + S::Vf64::set1(1.0) - simplex_1d::<S>(x, seed).abs();
+        result =
+            result + S::Vf64::set1(1.0) - simplex_1d_with_masks::<S>(x, seed, bitcast_masks).abs();
     }
 
     result
@@ -24,14 +51,38 @@ pub fn ridge_1d<S: Simd>(
 
 #[inline(always)]
 pub fn ridge_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_2d_with_masks::<S>(
+        x,
+        y,
+        lac,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `ridge_2d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn ridge_2d_with_masks<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     lac: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
-    let mut result = S::Vf64::set1(1.0) - simplex_2d::<S>(x, y, seed).abs();
+    let mut result =
+        S::Vf64::set1(1.0) - simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks).abs();
     let mut amp = S::Vf64::set1(1.0);
 
     for _ in 1..octaves {
@@ -39,7 +90,11 @@ pub fn ridge_2d<S: Simd>(
         y = y * lac;
         amp = amp * gain;
         result = result
-            + S::Vf64::neg_mul_add(simplex_2d::<S>(x, y, seed).abs(), amp, S::Vf64::set1(1.0));
+            + neg_mul_add_64::<S>(
+                simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks).abs(),
+                amp,
+                S::Vf64::set1(1.0),
+            );
     }
 
     result
@@ -47,6 +102,21 @@ pub fn ridge_2d<S: Simd>(
 
 #[inline(always)]
 pub fn ridge_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_3d_with_z_prime::<S>(x, y, z, lac, gain, octaves, seed, Z_PRIME_64)
+}
+
+/// Like `ridge_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn ridge_3d_with_z_prime<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     mut z: S::Vf64,
@@ -54,8 +124,10 @@ pub fn ridge_3d<S: Simd>(
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    z_prime: i64,
 ) -> S::Vf64 {
-    let mut result = S::Vf64::set1(1.0) - simplex_3d::<S>(x, y, z, seed).abs();
+    let mut result =
+        S::Vf64::set1(1.0) - simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime).abs();
     let mut amp = S::Vf64::set1(1.0);
 
     for _ in 1..octaves {
@@ -64,8 +136,8 @@ pub fn ridge_3d<S: Simd>(
         z = z * lac;
         amp = amp * gain;
         result = result
-            + S::Vf64::neg_mul_add(
-                simplex_3d::<S>(x, y, z, seed).abs(),
+            + neg_mul_add_64::<S>(
+                simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime).abs(),
                 amp,
                 S::Vf64::set1(1.0),
             );
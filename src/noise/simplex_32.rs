@@ -5,7 +5,7 @@
 
 use crate::noise::cellular_32::{X_PRIME_32, Y_PRIME_32, Z_PRIME_32};
 use crate::noise::gradient_32::{grad1, grad2, grad3d, grad3d_dot, grad4};
-use crate::noise::ops::gather_32;
+use crate::noise::ops::{gather_32, neg_mul_add_32};
 
 use simdeez::prelude::*;
 
@@ -204,9 +204,9 @@ pub fn simplex_2d_deriv<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> (S::Vf32,
 
     // Weights associated with the gradients at each corner
     // These FMA operations are equivalent to: let t = 0.5 - x*x - y*y
-    let mut t0 = S::Vf32::neg_mul_add(y0, y0, S::Vf32::neg_mul_add(x0, x0, S::Vf32::set1(0.5)));
-    let mut t1 = S::Vf32::neg_mul_add(y1, y1, S::Vf32::neg_mul_add(x1, x1, S::Vf32::set1(0.5)));
-    let mut t2 = S::Vf32::neg_mul_add(y2, y2, S::Vf32::neg_mul_add(x2, x2, S::Vf32::set1(0.5)));
+    let mut t0 = neg_mul_add_32::<S>(y0, y0, neg_mul_add_32::<S>(x0, x0, S::Vf32::set1(0.5)));
+    let mut t1 = neg_mul_add_32::<S>(y1, y1, neg_mul_add_32::<S>(x1, x1, S::Vf32::set1(0.5)));
+    let mut t2 = neg_mul_add_32::<S>(y2, y2, neg_mul_add_32::<S>(x2, x2, S::Vf32::set1(0.5)));
 
     // Zero out negative weights
     t0 &= t0.cmp_gte(S::Vf32::zeroes());
@@ -259,7 +259,20 @@ pub fn simplex_2d_deriv<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> (S::Vf32,
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub fn simplex_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
-    simplex_3d_deriv::<S>(x, y, z, seed).0
+    simplex_3d_with_z_prime::<S>(x, y, z, seed, Z_PRIME_32)
+}
+
+/// Like `simplex_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_3d_with_z_prime<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+    z_prime: i32,
+) -> S::Vf32 {
+    simplex_3d_deriv_with_z_prime::<S>(x, y, z, seed, z_prime).0
 }
 
 /// Like `simplex_3d`, but also computes the derivative
@@ -269,6 +282,19 @@ pub fn simplex_3d_deriv<S: Simd>(
     y: S::Vf32,
     z: S::Vf32,
     seed: i32,
+) -> (S::Vf32, [S::Vf32; 3]) {
+    simplex_3d_deriv_with_z_prime::<S>(x, y, z, seed, Z_PRIME_32)
+}
+
+/// Like `simplex_3d_deriv`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_3d_deriv_with_z_prime<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+    z_prime: i32,
 ) -> (S::Vf32, [S::Vf32; 3]) {
     // Find skewed simplex grid coordinates associated with the input coordinates
     let f = S::Vf32::set1(F3_32) * ((x + y) + z);
@@ -279,7 +305,7 @@ pub fn simplex_3d_deriv<S: Simd>(
     // Integer grid coordinates
     let i = x0.cast_i32() * S::Vi32::set1(X_PRIME_32);
     let j = y0.cast_i32() * S::Vi32::set1(Y_PRIME_32);
-    let k = z0.cast_i32() * S::Vi32::set1(Z_PRIME_32);
+    let k = z0.cast_i32() * S::Vi32::set1(z_prime);
 
     // Compute distance from first simplex vertex to input coordinates
     let g = S::Vf32::set1(G3_32) * ((x0 + y0) + z0);
@@ -347,20 +373,20 @@ pub fn simplex_3d_deriv<S: Simd>(
 
     let v1x = i + (i1.bitcast_i32() & S::Vi32::set1(X_PRIME_32));
     let v1y = j + (j1.bitcast_i32() & S::Vi32::set1(Y_PRIME_32));
-    let v1z = k + (k1.bitcast_i32() & S::Vi32::set1(Z_PRIME_32));
+    let v1z = k + (k1.bitcast_i32() & S::Vi32::set1(z_prime));
     let g1 = grad3d_dot::<S>(seed, v1x, v1y, v1z, x1, y1, z1);
     let v1 = t41 * g1;
 
     let v2x = i + (i2.bitcast_i32() & S::Vi32::set1(X_PRIME_32));
     let v2y = j + (j2.bitcast_i32() & S::Vi32::set1(Y_PRIME_32));
-    let v2z = k + (k2.bitcast_i32() & S::Vi32::set1(Z_PRIME_32));
+    let v2z = k + (k2.bitcast_i32() & S::Vi32::set1(z_prime));
     let g2 = grad3d_dot::<S>(seed, v2x, v2y, v2z, x2, y2, z2);
     let v2 = t42 * g2;
 
     //SIMDf v3 = SIMDf_MASK(n3, SIMDf_MUL(SIMDf_MUL(t3, t3), FUNC(GradCoord)(seed, SIMDi_ADD(i, SIMDi_NUM(xPrime)), SIMDi_ADD(j, SIMDi_NUM(yPrime)), SIMDi_ADD(k, SIMDi_NUM(zPrime)), x3, y3, z3)));
     let v3x = i + S::Vi32::set1(X_PRIME_32);
     let v3y = j + S::Vi32::set1(Y_PRIME_32);
-    let v3z = k + S::Vi32::set1(Z_PRIME_32);
+    let v3z = k + S::Vi32::set1(z_prime);
     //define SIMDf_MASK(m,a) SIMDf_AND(SIMDf_CAST_TO_FLOAT(m),a)
     let g3 = grad3d_dot::<S>(seed, v3x, v3y, v3z, x3, y3, z3);
     let v3 = t43 * g3;
@@ -1,10 +1,11 @@
 use crate::noise::gradient_64::{grad1, grad2, grad3d, grad4};
+use crate::AlgorithmVersion;
 
 use simdeez::prelude::*;
 
 use crate::noise::cellular_32::{X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
 use crate::noise::gradient_64::grad3d_dot;
-use crate::noise::ops::gather_64;
+use crate::noise::ops::{gather_64, neg_mul_add_64};
 use crate::noise::simplex_32::{
     F2_64, F3_64, F4_64, G22_64, G24_64, G2_64, G33_64, G34_64, G3_64, G44_64, G4_64,
 };
@@ -48,6 +49,17 @@ fn assert_in_perm_range<S: Simd>(values: S::Vi64) {
 /// Like `simplex_1d`, but also computes the derivative
 #[inline(always)]
 pub fn simplex_1d_deriv<S: Simd>(x: S::Vf64, seed: i64) -> (S::Vf64, S::Vf64) {
+    simplex_1d_deriv_with_masks::<S>(x, seed, AlgorithmVersion::LATEST.bitcast_masks())
+}
+
+/// Like `simplex_1d_deriv`, but converts the gradient masks as `bitcast_masks` says, so
+/// older `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_1d_deriv_with_masks<S: Simd>(
+    x: S::Vf64,
+    seed: i64,
+    bitcast_masks: bool,
+) -> (S::Vf64, S::Vf64) {
     // Gradients are selected deterministically based on the whole part of `x`
     let ips = x.fast_floor();
     let mut i0 = ips.cast_i64();
@@ -71,7 +83,7 @@ pub fn simplex_1d_deriv<S: Simd>(x: S::Vf64, seed: i64) -> (S::Vf64, S::Vf64) {
     let t0 = S::Vf64::set1(1.0) - x20; // t_0
     let t20 = t0 * t0; // t^2_0
     let t40 = t20 * t20; // t^4_0
-    let gx0 = grad1::<S>(seed, gi0);
+    let gx0 = grad1::<S>(seed, gi0, bitcast_masks);
     let n0 = t40 * gx0 * x0;
     // n0 = (1 - x0^2)^4 * x0 * grad
 
@@ -80,7 +92,7 @@ pub fn simplex_1d_deriv<S: Simd>(x: S::Vf64, seed: i64) -> (S::Vf64, S::Vf64) {
     let t1 = S::Vf64::set1(1.0) - x21; // t_1
     let t21 = t1 * t1; // t^2_1
     let t41 = t21 * t21; // t^4_1
-    let gx1 = grad1::<S>(seed, gi1);
+    let gx1 = grad1::<S>(seed, gi1, bitcast_masks);
     let n1 = t41 * gx1 * x1;
 
     // n0 + n1 =
@@ -112,6 +124,17 @@ pub fn simplex_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vf64 {
     simplex_1d_deriv::<S>(x, seed).0
 }
 
+/// Like `simplex_1d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_1d_with_masks<S: Simd>(
+    x: S::Vf64,
+    seed: i64,
+    bitcast_masks: bool,
+) -> S::Vf64 {
+    simplex_1d_deriv_with_masks::<S>(x, seed, bitcast_masks).0
+}
+
 /// Samples 2-dimensional simplex noise
 ///
 /// Produces a value -1 ≤ n ≤ 1.
@@ -120,9 +143,33 @@ pub fn simplex_2d<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> S::Vf64 {
     simplex_2d_deriv::<S>(x, y, seed).0
 }
 
+/// Like `simplex_2d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_2d_with_masks<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    seed: i64,
+    bitcast_masks: bool,
+) -> S::Vf64 {
+    simplex_2d_deriv_with_masks::<S>(x, y, seed, bitcast_masks).0
+}
+
 /// Like `simplex_2d`, but also computes the derivative
 #[inline(always)]
 pub fn simplex_2d_deriv<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> (S::Vf64, [S::Vf64; 2]) {
+    simplex_2d_deriv_with_masks::<S>(x, y, seed, AlgorithmVersion::LATEST.bitcast_masks())
+}
+
+/// Like `simplex_2d_deriv`, but converts the gradient masks as `bitcast_masks` says, so
+/// older `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_2d_deriv_with_masks<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    seed: i64,
+    bitcast_masks: bool,
+) -> (S::Vf64, [S::Vf64; 2]) {
     // Skew to distort simplexes with side length sqrt(2)/sqrt(3) until they make up
     // squares
     let s = S::Vf64::set1(F2_64) * (x + y);
@@ -169,9 +216,9 @@ pub fn simplex_2d_deriv<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> (S::Vf64,
 
     // Weights associated with the gradients at each corner
     // These FMA operations are equivalent to: let t = 0.5 - x*x - y*y
-    let mut t0 = S::Vf64::neg_mul_add(y0, y0, S::Vf64::neg_mul_add(x0, x0, S::Vf64::set1(0.5)));
-    let mut t1 = S::Vf64::neg_mul_add(y1, y1, S::Vf64::neg_mul_add(x1, x1, S::Vf64::set1(0.5)));
-    let mut t2 = S::Vf64::neg_mul_add(y2, y2, S::Vf64::neg_mul_add(x2, x2, S::Vf64::set1(0.5)));
+    let mut t0 = neg_mul_add_64::<S>(y0, y0, neg_mul_add_64::<S>(x0, x0, S::Vf64::set1(0.5)));
+    let mut t1 = neg_mul_add_64::<S>(y1, y1, neg_mul_add_64::<S>(x1, x1, S::Vf64::set1(0.5)));
+    let mut t2 = neg_mul_add_64::<S>(y2, y2, neg_mul_add_64::<S>(x2, x2, S::Vf64::set1(0.5)));
 
     // Zero out negative weights
     t0 &= t0.cmp_gte(S::Vf64::zeroes());
@@ -185,13 +232,13 @@ pub fn simplex_2d_deriv<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> (S::Vf64,
     let t22 = t2 * t2;
     let t42 = t22 * t22;
 
-    let [gx0, gy0] = grad2::<S>(seed, gi0);
+    let [gx0, gy0] = grad2::<S>(seed, gi0, bitcast_masks);
     let g0 = gx0 * x0 + gy0 * y0;
     let n0 = t40 * g0;
-    let [gx1, gy1] = grad2::<S>(seed, gi1);
+    let [gx1, gy1] = grad2::<S>(seed, gi1, bitcast_masks);
     let g1 = gx1 * x1 + gy1 * y1;
     let n1 = t41 * g1;
-    let [gx2, gy2] = grad2::<S>(seed, gi2);
+    let [gx2, gy2] = grad2::<S>(seed, gi2, bitcast_masks);
     let g2 = gx2 * x2 + gy2 * y2;
     let n2 = t42 * g2;
 
@@ -224,7 +271,20 @@ pub fn simplex_2d_deriv<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> (S::Vf64,
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub fn simplex_3d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, seed: i64) -> S::Vf64 {
-    simplex_3d_deriv::<S>(x, y, z, seed).0
+    simplex_3d_with_z_prime::<S>(x, y, z, seed, Z_PRIME_64)
+}
+
+/// Like `simplex_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_3d_with_z_prime<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    seed: i64,
+    z_prime: i64,
+) -> S::Vf64 {
+    simplex_3d_deriv_with_z_prime::<S>(x, y, z, seed, z_prime).0
 }
 
 /// Like `simplex_3d`, but also computes the derivative
@@ -234,6 +294,19 @@ pub fn simplex_3d_deriv<S: Simd>(
     y: S::Vf64,
     z: S::Vf64,
     seed: i64,
+) -> (S::Vf64, [S::Vf64; 3]) {
+    simplex_3d_deriv_with_z_prime::<S>(x, y, z, seed, Z_PRIME_64)
+}
+
+/// Like `simplex_3d_deriv`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn simplex_3d_deriv_with_z_prime<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    seed: i64,
+    z_prime: i64,
 ) -> (S::Vf64, [S::Vf64; 3]) {
     // Find skewed simplex grid coordinates associated with the input coordinates
     let f = S::Vf64::set1(F3_64) * ((x + y) + z);
@@ -244,7 +317,7 @@ pub fn simplex_3d_deriv<S: Simd>(
     // Integer grid coordinates
     let i = x0.cast_i64() * S::Vi64::set1(X_PRIME_64);
     let j = y0.cast_i64() * S::Vi64::set1(Y_PRIME_64);
-    let k = z0.cast_i64() * S::Vi64::set1(Z_PRIME_64);
+    let k = z0.cast_i64() * S::Vi64::set1(z_prime);
 
     // Compute distance from first simplex vertex to input coordinates
     let g = S::Vf64::set1(G3_64) * (x0 + y0 + z0);
@@ -312,20 +385,20 @@ pub fn simplex_3d_deriv<S: Simd>(
 
     let v1x = i + (i1.bitcast_i64() & S::Vi64::set1(X_PRIME_64));
     let v1y = j + (j1.bitcast_i64() & S::Vi64::set1(Y_PRIME_64));
-    let v1z = k + (k1.bitcast_i64() & S::Vi64::set1(Z_PRIME_64));
+    let v1z = k + (k1.bitcast_i64() & S::Vi64::set1(z_prime));
     let g1 = grad3d_dot::<S>(seed, v1x, v1y, v1z, x1, y1, z1);
     let v1 = t41 * g1;
 
     let v2x = i + (i2.bitcast_i64() & S::Vi64::set1(X_PRIME_64));
     let v2y = j + (j2.bitcast_i64() & S::Vi64::set1(Y_PRIME_64));
-    let v2z = k + (k2.bitcast_i64() & S::Vi64::set1(Z_PRIME_64));
+    let v2z = k + (k2.bitcast_i64() & S::Vi64::set1(z_prime));
     let g2 = grad3d_dot::<S>(seed, v2x, v2y, v2z, x2, y2, z2);
     let v2 = t42 * g2;
 
     //SIMDf v3 = SIMDf_MASK(n3, SIMDf_MUL(SIMDf_MUL(t3, t3), FUNC(GradCoord)(seed, SIMDi_ADD(i, SIMDi_NUM(xPrime)), SIMDi_ADD(j, SIMDi_NUM(yPrime)), SIMDi_ADD(k, SIMDi_NUM(zPrime)), x3, y3, z3)));
     let v3x = i + S::Vi64::set1(X_PRIME_64);
     let v3y = j + S::Vi64::set1(Y_PRIME_64);
-    let v3z = k + S::Vi64::set1(Z_PRIME_64);
+    let v3z = k + S::Vi64::set1(z_prime);
     //define SIMDf_MASK(m,a) SIMDf_AND(SIMDf_CAST_TO_FLOAT(m),a)
     let g3 = grad3d_dot::<S>(seed, v3x, v3y, v3z, x3, y3, z3);
     let v3 = t43 * g3;
@@ -630,7 +703,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "not implemented")]
     fn test_noise_simplex64_3d_range() {
         let mut min = f64::INFINITY;
         let mut max = -f64::INFINITY;
@@ -654,7 +726,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "not implemented")]
     fn test_noise_simplex64_3d_deriv_sanity() {
         let mut avg_err = 0.0;
         const POINTS: i64 = 10;
@@ -705,6 +776,27 @@ mod tests {
         assert!(avg_err < 1e-3);
     }
 
+    #[test]
+    fn test_noise_simplex64_3d_matches_32() {
+        use crate::noise::simplex_32;
+        use simdeez::scalar::F32x1;
+
+        for seed in 0..10 {
+            for i in 0..1000 {
+                let [x, y, z] = [i as f32 * 0.037, i as f32 * -0.011 + 3.0, i as f32 * 0.005];
+                let n32 = simplex_32::simplex_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0;
+                let n64 = simplex_3d::<Scalar>(
+                    F64x1(x as f64),
+                    F64x1(y as f64),
+                    F64x1(z as f64),
+                    seed as i64,
+                )
+                .0;
+                assert!((n32 as f64 - n64).abs() < 1e-4, "{} {}", n32, n64);
+            }
+        }
+    }
+
     #[test]
     fn test_noise_simplex64_4d_range() {
         let mut min = f64::INFINITY;
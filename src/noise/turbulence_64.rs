@@ -1,22 +1,47 @@
-use crate::noise::simplex_64::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::cellular_32::Z_PRIME_64;
+use crate::noise::simplex_64::{
+    simplex_1d_with_masks, simplex_2d_with_masks, simplex_3d_with_z_prime, simplex_4d,
+};
+use crate::AlgorithmVersion;
 
 use simdeez::prelude::*;
 
 #[inline(always)]
 pub fn turbulence_1d<S: Simd>(
+    x: S::Vf64,
+    lacunarity: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    turbulence_1d_with_masks::<S>(
+        x,
+        lacunarity,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `turbulence_1d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn turbulence_1d_with_masks<S: Simd>(
     mut x: S::Vf64,
     lacunarity: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
     let mut amp = S::Vf64::set1(1.0);
-    let mut result = simplex_1d::<S>(x, seed).abs();
+    let mut result = simplex_1d_with_masks::<S>(x, seed, bitcast_masks).abs();
 
     for _ in 1..octaves {
         x = x * lacunarity;
         amp = amp * gain;
-        result = result + simplex_1d::<S>(x, seed).abs();
+        result = result + simplex_1d_with_masks::<S>(x, seed, bitcast_masks).abs();
     }
 
     result
@@ -24,14 +49,37 @@ pub fn turbulence_1d<S: Simd>(
 
 #[inline(always)]
 pub fn turbulence_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    turbulence_2d_with_masks::<S>(
+        x,
+        y,
+        lac,
+        gain,
+        octaves,
+        seed,
+        AlgorithmVersion::LATEST.bitcast_masks(),
+    )
+}
+
+/// Like `turbulence_2d`, but converts the gradient masks as `bitcast_masks` says, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn turbulence_2d_with_masks<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     lac: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    bitcast_masks: bool,
 ) -> S::Vf64 {
-    let mut result = simplex_2d::<S>(x, y, seed).abs();
+    let mut result = simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks).abs();
 
     let mut amp = S::Vf64::set1(1.0);
 
@@ -39,7 +87,7 @@ pub fn turbulence_2d<S: Simd>(
         x = x * lac;
         y = y * lac;
         amp = amp * gain;
-        result = result + (simplex_2d::<S>(x, y, seed) * amp).abs();
+        result = result + (simplex_2d_with_masks::<S>(x, y, seed, bitcast_masks) * amp).abs();
     }
 
     result
@@ -47,6 +95,21 @@ pub fn turbulence_2d<S: Simd>(
 
 #[inline(always)]
 pub fn turbulence_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    turbulence_3d_with_z_prime::<S>(x, y, z, lac, gain, octaves, seed, Z_PRIME_64)
+}
+
+/// Like `turbulence_3d`, but hashes the z cell coordinates with `z_prime`, so older
+/// `AlgorithmVersion`s can be reproduced.
+#[inline(always)]
+pub(crate) fn turbulence_3d_with_z_prime<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     mut z: S::Vf64,
@@ -54,8 +117,9 @@ pub fn turbulence_3d<S: Simd>(
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    z_prime: i64,
 ) -> S::Vf64 {
-    let mut result = simplex_3d::<S>(x, y, z, seed).abs();
+    let mut result = simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime).abs();
     let mut amp = S::Vf64::set1(1.0);
 
     for _ in 1..octaves {
@@ -63,7 +127,7 @@ pub fn turbulence_3d<S: Simd>(
         y = y * lac;
         z = z * lac;
         amp = amp * gain;
-        result = result + (simplex_3d::<S>(x, y, z, seed) * amp).abs();
+        result = result + (simplex_3d_with_z_prime::<S>(x, y, z, seed, z_prime) * amp).abs();
     }
 
     result
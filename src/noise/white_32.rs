@@ -0,0 +1,50 @@
+//! White noise: an uncorrelated random value for every integer cell.
+//!
+//! These use the same hashing as the cellular noise functions, so the value of a cell
+//! only depends on the seed and its integer coordinates.
+
+use super::cellular_32::{
+    hash_1d, hash_2d, hash_3d, hash_4d, HASH_2_FLOAT_32, W_PRIME_32, X_PRIME_32, Y_PRIME_32,
+    Z_PRIME_32,
+};
+
+use simdeez::prelude::*;
+
+#[inline(always)]
+fn hash_to_float<S: Simd>(hash: S::Vi32) -> S::Vf32 {
+    S::Vf32::set1(HASH_2_FLOAT_32) * hash.cast_f32()
+}
+
+/// Get a single value of 1d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vf32 {
+    let xc = x.floor().cast_i32() * S::Vi32::set1(X_PRIME_32);
+    hash_to_float::<S>(hash_1d::<S>(seed, xc))
+}
+
+/// Get a single value of 2d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    let xc = x.floor().cast_i32() * S::Vi32::set1(X_PRIME_32);
+    let yc = y.floor().cast_i32() * S::Vi32::set1(Y_PRIME_32);
+    hash_to_float::<S>(hash_2d::<S>(seed, xc, yc))
+}
+
+/// Get a single value of 3d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
+    let xc = x.floor().cast_i32() * S::Vi32::set1(X_PRIME_32);
+    let yc = y.floor().cast_i32() * S::Vi32::set1(Y_PRIME_32);
+    let zc = z.floor().cast_i32() * S::Vi32::set1(Z_PRIME_32);
+    hash_to_float::<S>(hash_3d::<S>(seed, xc, yc, zc))
+}
+
+/// Get a single value of 4d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_4d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32, seed: i32) -> S::Vf32 {
+    let xc = x.floor().cast_i32() * S::Vi32::set1(X_PRIME_32);
+    let yc = y.floor().cast_i32() * S::Vi32::set1(Y_PRIME_32);
+    let zc = z.floor().cast_i32() * S::Vi32::set1(Z_PRIME_32);
+    let wc = w.floor().cast_i32() * S::Vi32::set1(W_PRIME_32);
+    hash_to_float::<S>(hash_4d::<S>(seed, xc, yc, zc, wc))
+}
@@ -0,0 +1,43 @@
+//! White noise: an uncorrelated random value for every integer cell.
+//!
+//! These use the same hashing as the cellular noise functions, so the value of a cell
+//! only depends on the seed and its integer coordinates.
+
+use super::cellular_32::{W_PRIME_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
+use super::cellular_64::{hash_1d, hash_2d, hash_3d, hash_4d, hash_to_float};
+
+use simdeez::prelude::*;
+
+/// Get a single value of 1d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vf64 {
+    let xc = x.floor().cast_i64() * S::Vi64::set1(X_PRIME_64);
+    hash_to_float::<S>(hash_1d::<S>(seed, xc))
+}
+
+/// Get a single value of 2d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_2d<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> S::Vf64 {
+    let xc = x.floor().cast_i64() * S::Vi64::set1(X_PRIME_64);
+    let yc = y.floor().cast_i64() * S::Vi64::set1(Y_PRIME_64);
+    hash_to_float::<S>(hash_2d::<S>(seed, xc, yc))
+}
+
+/// Get a single value of 3d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_3d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, seed: i64) -> S::Vf64 {
+    let xc = x.floor().cast_i64() * S::Vi64::set1(X_PRIME_64);
+    let yc = y.floor().cast_i64() * S::Vi64::set1(Y_PRIME_64);
+    let zc = z.floor().cast_i64() * S::Vi64::set1(Z_PRIME_64);
+    hash_to_float::<S>(hash_3d::<S>(seed, xc, yc, zc))
+}
+
+/// Get a single value of 4d white noise, in the range [-1, 1].
+#[inline(always)]
+pub fn white_4d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, w: S::Vf64, seed: i64) -> S::Vf64 {
+    let xc = x.floor().cast_i64() * S::Vi64::set1(X_PRIME_64);
+    let yc = y.floor().cast_i64() * S::Vi64::set1(Y_PRIME_64);
+    let zc = z.floor().cast_i64() * S::Vi64::set1(Z_PRIME_64);
+    let wc = w.floor().cast_i64() * S::Vi64::set1(W_PRIME_64);
+    hash_to_float::<S>(hash_4d::<S>(seed, xc, yc, zc, wc))
+}
@@ -1,6 +1,6 @@
 use super::{
     Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, NoiseDimensions,
-    RidgeSettings, Settings, TurbulenceSettings,
+    RidgeSettings, Settings, TurbulenceSettings, WhiteNoiseSettings,
 };
 
 pub struct NoiseBuilder {}
@@ -465,4 +465,95 @@ impl NoiseBuilder {
         dim.w = w_offset;
         GradientSettings::default(dim)
     }
+
+    pub fn white_1d(width: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_1d_offset(x_offset: f32, width: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_2d(width: usize, height: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_3d(width: usize, height: usize, depth: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_4d(width: usize, height: usize, depth: usize, time: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        WhiteNoiseSettings::default(dim)
+    }
 }
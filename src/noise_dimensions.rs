@@ -1,4 +1,13 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+
+use crate::AlgorithmVersion;
+
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "NoiseDimensionsData"))]
 pub struct NoiseDimensions {
     pub dim: usize,
     pub x: f32,
@@ -12,9 +21,13 @@ pub struct NoiseDimensions {
     pub min: f32,
     pub max: f32,
     pub seed: i32,
+    /// The algorithm revision to generate with, see `AlgorithmVersion`.
+    pub version: AlgorithmVersion,
 }
 
 impl NoiseDimensions {
+    /// Dimensions of a single sample at the origin, generated with
+    /// `AlgorithmVersion::LATEST`, like deserialised dimensions without a version.
     pub fn default(d: usize) -> NoiseDimensions {
         if d < 1 || d > 4 {
             panic!("dimension invalid");
@@ -32,6 +45,61 @@ impl NoiseDimensions {
             min: 0.0,
             max: 1.0,
             seed: 1,
+            version: AlgorithmVersion::LATEST,
+        }
+    }
+
+    /// The sizes of the axes that are used, slowest changing first, so `[height, width]`
+    /// for 2d noise. This is the order NumPy and most volume formats expect.
+    pub fn shape(&self) -> Vec<usize> {
+        let sizes = [self.time, self.depth, self.height, self.width];
+        sizes[4 - self.dim.min(4)..].to_vec()
+    }
+}
+
+/// Deserialized fields, checked before they become `NoiseDimensions`.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "NoiseDimensions")]
+struct NoiseDimensionsData {
+    dim: usize,
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    width: usize,
+    height: usize,
+    depth: usize,
+    time: usize,
+    min: f32,
+    max: f32,
+    seed: i32,
+    #[serde(default)]
+    version: AlgorithmVersion,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<NoiseDimensionsData> for NoiseDimensions {
+    type Error = &'static str;
+
+    fn try_from(data: NoiseDimensionsData) -> Result<NoiseDimensions, &'static str> {
+        if data.dim < 1 || data.dim > 4 {
+            return Err("dimension invalid");
         }
+        Ok(NoiseDimensions {
+            dim: data.dim,
+            x: data.x,
+            y: data.y,
+            z: data.z,
+            w: data.w,
+            width: data.width,
+            height: data.height,
+            depth: data.depth,
+            time: data.time,
+            min: data.min,
+            max: data.max,
+            seed: data.seed,
+            version: data.version,
+        })
     }
 }
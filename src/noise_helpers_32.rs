@@ -1,26 +1,84 @@
-use crate::dimensional_being::DimensionalBeing;
+use crate::dimensional_being::{DimensionalBeing, Frequency};
+use crate::far_blocks::{get_far_noise, is_far_noise};
 use crate::{
-    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, NoiseType, RidgeSettings,
-    Settings, TurbulenceSettings,
+    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, NoiseNode, NoiseType,
+    RidgeSettings, TurbulenceSettings, WhiteNoiseSettings,
 };
 
 use simdeez::prelude::*;
 
 use std::f32;
 
-pub trait Sample32<S: Simd>: DimensionalBeing + Settings {
+pub trait Sample32<S: Simd>: DimensionalBeing {
     fn sample_1d(&self, x: S::Vf32) -> S::Vf32;
     fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32;
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32;
     fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32;
 }
 
+// Plain #[inline]: forcing every kernel of every dimension into the caller gives debug
+// builds huge stack frames.
+impl<S: Simd> Sample32<S> for NoiseType {
+    #[inline]
+    fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+        match self {
+            NoiseType::Fbm(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::Ridge(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::Turbulence(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::Gradient(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::Cellular(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::Cellular2(s) => Sample32::<S>::sample_1d(s, x),
+            NoiseType::White(s) => Sample32::<S>::sample_1d(s, x),
+        }
+    }
+
+    #[inline]
+    fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        match self {
+            NoiseType::Fbm(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::Ridge(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::Turbulence(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::Gradient(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::Cellular(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::Cellular2(s) => Sample32::<S>::sample_2d(s, x, y),
+            NoiseType::White(s) => Sample32::<S>::sample_2d(s, x, y),
+        }
+    }
+
+    #[inline]
+    fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        match self {
+            NoiseType::Fbm(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::Ridge(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::Turbulence(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::Gradient(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::Cellular(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::Cellular2(s) => Sample32::<S>::sample_3d(s, x, y, z),
+            NoiseType::White(s) => Sample32::<S>::sample_3d(s, x, y, z),
+        }
+    }
+
+    #[inline]
+    fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+        match self {
+            NoiseType::Fbm(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Ridge(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Turbulence(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Gradient(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Cellular(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Cellular2(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::White(s) => Sample32::<S>::sample_4d(s, x, y, z, w),
+        }
+    }
+}
+
 #[inline(always)]
-unsafe fn get_1d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
-    settings: Settings,
+pub(crate) unsafe fn get_1d_noise_helper_f32<S: Simd, Settings: Sample32<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f32>, f32, f32) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf32::set1(settings.get_freq_x());
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf32::set1(frequency[0]);
     let start_x = dim.x;
     let width = dim.width;
     let mut min_s = S::Vf32::set1(f32::MAX);
@@ -77,12 +135,13 @@ unsafe fn get_1d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_2d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
-    settings: Settings,
+pub(crate) unsafe fn get_2d_noise_helper_f32<S: Simd, Settings: Sample32<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f32>, f32, f32) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf32::set1(settings.get_freq_x());
-    let freq_y = S::Vf32::set1(settings.get_freq_y());
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf32::set1(frequency[0]);
+    let freq_y = S::Vf32::set1(frequency[1]);
     let start_x = dim.x;
     let width = dim.width;
     let start_y = dim.y;
@@ -144,13 +203,14 @@ unsafe fn get_2d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_3d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
-    settings: Settings,
+pub(crate) unsafe fn get_3d_noise_helper_f32<S: Simd, Settings: Sample32<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f32>, f32, f32) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf32::set1(settings.get_freq_x());
-    let freq_y = S::Vf32::set1(settings.get_freq_y());
-    let freq_z = S::Vf32::set1(settings.get_freq_z());
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf32::set1(frequency[0]);
+    let freq_y = S::Vf32::set1(frequency[1]);
+    let freq_z = S::Vf32::set1(frequency[2]);
     let start_x = dim.x;
     let width = dim.width;
     let start_y = dim.y;
@@ -219,14 +279,15 @@ unsafe fn get_3d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_4d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
-    settings: Settings,
+pub(crate) unsafe fn get_4d_noise_helper_f32<S: Simd, Settings: Sample32<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f32>, f32, f32) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf32::set1(settings.get_freq_x());
-    let freq_y = S::Vf32::set1(settings.get_freq_y());
-    let freq_z = S::Vf32::set1(settings.get_freq_z());
-    let freq_w = S::Vf32::set1(settings.get_freq_w());
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf32::set1(frequency[0]);
+    let freq_y = S::Vf32::set1(frequency[1]);
+    let freq_z = S::Vf32::set1(frequency[2]);
+    let freq_w = S::Vf32::set1(frequency[3]);
     let start_x = dim.x;
     let width = dim.width;
     let start_y = dim.y;
@@ -300,21 +361,87 @@ unsafe fn get_4d_noise_helper_f32<S: Simd, Settings: Sample32<S>>(
     (result, min, max)
 }
 
+/// Calls `f` with the samples of a block a vector at a time, in the order the helpers above
+/// store them, along with how many lanes of the vector are part of the block. The samples
+/// are the same as the helpers', for generating without storing the block.
+#[inline(always)]
+pub(crate) unsafe fn for_each_vector_f32<S, Settings, F>(settings: &Settings, mut f: F)
+where
+    S: Simd,
+    Settings: Sample32<S> + Frequency,
+    F: FnMut(S::Vf32, usize),
+{
+    let dim = settings.get_dimensions();
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf32::set1(frequency[0]);
+    let freq_y = S::Vf32::set1(frequency[1]);
+    let freq_z = S::Vf32::set1(frequency[2]);
+    let freq_w = S::Vf32::set1(frequency[3]);
+    let width = dim.width;
+    let height = if dim.dim > 1 { dim.height } else { 1 };
+    let depth = if dim.dim > 2 { dim.depth } else { 1 };
+    let time = if dim.dim > 3 { dim.time } else { 1 };
+
+    let vector_width = S::Vf32::WIDTH;
+    let remainder = width % vector_width;
+    let mut x_arr = Vec::<f32>::with_capacity(vector_width);
+    let x_ptr = x_arr.as_mut_ptr();
+    for i in (0..vector_width).rev() {
+        x_ptr.add(i).write(dim.x + i as f32);
+    }
+    x_arr.set_len(vector_width);
+    let sample = |x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32| match dim.dim {
+        1 => settings.sample_1d(x * freq_x),
+        2 => settings.sample_2d(x * freq_x, y * freq_y),
+        3 => settings.sample_3d(x * freq_x, y * freq_y, z * freq_z),
+        _ => settings.sample_4d(x * freq_x, y * freq_y, z * freq_z, w * freq_w),
+    };
+    let mut w = S::Vf32::set1(dim.w);
+    for _ in 0..time {
+        let mut z = S::Vf32::set1(dim.z);
+        for _ in 0..depth {
+            let mut y = S::Vf32::set1(dim.y);
+            for _ in 0..height {
+                let mut x = S::Vf32::load_from_ptr_unaligned(&x_arr[0]);
+                for _ in 0..width / vector_width {
+                    f(sample(x, y, z, w), vector_width);
+                    x += S::Vf32::set1(vector_width as f32);
+                }
+                if remainder != 0 {
+                    f(sample(x, y, z, w), remainder);
+                }
+                y += S::Vf32::set1(1.0);
+            }
+            z += S::Vf32::set1(1.0);
+        }
+        w += S::Vf32::set1(1.0);
+    }
+}
+
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_1d_noise_helper_f32::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_1d_noise_helper_f32::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_1d_noise_helper_f32::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_1d_noise_helper_f32::<S, GradientSettings>(*s),
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
-        }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
-        }
+    if is_far_noise(noise_type) {
+        return get_far_noise::<S, NoiseType>(noise_type);
     }
+    // The f32 kernels get a frame of their own, debug builds overflow the stack with the
+    // f64 kernels called from under them
+    S::invoke(
+        #[inline(always)]
+        || match noise_type {
+            NoiseType::Fbm(s) => get_1d_noise_helper_f32::<S, FbmSettings>(s),
+            NoiseType::Ridge(s) => get_1d_noise_helper_f32::<S, RidgeSettings>(s),
+            NoiseType::Turbulence(s) => get_1d_noise_helper_f32::<S, TurbulenceSettings>(s),
+            NoiseType::Gradient(s) => get_1d_noise_helper_f32::<S, GradientSettings>(s),
+            NoiseType::Cellular(_) => {
+                panic!("not implemented");
+            }
+            NoiseType::Cellular2(_) => {
+                panic!("not implemented");
+            }
+            NoiseType::White(s) => get_1d_noise_helper_f32::<S, WhiteNoiseSettings>(s),
+        },
+    )
 }
 
 /// Gets a width X height sized block of 2d noise, unscaled.
@@ -325,14 +452,21 @@ pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_2d_noise_helper_f32::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_2d_noise_helper_f32::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_2d_noise_helper_f32::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_2d_noise_helper_f32::<S, GradientSettings>(*s),
-        NoiseType::Cellular(s) => get_2d_noise_helper_f32::<S, CellularSettings>(*s),
-        NoiseType::Cellular2(s) => get_2d_noise_helper_f32::<S, Cellular2Settings>(*s),
+    if is_far_noise(noise_type) {
+        return get_far_noise::<S, NoiseType>(noise_type);
     }
+    S::invoke(
+        #[inline(always)]
+        || match noise_type {
+            NoiseType::Fbm(s) => get_2d_noise_helper_f32::<S, FbmSettings>(s),
+            NoiseType::Ridge(s) => get_2d_noise_helper_f32::<S, RidgeSettings>(s),
+            NoiseType::Turbulence(s) => get_2d_noise_helper_f32::<S, TurbulenceSettings>(s),
+            NoiseType::Gradient(s) => get_2d_noise_helper_f32::<S, GradientSettings>(s),
+            NoiseType::Cellular(s) => get_2d_noise_helper_f32::<S, CellularSettings>(s),
+            NoiseType::Cellular2(s) => get_2d_noise_helper_f32::<S, Cellular2Settings>(s),
+            NoiseType::White(s) => get_2d_noise_helper_f32::<S, WhiteNoiseSettings>(s),
+        },
+    )
 }
 
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
@@ -343,29 +477,87 @@ pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_3d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_3d_noise_helper_f32::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_3d_noise_helper_f32::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_3d_noise_helper_f32::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_3d_noise_helper_f32::<S, GradientSettings>(*s),
-        NoiseType::Cellular(s) => get_3d_noise_helper_f32::<S, CellularSettings>(*s),
-        NoiseType::Cellular2(s) => get_3d_noise_helper_f32::<S, Cellular2Settings>(*s),
+    if is_far_noise(noise_type) {
+        return get_far_noise::<S, NoiseType>(noise_type);
     }
+    S::invoke(
+        #[inline(always)]
+        || match noise_type {
+            NoiseType::Fbm(s) => get_3d_noise_helper_f32::<S, FbmSettings>(s),
+            NoiseType::Ridge(s) => get_3d_noise_helper_f32::<S, RidgeSettings>(s),
+            NoiseType::Turbulence(s) => get_3d_noise_helper_f32::<S, TurbulenceSettings>(s),
+            NoiseType::Gradient(s) => get_3d_noise_helper_f32::<S, GradientSettings>(s),
+            NoiseType::Cellular(s) => get_3d_noise_helper_f32::<S, CellularSettings>(s),
+            NoiseType::Cellular2(s) => get_3d_noise_helper_f32::<S, Cellular2Settings>(s),
+            NoiseType::White(s) => get_3d_noise_helper_f32::<S, WhiteNoiseSettings>(s),
+        },
+    )
 }
 
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_4d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_4d_noise_helper_f32::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_4d_noise_helper_f32::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_4d_noise_helper_f32::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_4d_noise_helper_f32::<S, GradientSettings>(*s),
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
-        }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
-        }
+    if is_far_noise(noise_type) {
+        return get_far_noise::<S, NoiseType>(noise_type);
+    }
+    S::invoke(
+        #[inline(always)]
+        || match noise_type {
+            NoiseType::Fbm(s) => get_4d_noise_helper_f32::<S, FbmSettings>(s),
+            NoiseType::Ridge(s) => get_4d_noise_helper_f32::<S, RidgeSettings>(s),
+            NoiseType::Turbulence(s) => get_4d_noise_helper_f32::<S, TurbulenceSettings>(s),
+            NoiseType::Gradient(s) => get_4d_noise_helper_f32::<S, GradientSettings>(s),
+            NoiseType::Cellular(_) => {
+                panic!("not implemented");
+            }
+            NoiseType::Cellular2(_) => {
+                panic!("not implemented");
+            }
+            NoiseType::White(s) => get_4d_noise_helper_f32::<S, WhiteNoiseSettings>(s),
+        },
+    )
+}
+
+#[inline(always)]
+pub unsafe fn get_1d_node_noise<S: Simd>(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+    if node.is_far() {
+        return get_far_noise::<S, NoiseNode>(node);
+    }
+    S::invoke(
+        #[inline(always)]
+        || get_1d_noise_helper_f32::<S, NoiseNode>(node),
+    )
+}
+
+#[inline(always)]
+pub unsafe fn get_2d_node_noise<S: Simd>(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+    if node.is_far() {
+        return get_far_noise::<S, NoiseNode>(node);
+    }
+    S::invoke(
+        #[inline(always)]
+        || get_2d_noise_helper_f32::<S, NoiseNode>(node),
+    )
+}
+
+#[inline(always)]
+pub unsafe fn get_3d_node_noise<S: Simd>(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+    if node.is_far() {
+        return get_far_noise::<S, NoiseNode>(node);
+    }
+    S::invoke(
+        #[inline(always)]
+        || get_3d_noise_helper_f32::<S, NoiseNode>(node),
+    )
+}
+
+#[inline(always)]
+pub unsafe fn get_4d_node_noise<S: Simd>(node: &NoiseNode) -> (Vec<f32>, f32, f32) {
+    if node.is_far() {
+        return get_far_noise::<S, NoiseNode>(node);
     }
+    S::invoke(
+        #[inline(always)]
+        || get_4d_noise_helper_f32::<S, NoiseNode>(node),
+    )
 }
@@ -1,28 +1,85 @@
 use simdeez::prelude::*;
 
 use super::NoiseType;
-use crate::dimensional_being::DimensionalBeing;
+use crate::dimensional_being::{DimensionalBeing, Frequency};
 
 use crate::{
-    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, RidgeSettings, Settings,
-    TurbulenceSettings,
+    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, RidgeSettings,
+    TurbulenceSettings, WhiteNoiseSettings,
 };
 
 use std::f64;
 
-pub trait Sample64<S: Simd>: DimensionalBeing + Settings {
+pub trait Sample64<S: Simd>: DimensionalBeing {
     fn sample_1d(&self, x: S::Vf64) -> S::Vf64;
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64;
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64;
     fn sample_4d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64, w: S::Vf64) -> S::Vf64;
 }
 
+// Plain #[inline]: forcing every kernel of every dimension into the caller gives debug
+// builds huge stack frames.
+impl<S: Simd> Sample64<S> for NoiseType {
+    #[inline]
+    fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
+        match self {
+            NoiseType::Fbm(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::Ridge(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::Turbulence(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::Gradient(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::Cellular(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::Cellular2(s) => Sample64::<S>::sample_1d(s, x),
+            NoiseType::White(s) => Sample64::<S>::sample_1d(s, x),
+        }
+    }
+
+    #[inline]
+    fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
+        match self {
+            NoiseType::Fbm(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::Ridge(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::Turbulence(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::Gradient(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::Cellular(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::Cellular2(s) => Sample64::<S>::sample_2d(s, x, y),
+            NoiseType::White(s) => Sample64::<S>::sample_2d(s, x, y),
+        }
+    }
+
+    #[inline]
+    fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
+        match self {
+            NoiseType::Fbm(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::Ridge(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::Turbulence(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::Gradient(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::Cellular(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::Cellular2(s) => Sample64::<S>::sample_3d(s, x, y, z),
+            NoiseType::White(s) => Sample64::<S>::sample_3d(s, x, y, z),
+        }
+    }
+
+    #[inline]
+    fn sample_4d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64, w: S::Vf64) -> S::Vf64 {
+        match self {
+            NoiseType::Fbm(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Ridge(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Turbulence(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Gradient(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Cellular(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::Cellular2(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+            NoiseType::White(s) => Sample64::<S>::sample_4d(s, x, y, z, w),
+        }
+    }
+}
+
 #[inline(always)]
-unsafe fn get_1d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
-    settings: Settings,
+unsafe fn get_1d_noise_helper_f64<S: Simd, Settings: Sample64<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f64>, f64, f64) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf64::set1(settings.get_freq_x() as f64);
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf64::set1(frequency[0] as f64);
     let start_x = dim.x as f64;
     let width = dim.width;
     let mut min_s = S::Vf64::set1(f64::MAX);
@@ -79,12 +136,13 @@ unsafe fn get_1d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_2d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
-    settings: Settings,
+unsafe fn get_2d_noise_helper_f64<S: Simd, Settings: Sample64<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f64>, f64, f64) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf64::set1(settings.get_freq_x() as f64);
-    let freq_y = S::Vf64::set1(settings.get_freq_y() as f64);
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf64::set1(frequency[0] as f64);
+    let freq_y = S::Vf64::set1(frequency[1] as f64);
     let start_x = dim.x as f64;
     let width = dim.width;
     let start_y = dim.y as f64;
@@ -146,13 +204,14 @@ unsafe fn get_2d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_3d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
-    settings: Settings,
+unsafe fn get_3d_noise_helper_f64<S: Simd, Settings: Sample64<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f64>, f64, f64) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf64::set1(settings.get_freq_x() as f64);
-    let freq_y = S::Vf64::set1(settings.get_freq_y() as f64);
-    let freq_z = S::Vf64::set1(settings.get_freq_z() as f64);
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf64::set1(frequency[0] as f64);
+    let freq_y = S::Vf64::set1(frequency[1] as f64);
+    let freq_z = S::Vf64::set1(frequency[2] as f64);
     let start_x = dim.x as f64;
     let width = dim.width;
     let start_y = dim.y as f64;
@@ -221,14 +280,15 @@ unsafe fn get_3d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
 }
 
 #[inline(always)]
-unsafe fn get_4d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
-    settings: Settings,
+unsafe fn get_4d_noise_helper_f64<S: Simd, Settings: Sample64<S> + Frequency>(
+    settings: &Settings,
 ) -> (Vec<f64>, f64, f64) {
     let dim = settings.get_dimensions();
-    let freq_x = S::Vf64::set1(settings.get_freq_x() as f64);
-    let freq_y = S::Vf64::set1(settings.get_freq_y() as f64);
-    let freq_z = S::Vf64::set1(settings.get_freq_z() as f64);
-    let freq_w = S::Vf64::set1(settings.get_freq_w() as f64);
+    let frequency = settings.get_frequency();
+    let freq_x = S::Vf64::set1(frequency[0] as f64);
+    let freq_y = S::Vf64::set1(frequency[1] as f64);
+    let freq_z = S::Vf64::set1(frequency[2] as f64);
+    let freq_w = S::Vf64::set1(frequency[3] as f64);
     let start_x = dim.x as f64;
     let width = dim.width;
     let start_y = dim.y as f64;
@@ -306,16 +366,17 @@ unsafe fn get_4d_noise_helper_f64<S: Simd, Settings: Sample64<S>>(
 #[allow(dead_code)]
 pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
     match noise_type {
-        NoiseType::Fbm(s) => get_1d_noise_helper_f64::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_1d_noise_helper_f64::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_1d_noise_helper_f64::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_1d_noise_helper_f64::<S, GradientSettings>(*s),
+        NoiseType::Fbm(s) => get_1d_noise_helper_f64::<S, FbmSettings>(s),
+        NoiseType::Ridge(s) => get_1d_noise_helper_f64::<S, RidgeSettings>(s),
+        NoiseType::Turbulence(s) => get_1d_noise_helper_f64::<S, TurbulenceSettings>(s),
+        NoiseType::Gradient(s) => get_1d_noise_helper_f64::<S, GradientSettings>(s),
         NoiseType::Cellular(_) => {
             panic!("not implemented");
         }
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
+        NoiseType::White(s) => get_1d_noise_helper_f64::<S, WhiteNoiseSettings>(s),
     }
 }
 
@@ -328,12 +389,13 @@ pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f
 #[allow(dead_code)]
 pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
     match noise_type {
-        NoiseType::Fbm(s) => get_2d_noise_helper_f64::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_2d_noise_helper_f64::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_2d_noise_helper_f64::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_2d_noise_helper_f64::<S, GradientSettings>(*s),
-        NoiseType::Cellular(s) => get_2d_noise_helper_f64::<S, CellularSettings>(*s),
-        NoiseType::Cellular2(s) => get_2d_noise_helper_f64::<S, Cellular2Settings>(*s),
+        NoiseType::Fbm(s) => get_2d_noise_helper_f64::<S, FbmSettings>(s),
+        NoiseType::Ridge(s) => get_2d_noise_helper_f64::<S, RidgeSettings>(s),
+        NoiseType::Turbulence(s) => get_2d_noise_helper_f64::<S, TurbulenceSettings>(s),
+        NoiseType::Gradient(s) => get_2d_noise_helper_f64::<S, GradientSettings>(s),
+        NoiseType::Cellular(s) => get_2d_noise_helper_f64::<S, CellularSettings>(s),
+        NoiseType::Cellular2(s) => get_2d_noise_helper_f64::<S, Cellular2Settings>(s),
+        NoiseType::White(s) => get_2d_noise_helper_f64::<S, WhiteNoiseSettings>(s),
     }
 }
 
@@ -346,12 +408,13 @@ pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f
 #[allow(dead_code)]
 pub unsafe fn get_3d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
     match noise_type {
-        NoiseType::Fbm(s) => get_3d_noise_helper_f64::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_3d_noise_helper_f64::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_3d_noise_helper_f64::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_3d_noise_helper_f64::<S, GradientSettings>(*s),
-        NoiseType::Cellular(s) => get_3d_noise_helper_f64::<S, CellularSettings>(*s),
-        NoiseType::Cellular2(s) => get_3d_noise_helper_f64::<S, Cellular2Settings>(*s),
+        NoiseType::Fbm(s) => get_3d_noise_helper_f64::<S, FbmSettings>(s),
+        NoiseType::Ridge(s) => get_3d_noise_helper_f64::<S, RidgeSettings>(s),
+        NoiseType::Turbulence(s) => get_3d_noise_helper_f64::<S, TurbulenceSettings>(s),
+        NoiseType::Gradient(s) => get_3d_noise_helper_f64::<S, GradientSettings>(s),
+        NoiseType::Cellular(s) => get_3d_noise_helper_f64::<S, CellularSettings>(s),
+        NoiseType::Cellular2(s) => get_3d_noise_helper_f64::<S, Cellular2Settings>(s),
+        NoiseType::White(s) => get_3d_noise_helper_f64::<S, WhiteNoiseSettings>(s),
     }
 }
 
@@ -359,15 +422,16 @@ pub unsafe fn get_3d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f
 #[allow(dead_code)]
 pub unsafe fn get_4d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
     match noise_type {
-        NoiseType::Fbm(s) => get_4d_noise_helper_f64::<S, FbmSettings>(*s),
-        NoiseType::Ridge(s) => get_4d_noise_helper_f64::<S, RidgeSettings>(*s),
-        NoiseType::Turbulence(s) => get_4d_noise_helper_f64::<S, TurbulenceSettings>(*s),
-        NoiseType::Gradient(s) => get_4d_noise_helper_f64::<S, GradientSettings>(*s),
+        NoiseType::Fbm(s) => get_4d_noise_helper_f64::<S, FbmSettings>(s),
+        NoiseType::Ridge(s) => get_4d_noise_helper_f64::<S, RidgeSettings>(s),
+        NoiseType::Turbulence(s) => get_4d_noise_helper_f64::<S, TurbulenceSettings>(s),
+        NoiseType::Gradient(s) => get_4d_noise_helper_f64::<S, GradientSettings>(s),
         NoiseType::Cellular(_) => {
             panic!("not implemented");
         }
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
+        NoiseType::White(s) => get_4d_noise_helper_f64::<S, WhiteNoiseSettings>(s),
     }
 }
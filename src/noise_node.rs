@@ -0,0 +1,748 @@
+//! Composable noise graphs.
+//!
+//! A `NoiseNode` is an expression tree over `NoiseType` sources. The whole tree is evaluated
+//! for every SIMD vector of sample positions, so combining noises does not need a full
+//! buffer per source.
+
+use std::ops::{Add, Mul};
+
+use simdeez::prelude::*;
+
+use crate::dimensional_being::{DimensionalBeing, Frequency};
+use crate::far_blocks::{amplitude, is_far_block, FarPositions};
+use crate::modifier::Modifier;
+use crate::noise::ops::{pow_32, pow_64, pow_lanes_32, pow_lanes_64};
+use crate::noise_helpers_32::Sample32;
+use crate::noise_helpers_64::Sample64;
+use crate::quantize::{get_i16_node_noise, get_u16_node_noise, get_u8_node_noise};
+use crate::shared::scale_buffer;
+use crate::{get_1d_node_noise, get_2d_node_noise, get_3d_node_noise, get_4d_node_noise};
+use crate::{NoiseDimensions, NoiseType};
+
+/// A node in a noise graph.
+///
+/// Build graphs from wrapped settings with the combinator methods, `+` and `*`:
+///
+/// ```rust
+/// use simdnoise::*;
+///
+/// let hills = NoiseNode::from(NoiseBuilder::fbm_2d(64, 64).with_freq(0.02).wrap());
+/// let mountains = NoiseNode::from(NoiseBuilder::ridge_2d(64, 64).with_freq(0.05).wrap());
+/// let control = NoiseNode::from(NoiseBuilder::gradient_2d(64, 64).with_freq(0.01).wrap());
+/// let terrain = hills.select(mountains, control, 0.0, 0.1).clamp(-1.0, 1.0);
+/// let (noise, min, max) = terrain.generate();
+/// ```
+///
+/// All `Noise` nodes in the graph must have the same size and offset, which the generated
+/// block takes; every source keeps its own frequencies and seed.
+#[derive(Clone)]
+pub enum NoiseNode {
+    /// A noise source, sampled with its own frequencies and seed
+    Noise(NoiseType),
+    /// The same value everywhere
+    Constant(f32),
+    Add(Box<NoiseNode>, Box<NoiseNode>),
+    Multiply(Box<NoiseNode>, Box<NoiseNode>),
+    Min(Box<NoiseNode>, Box<NoiseNode>),
+    Max(Box<NoiseNode>, Box<NoiseNode>),
+    /// The first node raised to the power of the second. A `Constant` exponent that is
+    /// whole or a half is computed a vector at a time; other exponents fall back to `powf`
+    /// lane by lane, which is several times slower.
+    Power(Box<NoiseNode>, Box<NoiseNode>),
+    /// Interpolates from `a` at a control value of -1 to `b` at a control value of 1
+    Blend {
+        a: Box<NoiseNode>,
+        b: Box<NoiseNode>,
+        control: Box<NoiseNode>,
+    },
+    /// `low` where the control is below the threshold and `high` above it, with a smooth
+    /// transition `falloff` wide on either side of the threshold
+    Select {
+        low: Box<NoiseNode>,
+        high: Box<NoiseNode>,
+        control: Box<NoiseNode>,
+        threshold: f32,
+        falloff: f32,
+    },
+    Clamp {
+        source: Box<NoiseNode>,
+        min: f32,
+        max: f32,
+    },
+    Abs(Box<NoiseNode>),
+    /// The negated value
+    Invert(Box<NoiseNode>),
+    /// Samples `source` at `matrix * p + translation`, where `p` is the sample position
+    /// before the sources apply their frequencies
+    Transform {
+        source: Box<NoiseNode>,
+        matrix: [[f32; 4]; 4],
+        translation: [f32; 4],
+    },
+    /// Samples `source` with each axis of the sample position moved by the value of the
+    /// matching offset node, x first
+    Displace {
+        source: Box<NoiseNode>,
+        offsets: Vec<NoiseNode>,
+    },
+    /// The value of `source` passed through a modifier
+    Modify(Box<NoiseNode>, Modifier),
+}
+
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+impl From<NoiseType> for NoiseNode {
+    fn from(noise_type: NoiseType) -> NoiseNode {
+        NoiseNode::Noise(noise_type)
+    }
+}
+
+impl Add for NoiseNode {
+    type Output = NoiseNode;
+
+    fn add(self, other: NoiseNode) -> NoiseNode {
+        NoiseNode::Add(Box::new(self), Box::new(other))
+    }
+}
+
+impl Mul for NoiseNode {
+    type Output = NoiseNode;
+
+    fn mul(self, other: NoiseNode) -> NoiseNode {
+        NoiseNode::Multiply(Box::new(self), Box::new(other))
+    }
+}
+
+impl NoiseNode {
+    pub fn min(self, other: NoiseNode) -> NoiseNode {
+        NoiseNode::Min(Box::new(self), Box::new(other))
+    }
+
+    pub fn max(self, other: NoiseNode) -> NoiseNode {
+        NoiseNode::Max(Box::new(self), Box::new(other))
+    }
+
+    pub fn power(self, exponent: NoiseNode) -> NoiseNode {
+        NoiseNode::Power(Box::new(self), Box::new(exponent))
+    }
+
+    pub fn blend(self, other: NoiseNode, control: NoiseNode) -> NoiseNode {
+        NoiseNode::Blend {
+            a: Box::new(self),
+            b: Box::new(other),
+            control: Box::new(control),
+        }
+    }
+
+    pub fn select(
+        self,
+        high: NoiseNode,
+        control: NoiseNode,
+        threshold: f32,
+        falloff: f32,
+    ) -> NoiseNode {
+        NoiseNode::Select {
+            low: Box::new(self),
+            high: Box::new(high),
+            control: Box::new(control),
+            threshold,
+            falloff,
+        }
+    }
+
+    pub fn clamp(self, min: f32, max: f32) -> NoiseNode {
+        NoiseNode::Clamp {
+            source: Box::new(self),
+            min,
+            max,
+        }
+    }
+
+    pub fn abs(self) -> NoiseNode {
+        NoiseNode::Abs(Box::new(self))
+    }
+
+    pub fn invert(self) -> NoiseNode {
+        NoiseNode::Invert(Box::new(self))
+    }
+
+    pub fn transform(self, matrix: [[f32; 4]; 4], translation: [f32; 4]) -> NoiseNode {
+        NoiseNode::Transform {
+            source: Box::new(self),
+            matrix,
+            translation,
+        }
+    }
+
+    pub fn translate(self, offset: [f32; 4]) -> NoiseNode {
+        self.transform(IDENTITY, offset)
+    }
+
+    pub fn scale(self, factors: [f32; 4]) -> NoiseNode {
+        let mut matrix = IDENTITY;
+        for (i, factor) in factors.iter().enumerate() {
+            matrix[i][i] = *factor;
+        }
+        self.transform(matrix, [0.0; 4])
+    }
+
+    /// Moves the `axis` coordinate by `amount` times the `by` coordinate, axes counting
+    /// from 0 for x to 3 for w.
+    pub fn shear(self, axis: usize, by: usize, amount: f32) -> NoiseNode {
+        if axis >= 4 || by >= 4 || axis == by {
+            panic!("invalid shear axes in noise graph");
+        }
+        let mut matrix = IDENTITY;
+        matrix[axis][by] = amount;
+        self.transform(matrix, [0.0; 4])
+    }
+
+    /// Rotates the sample positions by `angle` radians around the origin.
+    pub fn rotate_2d(self, angle: f32) -> NoiseNode {
+        self.rotate_4d(0, 1, angle)
+    }
+
+    /// Rotates the sample positions by `angle` radians around `axis`.
+    pub fn rotate_3d(self, axis: [f32; 3], angle: f32) -> NoiseNode {
+        let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if length == 0.0 || length.is_nan() {
+            panic!("invalid rotation axis in noise graph");
+        }
+        let [x, y, z] = [axis[0] / length, axis[1] / length, axis[2] / length];
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        let matrix = [
+            [
+                t * x * x + cos,
+                t * x * y - sin * z,
+                t * x * z + sin * y,
+                0.0,
+            ],
+            [
+                t * x * y + sin * z,
+                t * y * y + cos,
+                t * y * z - sin * x,
+                0.0,
+            ],
+            [
+                t * x * z - sin * y,
+                t * y * z + sin * x,
+                t * z * z + cos,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        self.transform(matrix, [0.0; 4])
+    }
+
+    /// Rotates the sample positions by `angle` radians in the plane of axes `a` and `b`,
+    /// counting from 0 for x to 3 for w. Rotations in 4d are combined from these.
+    pub fn rotate_4d(self, a: usize, b: usize, angle: f32) -> NoiseNode {
+        if a >= 4 || b >= 4 || a == b {
+            panic!("invalid rotation plane in noise graph");
+        }
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = IDENTITY;
+        matrix[a][a] = cos;
+        matrix[a][b] = -sin;
+        matrix[b][a] = sin;
+        matrix[b][b] = cos;
+        self.transform(matrix, [0.0; 4])
+    }
+
+    /// Displaces the sample positions by the values of `offsets`, one node per axis starting
+    /// at x. Scale the offsets, for example by multiplying with a `Constant`, to control the
+    /// distance in samples.
+    pub fn displace(self, offsets: Vec<NoiseNode>) -> NoiseNode {
+        NoiseNode::Displace {
+            source: Box::new(self),
+            offsets,
+        }
+    }
+
+    pub fn modify(self, modifier: Modifier) -> NoiseNode {
+        NoiseNode::Modify(Box::new(self), modifier)
+    }
+
+    /// Steps between the ascending `points`, see `Modifier::Terrace`.
+    pub fn terrace(self, points: Vec<f32>, invert: bool) -> NoiseNode {
+        self.modify(Modifier::Terrace { points, invert })
+    }
+
+    /// Maps through `[input, output]` control points, see `Modifier::Curve`.
+    pub fn curve(self, points: Vec<[f32; 2]>) -> NoiseNode {
+        self.modify(Modifier::Curve(points))
+    }
+
+    pub fn exponent(self, exponent: f32) -> NoiseNode {
+        self.modify(Modifier::Exponent(exponent))
+    }
+
+    /// Generate a chunk of noise, and the min and max value generated, so you can scale it
+    /// as you wish
+    pub fn generate(&self) -> (Vec<f32>, f32, f32) {
+        self.validate();
+        match self.get_dimensions().dim {
+            1 => get_1d_node_noise(self),
+            2 => get_2d_node_noise(self),
+            3 => get_3d_node_noise(self),
+            4 => get_4d_node_noise(self),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    pub fn generate_scaled(&self, min: f32, max: f32) -> Vec<f32> {
+        let (noise, noise_min, noise_max) = self.generate();
+        scale_buffer(noise, min, max, noise_min, noise_max)
+    }
+
+    /// Generate a chunk of noise scaled from min to max and rounded to bytes.
+    ///
+    /// The values are converted while they are generated, with rounding to the nearest
+    /// integer and saturation, so no float buffer the size of the output is needed. Scaling
+    /// needs the range of the noise first, which takes a second pass over the samples.
+    pub fn generate_u8(&self, min: u8, max: u8) -> Vec<u8> {
+        self.validate();
+        get_u8_node_noise(self, min, max)
+    }
+
+    /// Generate a chunk of noise scaled from min to max and rounded to `u16`, see
+    /// `generate_u8`.
+    pub fn generate_u16(&self, min: u16, max: u16) -> Vec<u16> {
+        self.validate();
+        get_u16_node_noise(self, min, max)
+    }
+
+    /// Generate a chunk of noise scaled from min to max and rounded to `i16`, see
+    /// `generate_u8`.
+    pub fn generate_i16(&self, min: i16, max: i16) -> Vec<i16> {
+        self.validate();
+        get_i16_node_noise(self, min, max)
+    }
+
+    pub fn validate(&self) {
+        let block = match self.first_source() {
+            Some(noise_type) => noise_type.get_dimensions(),
+            None => panic!("noise graph needs at least one noise source"),
+        };
+        self.validate_node(&block);
+    }
+
+    fn validate_node(&self, block: &NoiseDimensions) {
+        let dim = block.dim;
+        match self {
+            NoiseNode::Noise(noise_type) => {
+                let source = noise_type.get_dimensions();
+                if source.dim != dim {
+                    panic!("noise graph mixes noise of different dimensions");
+                }
+                let size = |d: &NoiseDimensions| [d.width, d.height, d.depth, d.time];
+                let offset = |d: &NoiseDimensions| [d.x, d.y, d.z, d.w];
+                if size(&source)[..dim] != size(block)[..dim]
+                    || offset(&source)[..dim] != offset(block)[..dim]
+                {
+                    panic!("noise graph mixes sources of different sizes or offsets");
+                }
+                noise_type.validate();
+            }
+            NoiseNode::Clamp { source, min, max } => {
+                if min > max {
+                    panic!("invalid clamp range in noise graph");
+                }
+                source.validate_node(block);
+            }
+            NoiseNode::Displace { source, offsets } => {
+                if offsets.len() > dim {
+                    panic!("more displacement offsets than noise dimensions in noise graph");
+                }
+                source.validate_node(block);
+                offsets.iter().for_each(|o| o.validate_node(block));
+            }
+            NoiseNode::Modify(source, modifier) => {
+                modifier.validate();
+                source.validate_node(block);
+            }
+            _ => self.children().iter().for_each(|c| c.validate_node(block)),
+        }
+    }
+
+    /// Whether the graph is sampled in f64, see `far_blocks`.
+    pub(crate) fn is_far(&self) -> bool {
+        is_far_block(self)
+    }
+
+    /// An upper bound of the magnitude of the values of the graph, infinite when there is
+    /// none.
+    fn amplitude(&self) -> f64 {
+        match self {
+            NoiseNode::Noise(noise_type) => amplitude(noise_type),
+            NoiseNode::Constant(v) => (*v as f64).abs(),
+            NoiseNode::Add(a, b) => a.amplitude() + b.amplitude(),
+            NoiseNode::Multiply(a, b) => {
+                let (a, b) = (a.amplitude(), b.amplitude());
+                // Nothing scaled by zero, rather than the NaN of infinity times zero
+                if a == 0.0 || b == 0.0 {
+                    0.0
+                } else {
+                    a * b
+                }
+            }
+            NoiseNode::Min(a, b) | NoiseNode::Max(a, b) => a.amplitude().max(b.amplitude()),
+            NoiseNode::Power(..) => f64::INFINITY,
+            NoiseNode::Blend { a, b, .. } => a.amplitude().max(b.amplitude()),
+            NoiseNode::Select { low, high, .. } => low.amplitude().max(high.amplitude()),
+            NoiseNode::Clamp { min, max, .. } => (*min as f64).abs().max((*max as f64).abs()),
+            NoiseNode::Abs(source)
+            | NoiseNode::Invert(source)
+            | NoiseNode::Transform { source, .. }
+            | NoiseNode::Displace { source, .. } => source.amplitude(),
+            NoiseNode::Modify(source, modifier) => modifier.amplitude(source.amplitude()),
+        }
+    }
+
+    fn first_source(&self) -> Option<&NoiseType> {
+        match self {
+            NoiseNode::Noise(noise_type) => Some(noise_type),
+            _ => self.children().iter().find_map(|c| c.first_source()),
+        }
+    }
+
+    fn children(&self) -> Vec<&NoiseNode> {
+        match self {
+            NoiseNode::Noise(_) | NoiseNode::Constant(_) => vec![],
+            NoiseNode::Add(a, b)
+            | NoiseNode::Multiply(a, b)
+            | NoiseNode::Min(a, b)
+            | NoiseNode::Max(a, b)
+            | NoiseNode::Power(a, b) => vec![a, b],
+            NoiseNode::Blend { a, b, control } => vec![a, b, control],
+            NoiseNode::Select {
+                low, high, control, ..
+            } => vec![low, high, control],
+            NoiseNode::Clamp { source, .. }
+            | NoiseNode::Abs(source)
+            | NoiseNode::Invert(source)
+            | NoiseNode::Transform { source, .. }
+            | NoiseNode::Modify(source, _) => vec![source],
+            NoiseNode::Displace { source, offsets } => {
+                let mut children: Vec<&NoiseNode> = vec![source];
+                children.extend(offsets.iter());
+                children
+            }
+        }
+    }
+
+    /// Evaluates the tree at the sample positions `p`, of which the first `dim` are used.
+    ///
+    /// Graphs are recursive, so the nodes cannot be inlined into the backend's generated
+    /// function. Every node enters the instruction set of `S` again instead, and sources get
+    /// a call of their own, which keeps the kernels out of the frames of the nodes above.
+    fn eval_f32<S: Simd>(&self, p: [S::Vf32; 4], dim: usize) -> S::Vf32 {
+        match self {
+            NoiseNode::Noise(noise_type) => S::invoke(
+                #[inline(always)]
+                || sample_f32::<S>(noise_type, p, dim),
+            ),
+            _ => S::invoke(
+                #[inline(always)]
+                || self.combine_f32::<S>(p, dim),
+            ),
+        }
+    }
+
+    /// Evaluates a node that is not a source, see `eval_f32`.
+    #[inline(always)]
+    fn combine_f32<S: Simd>(&self, p: [S::Vf32; 4], dim: usize) -> S::Vf32 {
+        match self {
+            NoiseNode::Noise(_) => unreachable!("sources are sampled by eval_f32"),
+            NoiseNode::Constant(v) => S::Vf32::set1(*v),
+            NoiseNode::Add(a, b) => a.eval_f32::<S>(p, dim) + b.eval_f32::<S>(p, dim),
+            NoiseNode::Multiply(a, b) => a.eval_f32::<S>(p, dim) * b.eval_f32::<S>(p, dim),
+            NoiseNode::Min(a, b) => a.eval_f32::<S>(p, dim).min(b.eval_f32::<S>(p, dim)),
+            NoiseNode::Max(a, b) => a.eval_f32::<S>(p, dim).max(b.eval_f32::<S>(p, dim)),
+            NoiseNode::Power(a, b) => match **b {
+                NoiseNode::Constant(exponent) => pow_32::<S>(a.eval_f32::<S>(p, dim), exponent),
+                _ => pow_lanes_32::<S>(a.eval_f32::<S>(p, dim), b.eval_f32::<S>(p, dim)),
+            },
+            NoiseNode::Blend { a, b, control } => {
+                let a = a.eval_f32::<S>(p, dim);
+                let b = b.eval_f32::<S>(p, dim);
+                let t = (control.eval_f32::<S>(p, dim) + S::Vf32::set1(1.0)) * S::Vf32::set1(0.5);
+                let t = t.max(S::Vf32::zeroes()).min(S::Vf32::set1(1.0));
+                a + (b - a) * t
+            }
+            NoiseNode::Select {
+                low,
+                high,
+                control,
+                threshold,
+                falloff,
+            } => {
+                let low = low.eval_f32::<S>(p, dim);
+                let high = high.eval_f32::<S>(p, dim);
+                let control = control.eval_f32::<S>(p, dim);
+                if *falloff > 0.0 {
+                    let t = (control - S::Vf32::set1(threshold - falloff))
+                        * S::Vf32::set1(0.5 / falloff);
+                    let t = t.max(S::Vf32::zeroes()).min(S::Vf32::set1(1.0));
+                    let t = t * t * (S::Vf32::set1(3.0) - t - t);
+                    low + (high - low) * t
+                } else {
+                    control.cmp_lt(S::Vf32::set1(*threshold)).blendv(high, low)
+                }
+            }
+            NoiseNode::Clamp { source, min, max } => source
+                .eval_f32::<S>(p, dim)
+                .max(S::Vf32::set1(*min))
+                .min(S::Vf32::set1(*max)),
+            NoiseNode::Abs(source) => source.eval_f32::<S>(p, dim).abs(),
+            NoiseNode::Invert(source) => S::Vf32::zeroes() - source.eval_f32::<S>(p, dim),
+            NoiseNode::Transform {
+                source,
+                matrix,
+                translation,
+            } => {
+                let mut q = p;
+                for i in 0..dim {
+                    let mut v = S::Vf32::set1(translation[i]);
+                    for j in 0..dim {
+                        v += p[j] * S::Vf32::set1(matrix[i][j]);
+                    }
+                    q[i] = v;
+                }
+                source.eval_f32::<S>(q, dim)
+            }
+            NoiseNode::Displace { source, offsets } => {
+                let mut q = p;
+                for (i, offset) in offsets.iter().enumerate() {
+                    q[i] = p[i] + offset.eval_f32::<S>(p, dim);
+                }
+                source.eval_f32::<S>(q, dim)
+            }
+            NoiseNode::Modify(source, modifier) => {
+                modifier.apply_f32::<S>(source.eval_f32::<S>(p, dim))
+            }
+        }
+    }
+
+    /// Evaluates the tree at the sample positions `p`, see `eval_f32`.
+    fn eval_f64<S: Simd>(&self, p: [S::Vf64; 4], dim: usize) -> S::Vf64 {
+        match self {
+            NoiseNode::Noise(noise_type) => S::invoke(
+                #[inline(always)]
+                || sample_f64::<S>(noise_type, p, dim),
+            ),
+            _ => S::invoke(
+                #[inline(always)]
+                || self.combine_f64::<S>(p, dim),
+            ),
+        }
+    }
+
+    /// Evaluates a node that is not a source, see `eval_f32`.
+    #[inline(always)]
+    fn combine_f64<S: Simd>(&self, p: [S::Vf64; 4], dim: usize) -> S::Vf64 {
+        match self {
+            NoiseNode::Noise(_) => unreachable!("sources are sampled by eval_f64"),
+            NoiseNode::Constant(v) => S::Vf64::set1(*v as f64),
+            NoiseNode::Add(a, b) => a.eval_f64::<S>(p, dim) + b.eval_f64::<S>(p, dim),
+            NoiseNode::Multiply(a, b) => a.eval_f64::<S>(p, dim) * b.eval_f64::<S>(p, dim),
+            NoiseNode::Min(a, b) => a.eval_f64::<S>(p, dim).min(b.eval_f64::<S>(p, dim)),
+            NoiseNode::Max(a, b) => a.eval_f64::<S>(p, dim).max(b.eval_f64::<S>(p, dim)),
+            NoiseNode::Power(a, b) => match **b {
+                NoiseNode::Constant(exponent) => {
+                    pow_64::<S>(a.eval_f64::<S>(p, dim), exponent as f64)
+                }
+                _ => pow_lanes_64::<S>(a.eval_f64::<S>(p, dim), b.eval_f64::<S>(p, dim)),
+            },
+            NoiseNode::Blend { a, b, control } => {
+                let a = a.eval_f64::<S>(p, dim);
+                let b = b.eval_f64::<S>(p, dim);
+                let t = (control.eval_f64::<S>(p, dim) + S::Vf64::set1(1.0)) * S::Vf64::set1(0.5);
+                let t = t.max(S::Vf64::zeroes()).min(S::Vf64::set1(1.0));
+                a + (b - a) * t
+            }
+            NoiseNode::Select {
+                low,
+                high,
+                control,
+                threshold,
+                falloff,
+            } => {
+                let low = low.eval_f64::<S>(p, dim);
+                let high = high.eval_f64::<S>(p, dim);
+                let control = control.eval_f64::<S>(p, dim);
+                if *falloff > 0.0 {
+                    let t = (control - S::Vf64::set1((threshold - falloff) as f64))
+                        * S::Vf64::set1(0.5 / *falloff as f64);
+                    let t = t.max(S::Vf64::zeroes()).min(S::Vf64::set1(1.0));
+                    let t = t * t * (S::Vf64::set1(3.0) - t - t);
+                    low + (high - low) * t
+                } else {
+                    control
+                        .cmp_lt(S::Vf64::set1(*threshold as f64))
+                        .blendv(high, low)
+                }
+            }
+            NoiseNode::Clamp { source, min, max } => source
+                .eval_f64::<S>(p, dim)
+                .max(S::Vf64::set1(*min as f64))
+                .min(S::Vf64::set1(*max as f64)),
+            NoiseNode::Abs(source) => source.eval_f64::<S>(p, dim).abs(),
+            NoiseNode::Invert(source) => S::Vf64::zeroes() - source.eval_f64::<S>(p, dim),
+            NoiseNode::Transform {
+                source,
+                matrix,
+                translation,
+            } => {
+                let mut q = p;
+                for i in 0..dim {
+                    let mut v = S::Vf64::set1(translation[i] as f64);
+                    for j in 0..dim {
+                        v += p[j] * S::Vf64::set1(matrix[i][j] as f64);
+                    }
+                    q[i] = v;
+                }
+                source.eval_f64::<S>(q, dim)
+            }
+            NoiseNode::Displace { source, offsets } => {
+                let mut q = p;
+                for (i, offset) in offsets.iter().enumerate() {
+                    q[i] = p[i] + offset.eval_f64::<S>(p, dim);
+                }
+                source.eval_f64::<S>(q, dim)
+            }
+            NoiseNode::Modify(source, modifier) => {
+                modifier.apply_f64::<S>(source.eval_f64::<S>(p, dim))
+            }
+        }
+    }
+}
+
+impl DimensionalBeing for NoiseNode {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        match self.first_source() {
+            Some(noise_type) => noise_type.get_dimensions(),
+            None => panic!("noise graph needs at least one noise source"),
+        }
+    }
+}
+
+impl FarPositions for NoiseNode {
+    /// Follows the transforms down to the sources.
+    fn reaches_far(&self, reach: [f64; 4]) -> bool {
+        match self {
+            NoiseNode::Noise(noise_type) => noise_type.reaches_far(reach),
+            NoiseNode::Transform {
+                source,
+                matrix,
+                translation,
+            } => {
+                let mut moved = [0.0; 4];
+                for (i, m) in moved.iter_mut().enumerate() {
+                    *m = translation[i].abs() as f64;
+                    for (j, r) in reach.iter().enumerate() {
+                        *m += matrix[i][j].abs() as f64 * r;
+                    }
+                }
+                source.reaches_far(moved)
+            }
+            // The offsets move the positions at most by their amplitude
+            NoiseNode::Displace { source, offsets } => {
+                let mut moved = reach;
+                for (m, offset) in moved.iter_mut().zip(offsets) {
+                    *m += offset.amplitude();
+                }
+                source.reaches_far(moved) || offsets.iter().any(|o| o.reaches_far(reach))
+            }
+            _ => self.children().iter().any(|c| c.reaches_far(reach)),
+        }
+    }
+}
+
+impl Frequency for NoiseNode {
+    /// The sources apply their own frequencies.
+    fn get_frequency(&self) -> [f32; 4] {
+        [1.0; 4]
+    }
+}
+
+impl<S: Simd> Sample32<S> for NoiseNode {
+    #[inline(always)]
+    fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+        let zero = S::Vf32::zeroes();
+        self.eval_f32::<S>([x, zero, zero, zero], 1)
+    }
+
+    #[inline(always)]
+    fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        let zero = S::Vf32::zeroes();
+        self.eval_f32::<S>([x, y, zero, zero], 2)
+    }
+
+    #[inline(always)]
+    fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        self.eval_f32::<S>([x, y, z, S::Vf32::zeroes()], 3)
+    }
+
+    #[inline(always)]
+    fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+        self.eval_f32::<S>([x, y, z, w], 4)
+    }
+}
+
+impl<S: Simd> Sample64<S> for NoiseNode {
+    #[inline(always)]
+    fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
+        let zero = S::Vf64::zeroes();
+        self.eval_f64::<S>([x, zero, zero, zero], 1)
+    }
+
+    #[inline(always)]
+    fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
+        let zero = S::Vf64::zeroes();
+        self.eval_f64::<S>([x, y, zero, zero], 2)
+    }
+
+    #[inline(always)]
+    fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
+        self.eval_f64::<S>([x, y, z, S::Vf64::zeroes()], 3)
+    }
+
+    #[inline(always)]
+    fn sample_4d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64, w: S::Vf64) -> S::Vf64 {
+        self.eval_f64::<S>([x, y, z, w], 4)
+    }
+}
+
+/// Samples a noise source at `p`, applying its frequencies.
+#[inline(always)]
+fn sample_f32<S: Simd>(noise_type: &NoiseType, p: [S::Vf32; 4], dim: usize) -> S::Vf32 {
+    let freq = noise_type.get_frequency();
+    let f = |i: usize| p[i] * S::Vf32::set1(freq[i]);
+    match dim {
+        1 => Sample32::<S>::sample_1d(noise_type, f(0)),
+        2 => Sample32::<S>::sample_2d(noise_type, f(0), f(1)),
+        3 => Sample32::<S>::sample_3d(noise_type, f(0), f(1), f(2)),
+        _ => Sample32::<S>::sample_4d(noise_type, f(0), f(1), f(2), f(3)),
+    }
+}
+
+/// Samples a noise source at `p`, applying its frequencies.
+#[inline(always)]
+fn sample_f64<S: Simd>(noise_type: &NoiseType, p: [S::Vf64; 4], dim: usize) -> S::Vf64 {
+    let freq = noise_type.get_frequency();
+    let f = |i: usize| p[i] * S::Vf64::set1(freq[i] as f64);
+    match dim {
+        1 => Sample64::<S>::sample_1d(noise_type, f(0)),
+        2 => Sample64::<S>::sample_2d(noise_type, f(0), f(1)),
+        3 => Sample64::<S>::sample_3d(noise_type, f(0), f(1), f(2)),
+        _ => Sample64::<S>::sample_4d(noise_type, f(0), f(1), f(2), f(3)),
+    }
+}
@@ -1,10 +1,16 @@
 use super::{
     Cellular2Settings, CellularSettings, DimensionalBeing, FbmSettings, GradientSettings,
-    NoiseDimensions, RidgeSettings, TurbulenceSettings,
+    NoiseDimensions, RidgeSettings, TurbulenceSettings, WhiteNoiseSettings,
 };
+use crate::dimensional_being::Frequency;
+use crate::settings::Settings;
+use crate::AlgorithmVersion;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Specifies what type of noise to generate and contains any relevant settings.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NoiseType {
     Fbm(FbmSettings),
     Ridge(RidgeSettings),
@@ -12,6 +18,7 @@ pub enum NoiseType {
     Gradient(GradientSettings),
     Cellular(CellularSettings),
     Cellular2(Cellular2Settings),
+    White(WhiteNoiseSettings),
 }
 
 impl DimensionalBeing for NoiseType {
@@ -23,6 +30,55 @@ impl DimensionalBeing for NoiseType {
             NoiseType::Gradient(s) => s.get_dimensions(),
             NoiseType::Cellular(s) => s.get_dimensions(),
             NoiseType::Cellular2(s) => s.get_dimensions(),
+            NoiseType::White(s) => s.get_dimensions(),
+        }
+    }
+}
+
+impl Frequency for NoiseType {
+    fn get_frequency(&self) -> [f32; 4] {
+        match self {
+            NoiseType::Fbm(s) => s.get_frequency(),
+            NoiseType::Ridge(s) => s.get_frequency(),
+            NoiseType::Turbulence(s) => s.get_frequency(),
+            NoiseType::Gradient(s) => s.get_frequency(),
+            NoiseType::Cellular(s) => s.get_frequency(),
+            NoiseType::Cellular2(s) => s.get_frequency(),
+            NoiseType::White(s) => s.get_frequency(),
+        }
+    }
+}
+
+impl NoiseType {
+    /// The algorithm revision this noise is generated with.
+    pub fn version(&self) -> AlgorithmVersion {
+        self.get_dimensions().version
+    }
+
+    /// The size, offset and seed of the block this noise generates.
+    pub fn dimensions(&self) -> NoiseDimensions {
+        self.get_dimensions()
+    }
+
+    pub(crate) fn validate(&self) {
+        match self {
+            NoiseType::Fbm(s) => s.validate(),
+            NoiseType::Ridge(s) => s.validate(),
+            NoiseType::Turbulence(s) => s.validate(),
+            NoiseType::Gradient(s) => s.validate(),
+            NoiseType::Cellular(s) => s.validate(),
+            NoiseType::Cellular2(s) => s.validate(),
+            NoiseType::White(s) => s.validate(),
+        }
+    }
+
+    /// Checks the settings that generating would panic on, and returns the problem instead.
+    pub fn check(&self) -> Result<(), &'static str> {
+        match self {
+            NoiseType::Cellular(s) => s.check(),
+            NoiseType::Cellular2(s) => s.check(),
+            NoiseType::White(s) => s.check(),
+            _ => Ok(()),
         }
     }
 }
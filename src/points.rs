@@ -0,0 +1,307 @@
+//! Point distributions, for scattering objects over a terrain or texture.
+//!
+//! `feature_points_2d` and `feature_points_3d` list the feature points that cellular noise
+//! measures distances to, so objects can be placed to match its cells. `poisson_disk_2d` and
+//! `poisson_disk_3d` give random points that are never closer together than a minimum distance.
+
+use std::error::Error;
+use std::fmt;
+
+use simdeez::prelude::*;
+use simdeez::scalar::Scalar;
+
+use crate::noise::cellular_32::{
+    hash_2d, hash_3d, BIT_10_MASK_32, HASH_2_FLOAT_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32,
+};
+use crate::shared::SplitMix64;
+use crate::CellLattice;
+
+/// How many candidates are tried around an active point before it is retired.
+const POISSON_ATTEMPTS: usize = 30;
+
+/// The most background grid cells Poisson-disk sampling allocates, 512 MiB of indices.
+const MAX_POISSON_CELLS: i64 = 1 << 26;
+
+/// A feature point of cellular noise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FeaturePoint<const N: usize> {
+    /// Position of the point, in noise space for the free functions and in sample
+    /// coordinates for the `CellularSettings` methods.
+    pub position: [f32; N],
+    /// The lattice cell that owns the point.
+    pub cell: [i32; N],
+    /// The value cellular noise with `CellReturnType::CellValue` returns inside this cell.
+    pub value: f32,
+}
+
+/// The cellular kernel has no fixed feature points, see `feature_points_3d`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NoFeaturePoints;
+
+impl fmt::Display for NoFeaturePoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(
+            "the square lattice with a search radius of 1 has no fixed 3d feature points, \
+             use a search radius of 2 or more",
+        )
+    }
+}
+
+impl Error for NoFeaturePoints {}
+
+/// Lists the feature points of 2d cellular noise that lie in the box from `min` (inclusive)
+/// to `max` (exclusive), in noise space. The points are the ones `cellular_2d_radius` and
+/// `cellular_2d_lattice` use for the same `lattice`, `jitter` and `seed`.
+///
+/// The SIMD kernels use an approximate reciprocal square root, so their points can differ
+/// from these in the fourth decimal.
+pub fn feature_points_2d(
+    min: [f32; 2],
+    max: [f32; 2],
+    lattice: CellLattice,
+    jitter: f32,
+    seed: i32,
+) -> Vec<FeaturePoint<2>> {
+    if !lattice.supports_dimension(2) {
+        panic!("lattice not supported for 2d feature points");
+    }
+    let (basis, inverse) = lattice.basis_2d();
+    feature_points(min, max, basis, inverse, jitter, |cell| {
+        let v = |c: i32, prime: i32| <Scalar as Simd>::Vi32::set1(c.wrapping_mul(prime));
+        hash_2d::<Scalar>(seed, v(cell[0], X_PRIME_32), v(cell[1], Y_PRIME_32))[0]
+    })
+}
+
+/// Lists the feature points of 3d cellular noise that lie in the box from `min` (inclusive)
+/// to `max` (exclusive), in noise space. The points are the ones `cellular_3d_radius` and
+/// `cellular_3d_lattice` use for the same `lattice`, `jitter` and `seed`.
+///
+/// The original `cellular_3d` kernel has no fixed feature points: for backwards compatibility
+/// it places its last two z neighbours relative to the sample's y cell. `CellularSettings`
+/// uses it for the square lattice with a search radius of 1, so its `feature_points_3d`
+/// returns `NoFeaturePoints` for that combination.
+pub fn feature_points_3d(
+    min: [f32; 3],
+    max: [f32; 3],
+    lattice: CellLattice,
+    jitter: f32,
+    seed: i32,
+) -> Vec<FeaturePoint<3>> {
+    if !lattice.supports_dimension(3) {
+        panic!("lattice not supported for 3d feature points");
+    }
+    let (basis, inverse) = lattice.basis_3d();
+    feature_points(min, max, basis, inverse, jitter, |cell| {
+        let v = |c: i32, prime: i32| <Scalar as Simd>::Vi32::set1(c.wrapping_mul(prime));
+        hash_3d::<Scalar>(
+            seed,
+            v(cell[0], X_PRIME_32),
+            v(cell[1], Y_PRIME_32),
+            v(cell[2], Z_PRIME_32),
+        )[0]
+    })
+}
+
+/// Generates points in the box from the origin to `[width, height]` that are at least
+/// `min_distance` apart, using Bridson's algorithm. The points fill the box until no more
+/// fit, and the result only depends on the arguments.
+///
+/// Panics if `min_distance` is not positive and finite, or so small for the box that the
+/// background grid, one cell per `min_distance / sqrt(2)` square, exceeds 2^26 cells.
+pub fn poisson_disk_2d(width: f32, height: f32, min_distance: f32, seed: i32) -> Vec<[f32; 2]> {
+    poisson_disk([width, height], min_distance, seed)
+}
+
+/// Generates points in the box from the origin to `[width, height, depth]` that are at least
+/// `min_distance` apart, using Bridson's algorithm. The points fill the box until no more
+/// fit, and the result only depends on the arguments.
+///
+/// Panics if `min_distance` is not positive and finite, or so small for the box that the
+/// background grid, one cell per `min_distance / sqrt(3)` cube, exceeds 2^26 cells.
+pub fn poisson_disk_3d(
+    width: f32,
+    height: f32,
+    depth: f32,
+    min_distance: f32,
+    seed: i32,
+) -> Vec<[f32; 3]> {
+    poisson_disk([width, height, depth], min_distance, seed)
+}
+
+fn feature_points<const N: usize>(
+    min: [f32; N],
+    max: [f32; N],
+    basis: [[f64; N]; N],
+    inverse: [[f64; N]; N],
+    jitter: f32,
+    hash: impl Fn([i32; N]) -> i32,
+) -> Vec<FeaturePoint<N>> {
+    // Lattice cells covering the box, padded by the furthest a point can be from its cell
+    let mut lo = [i64::MAX; N];
+    let mut hi = [i64::MIN; N];
+    for corner in 0..1 << N {
+        for (i, row) in inverse.iter().enumerate() {
+            let mut l = 0.0;
+            for (j, m) in row.iter().enumerate() {
+                let c = if corner & (1 << j) == 0 {
+                    min[j]
+                } else {
+                    max[j]
+                };
+                l += m * c as f64;
+            }
+            lo[i] = lo[i].min(l.floor() as i64);
+            hi[i] = hi[i].max(l.ceil() as i64);
+        }
+    }
+    for (i, row) in inverse.iter().enumerate() {
+        let reach = (jitter.abs() as f64 * row.iter().map(|m| m.abs()).sum::<f64>()).ceil() as i64;
+        lo[i] -= reach + 1;
+        hi[i] += reach + 1;
+    }
+
+    let mut points = Vec::new();
+    for_each_cell(lo, hi, |cell| {
+        let cell = cell.map(|c| c as i32);
+        let hash = hash(cell);
+        let mut offset = [0.0f32; N];
+        for (k, d) in offset.iter_mut().enumerate() {
+            *d = ((hash >> (10 * k)) & BIT_10_MASK_32) as f32 - 511.5;
+        }
+        let inv_mag = jitter / offset.iter().map(|d| d * d).sum::<f32>().sqrt();
+        let mut position = [0.0f32; N];
+        for (i, p) in position.iter_mut().enumerate() {
+            for (j, c) in cell.iter().enumerate() {
+                *p += *c as f32 * basis[i][j] as f32;
+            }
+            *p += offset[i] * inv_mag;
+        }
+        if (0..N).all(|i| position[i] >= min[i] && position[i] < max[i]) {
+            points.push(FeaturePoint {
+                position,
+                cell,
+                value: HASH_2_FLOAT_32 * hash as f32,
+            });
+        }
+    });
+    points
+}
+
+fn poisson_disk<const N: usize>(size: [f32; N], min_distance: f32, seed: i32) -> Vec<[f32; N]> {
+    if !min_distance.is_finite() || min_distance <= 0.0 {
+        panic!("invalid min_distance in poisson disk sampling");
+    }
+    if size.iter().any(|s| !s.is_finite() || *s <= 0.0) {
+        return Vec::new();
+    }
+    let r = min_distance as f64;
+    let size = size.map(|s| s as f64);
+
+    // Background grid small enough that each cell holds at most one point
+    let cell_size = r / (N as f64).sqrt();
+    let grid = size.map(|s| (s / cell_size).ceil() as i64);
+    let cell_count = grid
+        .iter()
+        .try_fold(1i64, |count, g| count.checked_mul(*g))
+        .filter(|count| *count <= MAX_POISSON_CELLS)
+        .expect("min_distance too small for the size in poisson disk sampling");
+    let reach = (N as f64).sqrt().ceil() as i64;
+    let mut cells = vec![usize::MAX; cell_count as usize];
+    let cell_index = |p: &[f64; N]| {
+        let mut index = 0;
+        for i in (0..N).rev() {
+            index = index * grid[i] + ((p[i] / cell_size) as i64).min(grid[i] - 1);
+        }
+        index as usize
+    };
+
+    let mut rng = SplitMix64::new(seed);
+    let mut points: Vec<[f64; N]> = Vec::new();
+    let mut active = Vec::new();
+    let first = size.map(|s| rng.next_f64() * s);
+    cells[cell_index(&first)] = 0;
+    points.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let a = (rng.next() % active.len() as u64) as usize;
+        let center = points[active[a]];
+        let mut found = false;
+        for _ in 0..POISSON_ATTEMPTS {
+            // Uniform in the shell between r and 2r around the active point
+            let candidate = loop {
+                let d = [0; N].map(|_| (rng.next_f64() * 4.0 - 2.0) * r);
+                let length2 = d.iter().map(|v| v * v).sum::<f64>();
+                if length2 >= r * r && length2 <= 4.0 * r * r {
+                    let mut c = center;
+                    for (c, d) in c.iter_mut().zip(d.iter()) {
+                        *c += d;
+                    }
+                    break c;
+                }
+            };
+            if (0..N).any(|i| candidate[i] < 0.0 || candidate[i] >= size[i]) {
+                continue;
+            }
+
+            let base = candidate.map(|c| (c / cell_size) as i64);
+            let mut lo = [0; N];
+            let mut hi = [0; N];
+            for i in 0..N {
+                lo[i] = (base[i] - reach).max(0);
+                hi[i] = (base[i] + reach).min(grid[i] - 1);
+            }
+            let mut free = true;
+            for_each_cell(lo, hi, |cell| {
+                let mut index = 0;
+                for i in (0..N).rev() {
+                    index = index * grid[i] + cell[i];
+                }
+                let other = cells[index as usize];
+                if other != usize::MAX {
+                    let distance2 = (0..N)
+                        .map(|i| (points[other][i] - candidate[i]).powi(2))
+                        .sum::<f64>();
+                    if distance2 < r * r {
+                        free = false;
+                    }
+                }
+            });
+            if free {
+                cells[cell_index(&candidate)] = points.len();
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(a);
+        }
+    }
+
+    points.iter().map(|p| p.map(|v| v as f32)).collect()
+}
+
+/// Calls `f` for every cell from `lo` to `hi`, both inclusive.
+fn for_each_cell<const N: usize>(lo: [i64; N], hi: [i64; N], mut f: impl FnMut([i64; N])) {
+    if (0..N).any(|i| lo[i] > hi[i]) {
+        return;
+    }
+    let mut cell = lo;
+    loop {
+        f(cell);
+        let mut i = 0;
+        loop {
+            if i == N {
+                return;
+            }
+            if cell[i] < hi[i] {
+                cell[i] += 1;
+                break;
+            }
+            cell[i] = lo[i];
+            i += 1;
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! A small text format for noise settings, so they can be shared without writing Rust.
+//!
+//! A preset is a list of `key = value` lines. Lists are separated by spaces, and `#` starts
+//! a comment:
+//!
+//! ```text
+//! # Rolling hills
+//! type = fbm
+//! size = 256 256
+//! offset = 0 0
+//! freq = 0.02
+//! octaves = 5
+//! lacunarity = 0.5
+//! gain = 2
+//! seed = 42
+//! ```
+//!
+//! `type` and `size` are required, the number of sizes sets the dimensions. `freq` takes
+//! one value for every axis or one per axis. Everything else falls back to the defaults of
+//! `NoiseBuilder`, `version` included. The keys per noise type are:
+//!
+//! * `fbm`, `ridge`, `turbulence`: `octaves`, `lacunarity`, `gain`
+//! * `gradient`, `white`: none
+//! * `cellular`: `distance`, `return`, `jitter`, `search_radius`, `lattice`
+//! * `cellular2`: `distance`, `return`, `jitter`, `search_radius`, `index0`, `index1`
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dimensional_being::{DimensionalBeing, Frequency};
+use crate::settings::{Settings, SimplexSettings};
+use crate::{
+    AlgorithmVersion, Cell2ReturnType, CellDistanceFunction, CellLattice, CellReturnType,
+    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, NoiseDimensions, NoiseType,
+    RidgeSettings, TurbulenceSettings, WhiteNoiseSettings,
+};
+
+/// Why a preset could not be read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PresetError {
+    /// The line the problem is on, counting from 1, if it is on a single line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for PresetError {}
+
+impl NoiseType {
+    /// Reads noise settings from a preset, see the `preset` module for the format.
+    pub fn from_preset(preset: &str) -> Result<NoiseType, PresetError> {
+        let mut entries = Entries::parse(preset)?;
+        let (type_line, name) = entries
+            .take("type")
+            .ok_or_else(|| error(None, "missing key `type`"))?;
+
+        let (size_line, size) = entries
+            .list::<usize>("size")?
+            .ok_or_else(|| error(None, "missing key `size`"))?;
+        if size.is_empty() || size.len() > 4 {
+            return Err(error(Some(size_line), "`size` needs 1 to 4 values"));
+        }
+        let mut dim = NoiseDimensions::default(size.len());
+        dim.width = size[0];
+        dim.height = size.get(1).copied().unwrap_or(1);
+        dim.depth = size.get(2).copied().unwrap_or(1);
+        dim.time = size.get(3).copied().unwrap_or(1);
+        if let Some((line, offset)) = entries.list::<f32>("offset")? {
+            if offset.len() != size.len() {
+                return Err(error(Some(line), "`offset` needs one value per axis"));
+            }
+            dim.x = offset[0];
+            dim.y = offset.get(1).copied().unwrap_or(0.0);
+            dim.z = offset.get(2).copied().unwrap_or(0.0);
+            dim.w = offset.get(3).copied().unwrap_or(0.0);
+        }
+        if let Some(seed) = entries.value("seed")? {
+            dim.seed = seed;
+        }
+        if let Some((line, number)) = entries.take("version") {
+            dim.version = number
+                .parse()
+                .ok()
+                .and_then(AlgorithmVersion::from_number)
+                .ok_or_else(|| unknown(line, "version", &number))?;
+        }
+
+        let noise_type = match name.as_str() {
+            "fbm" => NoiseType::Fbm(simplex(FbmSettings::default(dim), &mut entries)?),
+            "ridge" => NoiseType::Ridge(simplex(RidgeSettings::default(dim), &mut entries)?),
+            "turbulence" => {
+                NoiseType::Turbulence(simplex(TurbulenceSettings::default(dim), &mut entries)?)
+            }
+            "gradient" => {
+                NoiseType::Gradient(frequency(GradientSettings::default(dim), &mut entries)?)
+            }
+            "white" => NoiseType::White(frequency(WhiteNoiseSettings::default(dim), &mut entries)?),
+            "cellular" | "cellular2" if dim.dim != 2 && dim.dim != 3 => {
+                return Err(error(
+                    Some(size_line),
+                    "cellular noise needs 2 or 3 values in `size`",
+                ));
+            }
+            "cellular" => {
+                let mut settings = frequency(CellularSettings::default(dim), &mut entries)?;
+                if let Some((line, v)) = entries.take("distance") {
+                    settings.distance_function = distance_function(line, &v)?;
+                }
+                if let Some((line, v)) = entries.take("return") {
+                    settings.return_type = match v.as_str() {
+                        "cell_value" => CellReturnType::CellValue,
+                        "distance" => CellReturnType::Distance,
+                        _ => return Err(unknown(line, "return type", &v)),
+                    };
+                }
+                if let Some((line, v)) = entries.take("lattice") {
+                    settings.lattice = match v.as_str() {
+                        "square" => CellLattice::Square,
+                        "hexagonal" => CellLattice::Hexagonal,
+                        "body_centered_cubic" => CellLattice::BodyCenteredCubic,
+                        "face_centered_cubic" => CellLattice::FaceCenteredCubic,
+                        _ => return Err(unknown(line, "lattice", &v)),
+                    };
+                }
+                if let Some(jitter) = entries.value("jitter")? {
+                    settings.jitter = jitter;
+                }
+                if let Some(search_radius) = entries.value("search_radius")? {
+                    settings.search_radius = search_radius;
+                }
+                NoiseType::Cellular(settings)
+            }
+            "cellular2" => {
+                let mut settings = frequency(Cellular2Settings::default(dim), &mut entries)?;
+                if let Some((line, v)) = entries.take("distance") {
+                    settings.distance_function = distance_function(line, &v)?;
+                }
+                if let Some((line, v)) = entries.take("return") {
+                    settings.return_type = match v.as_str() {
+                        "distance2" => Cell2ReturnType::Distance2,
+                        "distance2_add" => Cell2ReturnType::Distance2Add,
+                        "distance2_sub" => Cell2ReturnType::Distance2Sub,
+                        "distance2_mul" => Cell2ReturnType::Distance2Mul,
+                        "distance2_div" => Cell2ReturnType::Distance2Div,
+                        _ => return Err(unknown(line, "return type", &v)),
+                    };
+                }
+                if let Some(jitter) = entries.value("jitter")? {
+                    settings.jitter = jitter;
+                }
+                if let Some(search_radius) = entries.value("search_radius")? {
+                    settings.search_radius = search_radius;
+                }
+                if let Some(index0) = entries.value("index0")? {
+                    settings.index0 = index0;
+                }
+                if let Some(index1) = entries.value("index1")? {
+                    settings.index1 = index1;
+                }
+                NoiseType::Cellular2(settings)
+            }
+            _ => return Err(unknown(type_line, "noise type", &name)),
+        };
+
+        if let Some((key, line)) = entries.remaining() {
+            return Err(error(
+                Some(line),
+                &format!("key `{}` is not used by {} noise", key, name),
+            ));
+        }
+        noise_type.check().map_err(|message| error(None, message))?;
+        Ok(noise_type)
+    }
+
+    /// Writes the settings as a preset that `from_preset` reads back to the same settings.
+    pub fn to_preset(&self) -> String {
+        let dim = self.get_dimensions();
+        let n = dim.dim;
+        let mut lines = vec![format!("type = {}", self.preset_name())];
+        let size = [dim.width, dim.height, dim.depth, dim.time];
+        lines.push(format!("size = {}", join(&size[..n])));
+        lines.push(format!(
+            "offset = {}",
+            join(&[dim.x, dim.y, dim.z, dim.w][..n])
+        ));
+        lines.push(format!("freq = {}", join(&self.get_frequency()[..n])));
+        lines.push(format!("seed = {}", dim.seed));
+        lines.push(format!("version = {}", dim.version.number()));
+        match self {
+            NoiseType::Fbm(s) => push_simplex(&mut lines, s.octaves, s.lacunarity, s.gain),
+            NoiseType::Ridge(s) => push_simplex(&mut lines, s.octaves, s.lacunarity, s.gain),
+            NoiseType::Turbulence(s) => push_simplex(&mut lines, s.octaves, s.lacunarity, s.gain),
+            NoiseType::Gradient(_) | NoiseType::White(_) => {}
+            NoiseType::Cellular(s) => {
+                lines.push(format!(
+                    "distance = {}",
+                    distance_function_name(s.distance_function)
+                ));
+                let return_type = match s.return_type {
+                    CellReturnType::CellValue => "cell_value",
+                    CellReturnType::Distance => "distance",
+                };
+                lines.push(format!("return = {}", return_type));
+                let lattice = match s.lattice {
+                    CellLattice::Square => "square",
+                    CellLattice::Hexagonal => "hexagonal",
+                    CellLattice::BodyCenteredCubic => "body_centered_cubic",
+                    CellLattice::FaceCenteredCubic => "face_centered_cubic",
+                };
+                lines.push(format!("lattice = {}", lattice));
+                lines.push(format!("jitter = {}", s.jitter));
+                lines.push(format!("search_radius = {}", s.search_radius));
+            }
+            NoiseType::Cellular2(s) => {
+                lines.push(format!(
+                    "distance = {}",
+                    distance_function_name(s.distance_function)
+                ));
+                let return_type = match s.return_type {
+                    Cell2ReturnType::Distance2 => "distance2",
+                    Cell2ReturnType::Distance2Add => "distance2_add",
+                    Cell2ReturnType::Distance2Sub => "distance2_sub",
+                    Cell2ReturnType::Distance2Mul => "distance2_mul",
+                    Cell2ReturnType::Distance2Div => "distance2_div",
+                };
+                lines.push(format!("return = {}", return_type));
+                lines.push(format!("jitter = {}", s.jitter));
+                lines.push(format!("search_radius = {}", s.search_radius));
+                lines.push(format!("index0 = {}", s.index0));
+                lines.push(format!("index1 = {}", s.index1));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    fn preset_name(&self) -> &'static str {
+        match self {
+            NoiseType::Fbm(_) => "fbm",
+            NoiseType::Ridge(_) => "ridge",
+            NoiseType::Turbulence(_) => "turbulence",
+            NoiseType::Gradient(_) => "gradient",
+            NoiseType::Cellular(_) => "cellular",
+            NoiseType::Cellular2(_) => "cellular2",
+            NoiseType::White(_) => "white",
+        }
+    }
+}
+
+/// The `key = value` lines of a preset, removed as they are used.
+struct Entries(HashMap<String, (usize, String)>);
+
+impl Entries {
+    fn parse(preset: &str) -> Result<Entries, PresetError> {
+        let mut entries = HashMap::new();
+        for (i, line) in preset.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(Some(line_number), "expected `key = value`"))?;
+            let key = key.trim().to_string();
+            if key.is_empty() {
+                return Err(error(Some(line_number), "expected `key = value`"));
+            }
+            if entries.contains_key(&key) {
+                return Err(error(
+                    Some(line_number),
+                    &format!("duplicate key `{}`", key),
+                ));
+            }
+            entries.insert(key, (line_number, value.trim().to_string()));
+        }
+        Ok(Entries(entries))
+    }
+
+    fn take(&mut self, key: &str) -> Option<(usize, String)> {
+        self.0.remove(key)
+    }
+
+    fn value<T: FromStr>(&mut self, key: &str) -> Result<Option<T>, PresetError> {
+        match self.take(key) {
+            Some((line, v)) => v
+                .parse()
+                .map(Some)
+                .map_err(|_| error(Some(line), &format!("invalid value for `{}`", key))),
+            None => Ok(None),
+        }
+    }
+
+    fn list<T: FromStr>(&mut self, key: &str) -> Result<Option<(usize, Vec<T>)>, PresetError> {
+        match self.take(key) {
+            Some((line, v)) => v
+                .split_whitespace()
+                .map(|v| v.parse())
+                .collect::<Result<Vec<T>, _>>()
+                .map(|list| Some((line, list)))
+                .map_err(|_| error(Some(line), &format!("invalid value for `{}`", key))),
+            None => Ok(None),
+        }
+    }
+
+    /// The first key that was not used, in line order.
+    fn remaining(&self) -> Option<(&str, usize)> {
+        self.0
+            .iter()
+            .map(|(key, (line, _))| (key.as_str(), *line))
+            .min_by_key(|(_, line)| *line)
+    }
+}
+
+fn error(line: Option<usize>, message: &str) -> PresetError {
+    PresetError {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn unknown(line: usize, what: &str, value: &str) -> PresetError {
+    error(Some(line), &format!("unknown {} `{}`", what, value))
+}
+
+fn frequency<T: Settings + DimensionalBeing>(
+    mut settings: T,
+    entries: &mut Entries,
+) -> Result<T, PresetError> {
+    let n = settings.get_dimensions().dim;
+    if let Some((line, freq)) = entries.list::<f32>("freq")? {
+        match freq.len() {
+            1 => settings.with_freq(freq[0]),
+            l if l == n => match freq[..] {
+                [x, y] => settings.with_freq_2d(x, y),
+                [x, y, z] => settings.with_freq_3d(x, y, z),
+                [x, y, z, w] => settings.with_freq_4d(x, y, z, w),
+                _ => settings.with_freq(freq[0]),
+            },
+            _ => {
+                return Err(error(
+                    Some(line),
+                    "`freq` needs one value, or one value per axis",
+                ))
+            }
+        };
+    }
+    Ok(settings)
+}
+
+fn simplex<T: Settings + SimplexSettings + DimensionalBeing>(
+    settings: T,
+    entries: &mut Entries,
+) -> Result<T, PresetError> {
+    let mut settings = frequency(settings, entries)?;
+    if let Some(octaves) = entries.value("octaves")? {
+        settings.with_octaves(octaves);
+    }
+    if let Some(lacunarity) = entries.value("lacunarity")? {
+        settings.with_lacunarity(lacunarity);
+    }
+    if let Some(gain) = entries.value("gain")? {
+        settings.with_gain(gain);
+    }
+    Ok(settings)
+}
+
+fn distance_function(line: usize, name: &str) -> Result<CellDistanceFunction, PresetError> {
+    match name {
+        "euclidean" => Ok(CellDistanceFunction::Euclidean),
+        "manhattan" => Ok(CellDistanceFunction::Manhattan),
+        "natural" => Ok(CellDistanceFunction::Natural),
+        _ => Err(unknown(line, "distance function", name)),
+    }
+}
+
+fn distance_function_name(distance_function: CellDistanceFunction) -> &'static str {
+    match distance_function {
+        CellDistanceFunction::Euclidean => "euclidean",
+        CellDistanceFunction::Manhattan => "manhattan",
+        CellDistanceFunction::Natural => "natural",
+    }
+}
+
+fn push_simplex(lines: &mut Vec<String>, octaves: u8, lacunarity: f32, gain: f32) {
+    lines.push(format!("octaves = {}", octaves));
+    lines.push(format!("lacunarity = {}", lacunarity));
+    lines.push(format!("gain = {}", gain));
+}
+
+fn join<T: fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,194 @@
+//! Integer output, scaled, rounded and saturated while the noise is generated.
+//!
+//! Scaling needs the range of the whole block before the first value can be converted, so
+//! the block is sampled twice: once for its range and once to convert it. Nothing but the
+//! integer output is stored, which for large volumes is worth more than the extra pass.
+
+use simdeez::prelude::*;
+
+use crate::dimensional_being::Frequency;
+use crate::far_blocks::{for_each_vector_f64, is_far_noise, FarPositions};
+use crate::noise::ops::round_i32;
+use crate::noise_helpers_32::{for_each_vector_f32, Sample32};
+use crate::noise_helpers_64::Sample64;
+use crate::{NoiseDimensions, NoiseNode, NoiseType};
+
+/// An integer type noise can be generated as.
+pub(crate) trait Quantized: Copy + Into<f32> {
+    const MIN: f32;
+    const MAX: f32;
+
+    /// `v`, which is already in range.
+    fn from_i32(v: i32) -> Self;
+}
+
+impl Quantized for u8 {
+    const MIN: f32 = u8::MIN as f32;
+    const MAX: f32 = u8::MAX as f32;
+
+    fn from_i32(v: i32) -> u8 {
+        v as u8
+    }
+}
+
+impl Quantized for u16 {
+    const MIN: f32 = u16::MIN as f32;
+    const MAX: f32 = u16::MAX as f32;
+
+    fn from_i32(v: i32) -> u16 {
+        v as u16
+    }
+}
+
+impl Quantized for i16 {
+    const MIN: f32 = i16::MIN as f32;
+    const MAX: f32 = i16::MAX as f32;
+
+    fn from_i32(v: i32) -> i16 {
+        v as i16
+    }
+}
+
+/// A block of noise that can be sampled a vector at a time, in the order the block helpers
+/// store it.
+trait Block<S: Simd> {
+    fn dimensions(&self) -> NoiseDimensions;
+
+    unsafe fn for_each_vector<F: FnMut(S::Vf32, usize)>(&self, f: F);
+}
+
+/// Sampled with the f32 kernels.
+struct Near<'a, T>(&'a T);
+
+/// Sampled in f64 where it is far, see `far_blocks`.
+struct Far<'a, T>(&'a T);
+
+impl<S: Simd, T: Sample32<S> + Frequency> Block<S> for Near<'_, T> {
+    fn dimensions(&self) -> NoiseDimensions {
+        self.0.get_dimensions()
+    }
+
+    #[inline(always)]
+    unsafe fn for_each_vector<F: FnMut(S::Vf32, usize)>(&self, f: F) {
+        for_each_vector_f32::<S, _, _>(self.0, f)
+    }
+}
+
+impl<S: Simd, T: Sample32<S> + Sample64<S> + Frequency + FarPositions> Block<S> for Far<'_, T> {
+    fn dimensions(&self) -> NoiseDimensions {
+        self.0.get_dimensions()
+    }
+
+    #[inline(always)]
+    unsafe fn for_each_vector<F: FnMut(S::Vf32, usize)>(&self, f: F) {
+        for_each_vector_f64::<S, _, _>(self.0, f)
+    }
+}
+
+/// The block scaled from `scale_min` to `scale_max` like `scale_noise` does, rounded to the
+/// nearest integer.
+#[inline(always)]
+unsafe fn quantize<S, B, Q>(block: &B, scale_min: Q, scale_max: Q) -> Vec<Q>
+where
+    S: Simd,
+    B: Block<S>,
+    Q: Quantized,
+{
+    let dim = block.dimensions();
+    let len = match dim.dim {
+        1 => dim.width,
+        2 => dim.width * dim.height,
+        3 => dim.width * dim.height * dim.depth,
+        _ => dim.width * dim.height * dim.depth * dim.time,
+    };
+
+    let mut min_s = S::Vf32::set1(f32::MAX);
+    let mut max_s = S::Vf32::set1(f32::MIN);
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    block.for_each_vector(|f, lanes| {
+        if lanes == S::Vf32::WIDTH {
+            min_s = min_s.min(f);
+            max_s = max_s.max(f);
+        } else {
+            for j in 0..lanes {
+                min = min.min(f[j]);
+                max = max.max(f[j]);
+            }
+        }
+    });
+    for i in 0..S::Vf32::WIDTH {
+        min = min.min(min_s[i]);
+        max = max.max(max_s[i]);
+    }
+
+    // A flat block has no range to scale, so it all maps to the bottom like `scale_noise`
+    // would if it did not divide by zero
+    if max == min {
+        return vec![scale_min; len];
+    }
+    let scale_min: f32 = scale_min.into();
+    let multiplier = (scale_max.into() - scale_min) / (max - min);
+    let offset = scale_min - min * multiplier;
+    let mut result = Vec::with_capacity(len);
+    block.for_each_vector(|f, lanes| {
+        let scaled = S::Vf32::set1(multiplier) * f + S::Vf32::set1(offset);
+        let clamped = scaled.max(S::Vf32::set1(Q::MIN)).min(S::Vf32::set1(Q::MAX));
+        let rounded = round_i32::<S>(clamped);
+        for j in 0..lanes {
+            result.push(Q::from_i32(rounded[j]));
+        }
+    });
+    result
+}
+
+// Expanded through this macro the per-backend functions lose the lint exemption they get
+// from `backend_runtime_generate!` directly.
+macro_rules! get_quantized_noise {
+    ($fn_name: ident, $node_fn_name: ident, $q_type: ty) => {
+        backend_runtime_generate!(
+            #[allow(dead_code)]
+            pub(crate) fn $fn_name(
+                noise_type: &NoiseType,
+                min: $q_type,
+                max: $q_type,
+            ) -> Vec<$q_type> {
+                unsafe {
+                    if is_far_noise(noise_type) {
+                        return quantize::<S, _, $q_type>(&Far(noise_type), min, max);
+                    }
+                    match noise_type {
+                        NoiseType::Fbm(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::Ridge(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::Turbulence(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::Gradient(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::Cellular(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::Cellular2(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                        NoiseType::White(s) => quantize::<S, _, $q_type>(&Near(s), min, max),
+                    }
+                }
+            }
+        );
+
+        backend_runtime_generate!(
+            #[allow(dead_code)]
+            pub(crate) fn $node_fn_name(
+                node: &NoiseNode,
+                min: $q_type,
+                max: $q_type,
+            ) -> Vec<$q_type> {
+                unsafe {
+                    if node.is_far() {
+                        quantize::<S, _, $q_type>(&Far(node), min, max)
+                    } else {
+                        quantize::<S, _, $q_type>(&Near(node), min, max)
+                    }
+                }
+            }
+        );
+    };
+}
+
+get_quantized_noise!(get_u8_noise, get_u8_node_noise, u8);
+get_quantized_noise!(get_u16_noise, get_u16_node_noise, u16);
+get_quantized_noise!(get_i16_noise, get_i16_node_noise, i16);
@@ -0,0 +1,154 @@
+//! User-defined noise.
+//!
+//! Implement `Sample` with your own SIMD kernels to get blocks of noise out of them through
+//! the same helpers, min and max tracking, and runtime instruction set detection as the
+//! built in noise types.
+
+use simdeez::prelude::*;
+
+use crate::dimensional_being::{DimensionalBeing, Frequency};
+use crate::noise_helpers_32::{self, Sample32};
+use crate::shared::scale_buffer;
+use crate::NoiseDimensions;
+
+/// A noise generator that can be sampled a SIMD vector at a time.
+///
+/// Only the `sample_*` function for the dimension returned by `dimensions` is called, the
+/// others panic unless they are implemented.
+///
+/// The kernels take f32 positions, which are too coarse to sample smooth noise 4096 samples
+/// or cells from the origin. Unlike the built in noise types, which switch to f64 kernels
+/// there from `AlgorithmVersion::V3` on, custom noise keeps the f32 positions at any distance.
+///
+/// The kernels are generic over the instruction set, so `Sample` cannot be made into a trait
+/// object; the noise is generated from its own type.
+///
+/// ```rust
+/// use simdnoise::*;
+/// use simdeez::prelude::*;
+///
+/// struct Stripes {
+///     dim: NoiseDimensions,
+/// }
+///
+/// impl Sample for Stripes {
+///     fn dimensions(&self) -> NoiseDimensions {
+///         self.dim
+///     }
+///
+///     fn frequency(&self) -> [f32; 4] {
+///         [0.25; 4]
+///     }
+///
+///     fn sample_2d<S: Simd>(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+///         let wave = x + y;
+///         wave - wave.floor()
+///     }
+/// }
+///
+/// let mut dim = NoiseDimensions::default(2);
+/// dim.width = 64;
+/// dim.height = 64;
+/// let (noise, min, max) = Stripes { dim }.generate();
+/// ```
+pub trait Sample: Sized {
+    /// The block to generate. `min` and `max` are not used; `generate_scaled` takes the
+    /// range to scale to.
+    fn dimensions(&self) -> NoiseDimensions;
+
+    /// The frequencies, x to w, the sample coordinates are multiplied with.
+    fn frequency(&self) -> [f32; 4] {
+        [1.0; 4]
+    }
+
+    fn sample_1d<S: Simd>(&self, x: S::Vf32) -> S::Vf32 {
+        let _ = x;
+        panic!("1d sampling not implemented for this noise")
+    }
+
+    fn sample_2d<S: Simd>(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        let _ = (x, y);
+        panic!("2d sampling not implemented for this noise")
+    }
+
+    fn sample_3d<S: Simd>(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        let _ = (x, y, z);
+        panic!("3d sampling not implemented for this noise")
+    }
+
+    fn sample_4d<S: Simd>(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+        let _ = (x, y, z, w);
+        panic!("4d sampling not implemented for this noise")
+    }
+
+    /// Generate a chunk of noise, and the min and max value generated, so you can scale it
+    /// as you wish
+    fn generate(&self) -> (Vec<f32>, f32, f32) {
+        get_sample_noise(self)
+    }
+
+    /// Generate a chunk of noise with values scaled from min to max
+    fn generate_scaled(&self, min: f32, max: f32) -> Vec<f32> {
+        let (noise, noise_min, noise_max) = get_sample_noise(self);
+        scale_buffer(noise, min, max, noise_min, noise_max)
+    }
+}
+
+/// Lets the block helpers sample a `Sample`.
+struct Custom<'a, T>(&'a T);
+
+impl<T: Sample> DimensionalBeing for Custom<'_, T> {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.0.dimensions()
+    }
+}
+
+impl<T: Sample> Frequency for Custom<'_, T> {
+    fn get_frequency(&self) -> [f32; 4] {
+        self.0.frequency()
+    }
+}
+
+impl<S: Simd, T: Sample> Sample32<S> for Custom<'_, T> {
+    #[inline(always)]
+    fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+        self.0.sample_1d::<S>(x)
+    }
+
+    #[inline(always)]
+    fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        self.0.sample_2d::<S>(x, y)
+    }
+
+    #[inline(always)]
+    fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        self.0.sample_3d::<S>(x, y, z)
+    }
+
+    #[inline(always)]
+    fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+        self.0.sample_4d::<S>(x, y, z, w)
+    }
+}
+
+/// Get a block of custom noise with a specific instruction set.
+///
+/// # Safety
+/// The instruction set `S` must be supported by the cpu.
+pub unsafe fn get_sample_noise_generic<S: Simd, T: Sample>(noise: &T) -> (Vec<f32>, f32, f32) {
+    let custom = Custom(noise);
+    match noise.dimensions().dim {
+        1 => noise_helpers_32::get_1d_noise_helper_f32::<S, _>(&custom),
+        2 => noise_helpers_32::get_2d_noise_helper_f32::<S, _>(&custom),
+        3 => noise_helpers_32::get_3d_noise_helper_f32::<S, _>(&custom),
+        4 => noise_helpers_32::get_4d_noise_helper_f32::<S, _>(&custom),
+        _ => panic!("not implemented"),
+    }
+}
+
+backend_runtime_generate!(
+    /// Get a block of custom noise, using the active backend.
+    pub fn get_sample_noise<T: Sample>(noise: &T) -> (Vec<f32>, f32, f32) {
+        unsafe { get_sample_noise_generic::<S, T>(noise) }
+    }
+);
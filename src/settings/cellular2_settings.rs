@@ -1,19 +1,31 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise};
-use crate::noise::cell2_32::{cellular2_2d, cellular2_3d};
-use crate::noise::cell2_64::{cellular2_2d as cellular2_2d_f64, cellular2_3d as cellular2_3d_f64};
+use crate::noise::cell2_32::{
+    cellular2_2d_radius, cellular2_3d_radius, cellular2_3d_with_z_prime, MAX_DISTANCE_INDEX,
+};
+use crate::noise::cell2_64::{
+    cellular2_2d_radius as cellular2_2d_radius_f64, cellular2_3d_radius as cellular2_3d_radius_f64,
+    cellular2_3d_with_z_prime as cellular2_3d_with_z_prime_f64,
+};
 pub use crate::noise::cell2_return_type::Cell2ReturnType;
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise};
 
 use super::Settings;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Cellular2SettingsData"))]
 pub struct Cellular2Settings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -24,6 +36,10 @@ pub struct Cellular2Settings {
     pub jitter: f32,
     pub index0: usize,
     pub index1: usize,
+    /// How many cells around the sample point are searched for feature points.
+    /// Raise this when using a high jitter, or when `index1` asks for more
+    /// neighbours than the default 3x3 (3x3x3) neighbourhood reliably contains.
+    pub search_radius: usize,
 }
 
 impl DimensionalBeing for Cellular2Settings {
@@ -44,6 +60,7 @@ impl Settings for Cellular2Settings {
             jitter: 0.25,
             index0: 0,
             index1: 1,
+            search_radius: 1,
         }
     }
     fn with_seed(&mut self, seed: i32) -> &mut Cellular2Settings {
@@ -51,6 +68,11 @@ impl Settings for Cellular2Settings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut Cellular2Settings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut Cellular2Settings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -102,6 +124,7 @@ impl Settings for Cellular2Settings {
     }
 
     fn generate(self) -> (Vec<f32>, f32, f32) {
+        self.validate();
         let d = self.dim.dim;
         match d {
             2 => get_2d_noise(&NoiseType::Cellular2(self)),
@@ -111,8 +134,8 @@ impl Settings for Cellular2Settings {
     }
 
     fn validate(&self) {
-        if self.index0 > 2 || self.index1 > 3 || self.index0 >= self.index1 {
-            panic!("invalid index settings in cellular2 noise");
+        if let Err(message) = self.check() {
+            panic!("{}", message);
         }
     }
 
@@ -139,7 +162,7 @@ impl<S: Simd> Sample32<S> for Cellular2Settings {
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
-        cellular2_2d::<S>(
+        cellular2_2d_radius::<S>(
             x,
             y,
             self.distance_function,
@@ -147,23 +170,40 @@ impl<S: Simd> Sample32<S> for Cellular2Settings {
             S::Vf32::set1(self.jitter),
             self.index0,
             self.index1,
+            self.search_radius,
             self.dim.seed,
         )
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        cellular2_3d::<S>(
-            x,
-            y,
-            z,
-            self.distance_function,
-            self.return_type,
-            S::Vf32::set1(self.jitter),
-            self.index0,
-            self.index1,
-            self.dim.seed,
-        )
+        if self.search_radius == 1 {
+            cellular2_3d_with_z_prime::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.index0,
+                self.index1,
+                self.dim.seed,
+                self.dim.version.z_prime_32(),
+            )
+        } else {
+            cellular2_3d_radius::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.index0,
+                self.index1,
+                self.search_radius,
+                self.dim.seed,
+            )
+        }
     }
 
     #[inline(always)]
@@ -182,7 +222,7 @@ impl<S: Simd> Sample64<S> for Cellular2Settings {
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        cellular2_2d_f64::<S>(
+        cellular2_2d_radius_f64::<S>(
             x,
             y,
             self.distance_function,
@@ -190,23 +230,40 @@ impl<S: Simd> Sample64<S> for Cellular2Settings {
             S::Vf64::set1(self.jitter.into()),
             self.index0,
             self.index1,
+            self.search_radius,
             self.dim.seed.into(),
         )
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        cellular2_3d_f64::<S>(
-            x,
-            y,
-            z,
-            self.distance_function,
-            self.return_type,
-            S::Vf64::set1(self.jitter.into()),
-            self.index0,
-            self.index1,
-            self.dim.seed.into(),
-        )
+        if self.search_radius == 1 {
+            cellular2_3d_with_z_prime_f64::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.index0,
+                self.index1,
+                self.dim.seed.into(),
+                self.dim.version.z_prime_64(),
+            )
+        } else {
+            cellular2_3d_radius_f64::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.index0,
+                self.index1,
+                self.search_radius,
+                self.dim.seed.into(),
+            )
+        }
     }
 
     #[inline(always)]
@@ -217,6 +274,27 @@ impl<S: Simd> Sample64<S> for Cellular2Settings {
 }
 
 impl Cellular2Settings {
+    pub(crate) fn check(&self) -> Result<(), &'static str> {
+        if self.index1 > MAX_DISTANCE_INDEX || self.index0 >= self.index1 {
+            return Err("invalid index settings in cellular2 noise");
+        }
+        if self.search_radius == 0 {
+            return Err("invalid search radius in cellular2 noise");
+        }
+        if !self.jitter.is_finite() {
+            return Err("invalid jitter in cellular2 noise");
+        }
+        let span = 2 * self.search_radius + 1;
+        if self.index1 >= span.pow(self.dim.dim as u32) {
+            return Err("index1 exceeds the number of cells searched in cellular2 noise");
+        }
+        if self.dim.version == AlgorithmVersion::V1 && self.dim.dim == 3 && self.search_radius != 1
+        {
+            return Err("search radius needs algorithm version 2 in 3d cellular2 noise");
+        }
+        Ok(())
+    }
+
     pub fn with_distance_function(&mut self, dist: CellDistanceFunction) -> &mut Cellular2Settings {
         self.distance_function = dist;
         self
@@ -241,4 +319,48 @@ impl Cellular2Settings {
         self.index1 = i;
         self
     }
+
+    pub fn with_search_radius(&mut self, radius: usize) -> &mut Cellular2Settings {
+        self.search_radius = radius;
+        self
+    }
+}
+
+/// Deserialized fields, validated before they become `Cellular2Settings`.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Cellular2Settings")]
+struct Cellular2SettingsData {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: f32,
+    index0: usize,
+    index1: usize,
+    search_radius: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Cellular2SettingsData> for Cellular2Settings {
+    type Error = &'static str;
+
+    fn try_from(data: Cellular2SettingsData) -> Result<Cellular2Settings, &'static str> {
+        let settings = Cellular2Settings {
+            dim: data.dim,
+            freq_x: data.freq_x,
+            freq_y: data.freq_y,
+            freq_z: data.freq_z,
+            distance_function: data.distance_function,
+            return_type: data.return_type,
+            jitter: data.jitter,
+            index0: data.index0,
+            index1: data.index1,
+            search_radius: data.search_radius,
+        };
+        settings.check()?;
+        Ok(settings)
+    }
 }
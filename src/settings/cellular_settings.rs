@@ -1,19 +1,35 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise};
-use crate::noise::cell_32::{cellular_2d, cellular_3d};
-use crate::noise::cell_64::{cellular_2d as cellular_2d_f64, cellular_3d as cellular_3d_f64};
+use crate::noise::cell_32::{
+    cellular_2d_lattice, cellular_2d_radius, cellular_3d_lattice, cellular_3d_radius,
+    cellular_3d_with_z_prime,
+};
+use crate::noise::cell_64::{
+    cellular_2d_lattice as cellular_2d_lattice_f64, cellular_2d_radius_with_hash_bits,
+    cellular_3d_lattice as cellular_3d_lattice_f64, cellular_3d_radius as cellular_3d_radius_f64,
+    cellular_3d_with_z_prime as cellular_3d_with_z_prime_f64,
+};
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
+pub use crate::noise::cell_lattice::CellLattice;
 pub use crate::noise::cell_return_type::CellReturnType;
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::points::{feature_points_2d, feature_points_3d, FeaturePoint, NoFeaturePoints};
+use crate::AlgorithmVersion;
+use crate::{get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise};
 
 use super::Settings;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CellularSettingsData"))]
 pub struct CellularSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -22,6 +38,11 @@ pub struct CellularSettings {
     pub distance_function: CellDistanceFunction,
     pub return_type: CellReturnType,
     pub jitter: f32,
+    /// How many cells around the sample point are searched for the nearest feature point.
+    /// The default of 1 checks the direct neighbours, which is enough for a jitter up to
+    /// about 0.5; larger jitter values need a radius of 2 or more to avoid discontinuities.
+    pub search_radius: usize,
+    pub lattice: CellLattice,
 }
 
 impl DimensionalBeing for CellularSettings {
@@ -40,6 +61,8 @@ impl Settings for CellularSettings {
             distance_function: CellDistanceFunction::Euclidean,
             return_type: CellReturnType::Distance,
             jitter: 0.25,
+            search_radius: 1,
+            lattice: CellLattice::Square,
         }
     }
 
@@ -48,6 +71,11 @@ impl Settings for CellularSettings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut CellularSettings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut CellularSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -100,6 +128,7 @@ impl Settings for CellularSettings {
     }
 
     fn generate(self) -> (Vec<f32>, f32, f32) {
+        self.validate();
         let d = self.dim.dim;
         match d {
             2 => get_2d_noise(&NoiseType::Cellular(self)),
@@ -109,10 +138,13 @@ impl Settings for CellularSettings {
     }
 
     fn validate(&self) {
-        //todo
+        if let Err(message) = self.check() {
+            panic!("{}", message);
+        }
     }
 
     fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        self.validate();
         let d = self.dim.dim;
         let mut new_self = self;
         new_self.dim.min = min;
@@ -134,27 +166,64 @@ impl<S: Simd> Sample32<S> for CellularSettings {
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
-        cellular_2d::<S>(
-            x,
-            y,
-            self.distance_function,
-            self.return_type,
-            S::Vf32::set1(self.jitter),
-            self.dim.seed,
-        )
+        match self.lattice {
+            CellLattice::Square => cellular_2d_radius::<S>(
+                x,
+                y,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.search_radius,
+                self.dim.seed,
+            ),
+            lattice => cellular_2d_lattice::<S>(
+                x,
+                y,
+                lattice,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.search_radius,
+                self.dim.seed,
+            ),
+        }
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        cellular_3d::<S>(
-            x,
-            y,
-            z,
-            self.distance_function,
-            self.return_type,
-            S::Vf32::set1(self.jitter),
-            self.dim.seed,
-        )
+        match (self.lattice, self.search_radius) {
+            (CellLattice::Square, 1) => cellular_3d_with_z_prime::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.dim.seed,
+                self.dim.version.z_prime_32(),
+            ),
+            (CellLattice::Square, _) => cellular_3d_radius::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.search_radius,
+                self.dim.seed,
+            ),
+            (lattice, _) => cellular_3d_lattice::<S>(
+                x,
+                y,
+                z,
+                lattice,
+                self.distance_function,
+                self.return_type,
+                S::Vf32::set1(self.jitter),
+                self.search_radius,
+                self.dim.seed,
+            ),
+        }
     }
 
     #[inline(always)]
@@ -173,27 +242,66 @@ impl<S: Simd> Sample64<S> for CellularSettings {
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        cellular_2d_f64::<S>(
-            x,
-            y,
-            self.distance_function,
-            self.return_type,
-            S::Vf64::set1(self.jitter.into()),
-            self.dim.seed.into(),
-        )
+        match self.lattice {
+            CellLattice::Square => cellular_2d_radius_with_hash_bits::<S>(
+                x,
+                y,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.search_radius,
+                self.dim.seed.into(),
+                self.dim.version.low_hash_bits(),
+            ),
+            lattice => cellular_2d_lattice_f64::<S>(
+                x,
+                y,
+                lattice,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.search_radius,
+                self.dim.seed.into(),
+            ),
+        }
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        cellular_3d_f64::<S>(
-            x,
-            y,
-            z,
-            self.distance_function,
-            self.return_type,
-            S::Vf64::set1(self.jitter.into()),
-            self.dim.seed.into(),
-        )
+        match (self.lattice, self.search_radius) {
+            (CellLattice::Square, 1) => cellular_3d_with_z_prime_f64::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.dim.seed.into(),
+                self.dim.version.z_prime_64(),
+                self.dim.version.low_hash_bits(),
+            ),
+            (CellLattice::Square, _) => cellular_3d_radius_f64::<S>(
+                x,
+                y,
+                z,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.search_radius,
+                self.dim.seed.into(),
+            ),
+            (lattice, _) => cellular_3d_lattice_f64::<S>(
+                x,
+                y,
+                z,
+                lattice,
+                self.distance_function,
+                self.return_type,
+                S::Vf64::set1(self.jitter.into()),
+                self.search_radius,
+                self.dim.seed.into(),
+            ),
+        }
     }
 
     #[inline(always)]
@@ -204,6 +312,25 @@ impl<S: Simd> Sample64<S> for CellularSettings {
 }
 
 impl CellularSettings {
+    pub(crate) fn check(&self) -> Result<(), &'static str> {
+        if self.search_radius == 0 {
+            return Err("invalid search radius in cellular noise");
+        }
+        if !self.jitter.is_finite() {
+            return Err("invalid jitter in cellular noise");
+        }
+        if !self.lattice.supports_dimension(self.dim.dim) {
+            return Err("lattice not supported for this dimension in cellular noise");
+        }
+        if self.dim.version == AlgorithmVersion::V1
+            && self.dim.dim == 3
+            && !matches!((self.lattice, self.search_radius), (CellLattice::Square, 1))
+        {
+            return Err("search radius and lattice need algorithm version 2 in 3d cellular noise");
+        }
+        Ok(())
+    }
+
     pub fn with_distance_function(&mut self, dist: CellDistanceFunction) -> &mut CellularSettings {
         self.distance_function = dist;
         self
@@ -218,4 +345,120 @@ impl CellularSettings {
         self.jitter = jitter;
         self
     }
+
+    pub fn with_search_radius(&mut self, radius: usize) -> &mut CellularSettings {
+        self.search_radius = radius;
+        self
+    }
+
+    pub fn with_lattice(&mut self, lattice: CellLattice) -> &mut CellularSettings {
+        self.lattice = lattice;
+        self
+    }
+
+    /// The feature points inside the area covered by `generate`, in sample coordinates: a
+    /// point at `[x, y]` lies at column `x` and row `y` of the generated noise. With a
+    /// frequency of 0 every sample lies at the same point, so there are none.
+    pub fn feature_points_2d(&self) -> Vec<FeaturePoint<2>> {
+        let freq = [self.freq_x, self.freq_y];
+        if !freq.iter().all(|f| f.is_normal()) {
+            return Vec::new();
+        }
+        let start = [self.dim.x, self.dim.y];
+        let size = [self.dim.width as f32, self.dim.height as f32];
+        let (min, max) = noise_space_bounds(freq, start, size);
+        let mut points = feature_points_2d(min, max, self.lattice, self.jitter, self.dim.seed);
+        to_sample_coordinates(&mut points, freq, start);
+        points
+    }
+
+    /// The feature points inside the volume covered by `generate`, in sample coordinates.
+    ///
+    /// Returns `NoFeaturePoints` for the square lattice with a search radius of 1, whose
+    /// kernel has no fixed feature points, see `feature_points_3d`. Use a search radius of 2
+    /// or more instead.
+    pub fn feature_points_3d(&self) -> Result<Vec<FeaturePoint<3>>, NoFeaturePoints> {
+        if matches!(self.lattice, CellLattice::Square) && self.search_radius == 1 {
+            return Err(NoFeaturePoints);
+        }
+        let freq = [self.freq_x, self.freq_y, self.freq_z];
+        if !freq.iter().all(|f| f.is_normal()) {
+            return Ok(Vec::new());
+        }
+        let start = [self.dim.x, self.dim.y, self.dim.z];
+        let size = [
+            self.dim.width as f32,
+            self.dim.height as f32,
+            self.dim.depth as f32,
+        ];
+        let (min, max) = noise_space_bounds(freq, start, size);
+        let mut points = feature_points_3d(min, max, self.lattice, self.jitter, self.dim.seed);
+        to_sample_coordinates(&mut points, freq, start);
+        Ok(points)
+    }
+}
+
+fn noise_space_bounds<const N: usize>(
+    freq: [f32; N],
+    start: [f32; N],
+    size: [f32; N],
+) -> ([f32; N], [f32; N]) {
+    let mut min = [0.0; N];
+    let mut max = [0.0; N];
+    for i in 0..N {
+        let a = start[i] * freq[i];
+        let b = (start[i] + size[i]) * freq[i];
+        min[i] = a.min(b);
+        max[i] = a.max(b);
+    }
+    (min, max)
+}
+
+fn to_sample_coordinates<const N: usize>(
+    points: &mut [FeaturePoint<N>],
+    freq: [f32; N],
+    start: [f32; N],
+) {
+    for point in points.iter_mut() {
+        for i in 0..N {
+            point.position[i] = point.position[i] / freq[i] - start[i];
+        }
+    }
+}
+
+/// Deserialized fields, validated before they become `CellularSettings`.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "CellularSettings")]
+struct CellularSettingsData {
+    dim: NoiseDimensions,
+    freq_x: f32,
+    freq_y: f32,
+    freq_z: f32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: f32,
+    search_radius: usize,
+    lattice: CellLattice,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CellularSettingsData> for CellularSettings {
+    type Error = &'static str;
+
+    fn try_from(data: CellularSettingsData) -> Result<CellularSettings, &'static str> {
+        let settings = CellularSettings {
+            dim: data.dim,
+            freq_x: data.freq_x,
+            freq_y: data.freq_y,
+            freq_z: data.freq_z,
+            distance_function: data.distance_function,
+            return_type: data.return_type,
+            jitter: data.jitter,
+            search_radius: data.search_radius,
+            lattice: data.lattice,
+        };
+        settings.check()?;
+        Ok(settings)
+    }
 }
@@ -1,19 +1,27 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise};
-use crate::noise::fbm_32::{fbm_1d, fbm_2d, fbm_3d, fbm_4d};
+use crate::noise::fbm_32::{fbm_1d, fbm_2d, fbm_3d_with_z_prime, fbm_4d};
 use crate::noise::fbm_64::{
-    fbm_1d as fbm_1d_f64, fbm_2d as fbm_2d_f64, fbm_3d as fbm_3d_f64, fbm_4d as fbm_4d_f64,
+    fbm_1d_with_masks as fbm_1d_with_masks_f64, fbm_2d_with_masks as fbm_2d_with_masks_f64,
+    fbm_3d_with_z_prime as fbm_3d_with_z_prime_f64, fbm_4d as fbm_4d_f64,
 };
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{
+    get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise,
+    get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise,
+};
 
 use super::{Settings, SimplexSettings};
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FbmSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -49,6 +57,11 @@ impl Settings for FbmSettings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut FbmSettings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut FbmSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -178,7 +191,7 @@ impl<S: Simd> Sample32<S> for FbmSettings {
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        fbm_3d::<S>(
+        fbm_3d_with_z_prime::<S>(
             x,
             y,
             z,
@@ -186,6 +199,7 @@ impl<S: Simd> Sample32<S> for FbmSettings {
             S::Vf32::set1(self.gain),
             self.octaves,
             self.dim.seed,
+            self.dim.version.z_prime_32(),
         )
     }
 
@@ -207,30 +221,32 @@ impl<S: Simd> Sample32<S> for FbmSettings {
 impl<S: Simd> Sample64<S> for FbmSettings {
     #[inline(always)]
     fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
-        fbm_1d_f64::<S>(
+        fbm_1d_with_masks_f64::<S>(
             x,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        fbm_2d_f64::<S>(
+        fbm_2d_with_masks_f64::<S>(
             x,
             y,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        fbm_3d_f64::<S>(
+        fbm_3d_with_z_prime_f64::<S>(
             x,
             y,
             z,
@@ -238,6 +254,7 @@ impl<S: Simd> Sample64<S> for FbmSettings {
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.z_prime_64(),
         )
     }
 
@@ -1,20 +1,28 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise};
-use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d_with_z_prime, simplex_4d};
 use crate::noise::simplex_64::{
-    simplex_1d as simplex_1d_f64, simplex_2d as simplex_2d_f64, simplex_3d as simplex_3d_f64,
-    simplex_4d as simplex_4d_f64,
+    simplex_1d_with_masks as simplex_1d_with_masks_f64,
+    simplex_2d_with_masks as simplex_2d_with_masks_f64,
+    simplex_3d_with_z_prime as simplex_3d_with_z_prime_f64, simplex_4d as simplex_4d_f64,
 };
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{
+    get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise,
+    get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise,
+};
 
 use crate::settings::Settings;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GradientSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -44,6 +52,11 @@ impl Settings for GradientSettings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut GradientSettings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut GradientSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -143,7 +156,7 @@ impl<S: Simd> Sample32<S> for GradientSettings {
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        simplex_3d::<S>(x, y, z, self.dim.seed)
+        simplex_3d_with_z_prime::<S>(x, y, z, self.dim.seed, self.dim.version.z_prime_32())
     }
 
     #[inline(always)]
@@ -155,17 +168,23 @@ impl<S: Simd> Sample32<S> for GradientSettings {
 impl<S: Simd> Sample64<S> for GradientSettings {
     #[inline(always)]
     fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
-        simplex_1d_f64::<S>(x, self.dim.seed.into())
+        simplex_1d_with_masks_f64::<S>(x, self.dim.seed.into(), self.dim.version.bitcast_masks())
     }
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        simplex_2d_f64::<S>(x, y, self.dim.seed.into())
+        simplex_2d_with_masks_f64::<S>(x, y, self.dim.seed.into(), self.dim.version.bitcast_masks())
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        simplex_3d_f64::<S>(x, y, z, self.dim.seed.into())
+        simplex_3d_with_z_prime_f64::<S>(
+            x,
+            y,
+            z,
+            self.dim.seed.into(),
+            self.dim.version.z_prime_64(),
+        )
     }
 
     #[inline(always)]
@@ -1,5 +1,7 @@
 pub use crate::noise_dimensions::NoiseDimensions;
 pub use crate::noise_type::NoiseType;
+use crate::quantize::{get_i16_noise, get_u16_noise, get_u8_noise};
+use crate::AlgorithmVersion;
 
 pub trait Settings {
     fn default(dim: NoiseDimensions) -> Self;
@@ -8,6 +10,8 @@ pub trait Settings {
     fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut Self;
     fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut Self;
     fn with_freq_4d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32, freq_w: f32) -> &mut Self;
+    /// Generate with an older `AlgorithmVersion`, to reproduce noise made with it
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut Self;
 
     fn get_freq_x(&self) -> f32;
     fn get_freq_y(&self) -> f32;
@@ -25,6 +29,31 @@ pub trait Settings {
 
     /// Generate a chunk of noise with values scaled from min to max
     fn generate_scaled(self, min: f32, max: f32) -> Vec<f32>;
+
+    /// Generate a chunk of noise scaled from min to max and rounded to bytes, without
+    /// keeping the floats around. The noise is sampled twice, see `NoiseNode::generate_u8`.
+    fn generate_u8(self, min: u8, max: u8) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        get_u8_noise(&self.wrap(), min, max)
+    }
+
+    /// Generate a chunk of noise scaled from min to max and rounded to `u16`, for heightmaps
+    fn generate_u16(self, min: u16, max: u16) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        get_u16_noise(&self.wrap(), min, max)
+    }
+
+    /// Generate a chunk of noise scaled from min to max and rounded to `i16`
+    fn generate_i16(self, min: i16, max: i16) -> Vec<i16>
+    where
+        Self: Sized,
+    {
+        get_i16_noise(&self.wrap(), min, max)
+    }
 }
 
 pub trait SimplexSettings {
@@ -39,6 +68,7 @@ mod fbm_settings;
 mod gradient_settings;
 mod ridge_settings;
 mod turbulence_settings;
+mod white_settings;
 
 pub use cellular2_settings::Cellular2Settings;
 pub use cellular_settings::CellularSettings;
@@ -46,3 +76,4 @@ pub use fbm_settings::FbmSettings;
 pub use gradient_settings::GradientSettings;
 pub use ridge_settings::RidgeSettings;
 pub use turbulence_settings::TurbulenceSettings;
+pub use white_settings::WhiteNoiseSettings;
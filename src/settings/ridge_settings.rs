@@ -1,20 +1,27 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise};
-use crate::noise::ridge_32::{ridge_1d, ridge_2d, ridge_3d, ridge_4d};
+use crate::noise::ridge_32::{ridge_1d, ridge_2d, ridge_3d_with_z_prime, ridge_4d};
 use crate::noise::ridge_64::{
-    ridge_1d as ridge_1d_f64, ridge_2d as ridge_2d_f64, ridge_3d as ridge_3d_f64,
-    ridge_4d as ridge_4d_f64,
+    ridge_1d_with_masks as ridge_1d_with_masks_f64, ridge_2d_with_masks as ridge_2d_with_masks_f64,
+    ridge_3d_with_z_prime as ridge_3d_with_z_prime_f64, ridge_4d as ridge_4d_f64,
 };
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{
+    get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise,
+    get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise,
+};
 
 use super::{Settings, SimplexSettings};
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RidgeSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -51,6 +58,11 @@ impl Settings for RidgeSettings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut RidgeSettings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut RidgeSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -180,7 +192,7 @@ impl<S: Simd> Sample32<S> for RidgeSettings {
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        ridge_3d::<S>(
+        ridge_3d_with_z_prime::<S>(
             x,
             y,
             z,
@@ -188,6 +200,7 @@ impl<S: Simd> Sample32<S> for RidgeSettings {
             S::Vf32::set1(self.gain),
             self.octaves,
             self.dim.seed,
+            self.dim.version.z_prime_32(),
         )
     }
 
@@ -209,30 +222,32 @@ impl<S: Simd> Sample32<S> for RidgeSettings {
 impl<S: Simd> Sample64<S> for RidgeSettings {
     #[inline(always)]
     fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
-        ridge_1d_f64::<S>(
+        ridge_1d_with_masks_f64::<S>(
             x,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        ridge_2d_f64::<S>(
+        ridge_2d_with_masks_f64::<S>(
             x,
             y,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        ridge_3d_f64::<S>(
+        ridge_3d_with_z_prime_f64::<S>(
             x,
             y,
             z,
@@ -240,6 +255,7 @@ impl<S: Simd> Sample64<S> for RidgeSettings {
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.z_prime_64(),
         )
     }
 
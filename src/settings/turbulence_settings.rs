@@ -1,20 +1,31 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use simdeez::prelude::*;
 
 use crate::dimensional_being::DimensionalBeing;
-use crate::{get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise, get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise};
-use crate::noise::turbulence_32::{turbulence_1d, turbulence_2d, turbulence_3d, turbulence_4d};
+use crate::noise::turbulence_32::{
+    turbulence_1d, turbulence_2d, turbulence_3d_with_z_prime, turbulence_4d,
+};
 use crate::noise::turbulence_64::{
-    turbulence_1d as turbulence_1d_f64, turbulence_2d as turbulence_2d_f64,
-    turbulence_3d as turbulence_3d_f64, turbulence_4d as turbulence_4d_f64,
+    turbulence_1d_with_masks as turbulence_1d_with_masks_f64,
+    turbulence_2d_with_masks as turbulence_2d_with_masks_f64,
+    turbulence_3d_with_z_prime as turbulence_3d_with_z_prime_f64,
+    turbulence_4d as turbulence_4d_f64,
 };
 pub use crate::noise_dimensions::NoiseDimensions;
 use crate::noise_helpers_32::Sample32;
 use crate::noise_helpers_64::Sample64;
 pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{
+    get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise,
+    get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise,
+};
 
 use super::{Settings, SimplexSettings};
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TurbulenceSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -51,6 +62,11 @@ impl Settings for TurbulenceSettings {
         self
     }
 
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut TurbulenceSettings {
+        self.dim.version = version;
+        self
+    }
+
     fn with_freq(&mut self, freq: f32) -> &mut TurbulenceSettings {
         self.freq_x = freq;
         self.freq_y = freq;
@@ -180,7 +196,7 @@ impl<S: Simd> Sample32<S> for TurbulenceSettings {
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
-        turbulence_3d::<S>(
+        turbulence_3d_with_z_prime::<S>(
             x,
             y,
             z,
@@ -188,6 +204,7 @@ impl<S: Simd> Sample32<S> for TurbulenceSettings {
             S::Vf32::set1(self.gain),
             self.octaves,
             self.dim.seed,
+            self.dim.version.z_prime_32(),
         )
     }
 
@@ -209,30 +226,32 @@ impl<S: Simd> Sample32<S> for TurbulenceSettings {
 impl<S: Simd> Sample64<S> for TurbulenceSettings {
     #[inline(always)]
     fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
-        turbulence_1d_f64::<S>(
+        turbulence_1d_with_masks_f64::<S>(
             x,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
-        turbulence_2d_f64::<S>(
+        turbulence_2d_with_masks_f64::<S>(
             x,
             y,
             S::Vf64::set1(self.lacunarity.into()),
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.bitcast_masks(),
         )
     }
 
     #[inline(always)]
     fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
-        turbulence_3d_f64::<S>(
+        turbulence_3d_with_z_prime_f64::<S>(
             x,
             y,
             z,
@@ -240,6 +259,7 @@ impl<S: Simd> Sample64<S> for TurbulenceSettings {
             S::Vf64::set1(self.gain.into()),
             self.octaves,
             self.dim.seed.into(),
+            self.dim.version.z_prime_64(),
         )
     }
 
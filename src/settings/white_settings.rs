@@ -0,0 +1,207 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use simdeez::prelude::*;
+
+use crate::dimensional_being::DimensionalBeing;
+use crate::noise::white_32::{white_1d, white_2d, white_3d, white_4d};
+use crate::noise::white_64::{
+    white_1d as white_1d_f64, white_2d as white_2d_f64, white_3d as white_3d_f64,
+    white_4d as white_4d_f64,
+};
+pub use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_helpers_32::Sample32;
+use crate::noise_helpers_64::Sample64;
+pub use crate::noise_type::NoiseType;
+use crate::AlgorithmVersion;
+use crate::{
+    get_1d_noise, get_1d_scaled_noise, get_2d_noise, get_2d_scaled_noise, get_3d_noise,
+    get_3d_scaled_noise, get_4d_noise, get_4d_scaled_noise,
+};
+
+use crate::settings::Settings;
+
+/// Uncorrelated noise: every integer cell of the scaled coordinates gets its own random
+/// value in [-1, 1]. With the default frequency of 1.0 that is one value per sample.
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WhiteNoiseSettings {
+    dim: NoiseDimensions,
+    pub freq_x: f32,
+    pub freq_y: f32,
+    pub freq_z: f32,
+    pub freq_w: f32,
+}
+
+impl DimensionalBeing for WhiteNoiseSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+}
+
+impl Settings for WhiteNoiseSettings {
+    fn default(dim: NoiseDimensions) -> WhiteNoiseSettings {
+        WhiteNoiseSettings {
+            dim,
+            freq_x: 1.0,
+            freq_y: 1.0,
+            freq_z: 1.0,
+            freq_w: 1.0,
+        }
+    }
+    fn with_seed(&mut self, seed: i32) -> &mut WhiteNoiseSettings {
+        self.dim.seed = seed;
+        self
+    }
+
+    fn with_version(&mut self, version: AlgorithmVersion) -> &mut WhiteNoiseSettings {
+        self.dim.version = version;
+        self
+    }
+
+    fn with_freq(&mut self, freq: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    fn get_freq_x(&self) -> f32 {
+        self.freq_x
+    }
+
+    fn get_freq_y(&self) -> f32 {
+        self.freq_y
+    }
+
+    fn get_freq_z(&self) -> f32 {
+        self.freq_z
+    }
+
+    fn get_freq_w(&self) -> f32 {
+        self.freq_w
+    }
+
+    fn wrap(self) -> NoiseType {
+        self.validate();
+        NoiseType::White(self)
+    }
+
+    fn validate(&self) {
+        if let Err(message) = self.check() {
+            panic!("{}", message);
+        }
+    }
+
+    fn generate(self) -> (Vec<f32>, f32, f32) {
+        self.validate();
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise(&NoiseType::White(self)),
+            2 => get_2d_noise(&NoiseType::White(self)),
+            3 => get_3d_noise(&NoiseType::White(self)),
+            4 => get_4d_noise(&NoiseType::White(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        self.validate();
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise(&NoiseType::White(new_self)),
+            2 => get_2d_scaled_noise(&NoiseType::White(new_self)),
+            3 => get_3d_scaled_noise(&NoiseType::White(new_self)),
+            4 => get_4d_scaled_noise(&NoiseType::White(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+impl<S: Simd> Sample32<S> for WhiteNoiseSettings {
+    #[inline(always)]
+    fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+        white_1d::<S>(x, self.dim.seed)
+    }
+
+    #[inline(always)]
+    fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        white_2d::<S>(x, y, self.dim.seed)
+    }
+
+    #[inline(always)]
+    fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        white_3d::<S>(x, y, z, self.dim.seed)
+    }
+
+    #[inline(always)]
+    fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+        white_4d::<S>(x, y, z, w, self.dim.seed)
+    }
+}
+
+impl<S: Simd> Sample64<S> for WhiteNoiseSettings {
+    #[inline(always)]
+    fn sample_1d(&self, x: S::Vf64) -> S::Vf64 {
+        white_1d_f64::<S>(x, self.dim.seed.into())
+    }
+
+    #[inline(always)]
+    fn sample_2d(&self, x: S::Vf64, y: S::Vf64) -> S::Vf64 {
+        white_2d_f64::<S>(x, y, self.dim.seed.into())
+    }
+
+    #[inline(always)]
+    fn sample_3d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64) -> S::Vf64 {
+        white_3d_f64::<S>(x, y, z, self.dim.seed.into())
+    }
+
+    #[inline(always)]
+    fn sample_4d(&self, x: S::Vf64, y: S::Vf64, z: S::Vf64, w: S::Vf64) -> S::Vf64 {
+        white_4d_f64::<S>(x, y, z, w, self.dim.seed.into())
+    }
+}
+
+impl WhiteNoiseSettings {
+    /// The validation of `validate`, as an error instead of a panic.
+    pub(crate) fn check(&self) -> Result<(), &'static str> {
+        if !(1..=4).contains(&self.dim.dim) {
+            return Err("invalid dimension in white noise");
+        }
+        let freq = [self.freq_x, self.freq_y, self.freq_z, self.freq_w];
+        if freq[..self.dim.dim].iter().any(|f| !f.is_finite()) {
+            return Err("invalid frequency in white noise");
+        }
+        Ok(())
+    }
+}
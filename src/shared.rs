@@ -1,6 +1,6 @@
 use simdeez::prelude::*;
 
-use crate::{dimensional_being::DimensionalBeing, NoiseType};
+use crate::{dimensional_being::DimensionalBeing, Modifier, NoiseType};
 
 #[inline(always)]
 pub unsafe fn scale_noise<S: Simd>(
@@ -31,9 +31,77 @@ pub unsafe fn scale_noise<S: Simd>(
     }
 }
 
-pub(crate) unsafe fn get_scaled_noise<S: Simd, F: Fn(&NoiseType) -> (Vec<f32>, f32, f32)>(noise_type: &NoiseType, noise_fn: F) -> Vec<f32> {
+backend_runtime_generate!(
+    /// Runtime dispatched `scale_noise` for buffers that were not generated from a
+    /// `NoiseType`.
+    pub(crate) fn scale_buffer(
+        data: Vec<f32>,
+        scale_min: f32,
+        scale_max: f32,
+        min: f32,
+        max: f32,
+    ) -> Vec<f32> {
+        let mut data = data;
+        unsafe { scale_noise::<S>(scale_min, scale_max, min, max, &mut data) };
+        data
+    }
+);
+
+#[inline(always)]
+pub(crate) unsafe fn modify_noise<S: Simd>(modifiers: &[Modifier], data: &mut [f32]) {
+    let vector_width = S::Vf32::WIDTH;
+    let apply = |v: S::Vf32| modifiers.iter().fold(v, |v, m| m.apply_f32::<S>(v));
+    let mut i = 0;
+    if data.len() >= vector_width {
+        while i <= data.len() - vector_width {
+            let value = apply(S::Vf32::load_from_ptr_unaligned(&data[i]));
+            value.copy_to_ptr_unaligned(data.get_unchecked_mut(i));
+            i += vector_width;
+        }
+    }
+    // The remainder goes through one partly filled vector
+    let tail = &mut data[i..];
+    if !tail.is_empty() {
+        let mut value = S::Vf32::zeroes();
+        for (k, v) in tail.iter().enumerate() {
+            value[k] = *v;
+        }
+        let value = apply(value);
+        for (k, v) in tail.iter_mut().enumerate() {
+            *v = value[k];
+        }
+    }
+}
+
+pub(crate) unsafe fn get_scaled_noise<S: Simd, F: Fn(&NoiseType) -> (Vec<f32>, f32, f32)>(
+    noise_type: &NoiseType,
+    noise_fn: F,
+) -> Vec<f32> {
     let (mut noise, min, max) = noise_fn(noise_type);
     let dim = noise_type.get_dimensions();
     scale_noise::<S>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Small seeded random number generator for the algorithms that are not hash based, so
+/// their results only depend on the seed.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: i32) -> SplitMix64 {
+        SplitMix64(seed as u32 as u64)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in [0, 1).
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,205 @@
+use simdnoise::{AlgorithmVersion, NoiseBuilder, NoiseType, Settings, SimplexSettings};
+
+// The golden files are generated without the `deterministic` feature, which changes the output
+#[cfg(not(feature = "deterministic"))]
+mod helpers;
+#[cfg(not(feature = "deterministic"))]
+use helpers::{read_from_file_f32, /*save_to_file_f32, */ BIN_PATH};
+
+const NAMES: [&str; 6] = [
+    "cellular",
+    "cellular2",
+    "ridge",
+    "turbulence",
+    "fbm",
+    "gradient",
+];
+
+/// The settings of the `nooffset` 3d noisebuilder tests.
+fn noise_3d(name: &str, version: AlgorithmVersion) -> Vec<f32> {
+    let (width, height, depth) = (64, 32, 16);
+    let noise = match name {
+        "cellular" => NoiseBuilder::cellular_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_version(version)
+            .generate(),
+        "cellular2" => NoiseBuilder::cellular2_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_version(version)
+            .generate(),
+        "ridge" => NoiseBuilder::ridge_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_octaves(5)
+            .with_gain(2.0)
+            .with_lacunarity(0.5)
+            .with_version(version)
+            .generate(),
+        "turbulence" => NoiseBuilder::turbulence_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_octaves(5)
+            .with_gain(2.0)
+            .with_lacunarity(0.5)
+            .with_version(version)
+            .generate(),
+        "fbm" => NoiseBuilder::fbm_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_octaves(5)
+            .with_gain(2.0)
+            .with_lacunarity(0.5)
+            .with_version(version)
+            .generate(),
+        "gradient" => NoiseBuilder::gradient_3d(width, height, depth)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_seed(1337)
+            .with_version(version)
+            .generate(),
+        _ => unreachable!(),
+    };
+    noise.0
+}
+
+#[cfg(not(feature = "deterministic"))]
+fn file_name(name: &str, version: &str) -> String {
+    format!(
+        "{}/{}_{}_{}_{}_{}{}.bin",
+        BIN_PATH, "noisebuilder", name, "nooffset", "32", "3d", version
+    )
+}
+
+// The v1 files are the output of the 3.1.7 sources with `Z_PRIME_32` and `Z_PRIME_64` set
+// back to 6791, not of the V1 code paths in this crate
+#[test]
+#[cfg(not(feature = "deterministic"))]
+fn test_algorithm_version_v1() {
+    for name in NAMES.iter() {
+        let noise = noise_3d(name, AlgorithmVersion::V1);
+        let file_name = file_name(name, "_v1");
+
+        //save_to_file_f32(&file_name, noise.as_slice()).unwrap();
+        let expected = read_from_file_f32(&file_name).unwrap();
+        assert!(expected == noise, "{}", name);
+    }
+}
+
+#[test]
+#[cfg(not(feature = "deterministic"))]
+fn test_algorithm_version_v2() {
+    for name in NAMES.iter() {
+        let noise = noise_3d(name, AlgorithmVersion::V2);
+        let expected = read_from_file_f32(&file_name(name, "")).unwrap();
+        assert!(expected == noise, "{}", name);
+    }
+}
+
+#[test]
+fn test_algorithm_version_3d_changed() {
+    for name in NAMES.iter() {
+        let v1 = noise_3d(name, AlgorithmVersion::V1);
+        assert!(v1 != noise_3d(name, AlgorithmVersion::V2), "{}", name);
+    }
+}
+
+#[test]
+fn test_algorithm_version_2d_unchanged() {
+    let generate = |version| {
+        NoiseBuilder::fbm_2d(64, 32)
+            .with_freq(0.05)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    assert_eq!(
+        generate(AlgorithmVersion::V1),
+        generate(AlgorithmVersion::V2)
+    );
+}
+
+#[test]
+fn test_algorithm_version_tag() {
+    assert_eq!(AlgorithmVersion::default(), AlgorithmVersion::LATEST);
+    assert_eq!(
+        NoiseBuilder::ridge_3d(4, 4, 4).wrap().version(),
+        AlgorithmVersion::LATEST
+    );
+    let noise_type = NoiseBuilder::gradient_3d(4, 4, 4)
+        .with_version(AlgorithmVersion::V1)
+        .wrap();
+    assert_eq!(noise_type.version(), AlgorithmVersion::V1);
+    for number in 1..=3 {
+        let version = AlgorithmVersion::from_number(number).unwrap();
+        assert_eq!(version.number(), number);
+    }
+    assert_eq!(AlgorithmVersion::from_number(0), None);
+}
+
+#[test]
+fn test_algorithm_version_preset() {
+    let noise_type = NoiseBuilder::turbulence_3d(8, 8, 8)
+        .with_version(AlgorithmVersion::V1)
+        .wrap();
+    let preset = noise_type.to_preset();
+    assert!(preset.contains("version = 1"), "{}", preset);
+    let back = NoiseType::from_preset(&preset).unwrap();
+    assert_eq!(back.version(), AlgorithmVersion::V1);
+
+    let untagged = NoiseType::from_preset("type = fbm\nsize = 8 8 8").unwrap();
+    assert_eq!(untagged.version(), AlgorithmVersion::LATEST);
+
+    let error = NoiseType::from_preset("type = fbm\nsize = 8\nversion = 9")
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "line 3: unknown version `9`");
+}
+
+#[test]
+#[should_panic(expected = "need algorithm version 2")]
+fn test_algorithm_version_v1_cellular_radius() {
+    NoiseBuilder::cellular_3d(8, 8, 8)
+        .with_search_radius(2)
+        .with_version(AlgorithmVersion::V1)
+        .generate();
+}
+
+#[test]
+#[should_panic(expected = "needs algorithm version 2")]
+fn test_algorithm_version_v1_cellular2_radius() {
+    NoiseBuilder::cellular2_3d(8, 8, 8)
+        .with_search_radius(2)
+        .with_version(AlgorithmVersion::V1)
+        .generate();
+}
+
+/// Generates f64 2d noise with the instruction set it is run with.
+#[cfg(all(target_arch = "x86_64", not(feature = "deterministic")))]
+struct Generate64;
+
+#[cfg(all(target_arch = "x86_64", not(feature = "deterministic")))]
+impl simdeez::prelude::__SimdRunner<NoiseType, Vec<f64>> for Generate64 {
+    unsafe fn run<S: simdeez::Simd>(noise_type: NoiseType) -> Vec<f64> {
+        simdnoise::intrinsics::scalar::get_2d_noise_64::<S>(&noise_type).0
+    }
+}
+
+#[test]
+#[cfg(all(target_arch = "x86_64", not(feature = "deterministic")))]
+fn test_algorithm_version_f64_masks() {
+    use simdeez::prelude::{__run_simd_invoke_scalar, __run_simd_invoke_sse2};
+
+    // SSE2 blends bit by bit, which before V3 mixed the gradients of the converted masks
+    let noise = |version| {
+        NoiseBuilder::fbm_2d(37, 23)
+            .with_freq(0.07)
+            .with_version(version)
+            .wrap()
+    };
+    let sse2 = |version| unsafe { __run_simd_invoke_sse2::<Generate64, _, _>(noise(version)) };
+    let scalar = |version| __run_simd_invoke_scalar::<Generate64, _, _>(noise(version));
+    assert_eq!(sse2(AlgorithmVersion::V3), scalar(AlgorithmVersion::V3));
+    assert!(sse2(AlgorithmVersion::V2) != scalar(AlgorithmVersion::V3));
+    assert_eq!(scalar(AlgorithmVersion::V2), scalar(AlgorithmVersion::V3));
+}
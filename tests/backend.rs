@@ -0,0 +1,88 @@
+use simdnoise::intrinsics::portable::{self, Portable};
+use simdnoise::{
+    active_backend, force_backend, get_2d_noise, Backend, NoiseBuilder, NoiseNode, Settings,
+    UnsupportedBackend,
+};
+
+// Forcing a backend is global, so everything that forces one is in this single test
+#[test]
+fn test_backend_force() {
+    assert_eq!(active_backend(), Backend::detect());
+    let noise_type = NoiseBuilder::cellular_2d(37, 23).with_freq(0.1).wrap();
+
+    force_backend(Some(Backend::Scalar)).unwrap();
+    assert_eq!(active_backend(), Backend::Scalar);
+    let scalar = Backend::Scalar.get_2d_noise(&noise_type).unwrap();
+    assert_eq!(get_2d_noise(&noise_type).0, scalar.0);
+
+    force_backend(Some(Backend::Portable)).unwrap();
+    assert_eq!(active_backend(), Backend::Portable);
+    assert_eq!(
+        get_2d_noise(&noise_type).0,
+        Backend::Portable.get_2d_noise(&noise_type).unwrap().0
+    );
+
+    // A graph evaluates every node inside the backend instead of falling back to scalar code
+    let fbm = NoiseBuilder::fbm_2d(37, 23).with_freq(0.05).wrap();
+    for backend in Backend::ALL.iter().filter(|b| b.is_supported()) {
+        force_backend(Some(*backend)).unwrap();
+        let node = NoiseNode::from(fbm) * NoiseNode::Constant(1.0);
+        assert_eq!(
+            node.generate().0,
+            backend.get_2d_noise(&fbm).unwrap().0,
+            "{:?}",
+            backend
+        );
+    }
+
+    force_backend(None).unwrap();
+    assert_eq!(active_backend(), Backend::detect());
+
+    if let Some(unsupported) = Backend::ALL.iter().find(|b| !b.is_supported()) {
+        assert_eq!(
+            force_backend(Some(*unsupported)),
+            Err(UnsupportedBackend(*unsupported))
+        );
+        assert_eq!(active_backend(), Backend::detect());
+    }
+}
+
+#[test]
+fn test_backend_get_noise() {
+    let noise_type = NoiseBuilder::fbm_3d(17, 13, 11).with_freq(0.07).wrap();
+    let expected = unsafe { portable::get_3d_noise::<Portable>(&noise_type) };
+    assert_eq!(
+        Backend::Portable.get_3d_noise(&noise_type).unwrap().0,
+        expected.0
+    );
+    let noise_type_2d = NoiseBuilder::fbm_2d(37, 23).with_freq(0.07).wrap();
+    let expected = unsafe { portable::get_2d_noise_64::<Portable>(&noise_type_2d) };
+    assert_eq!(
+        Backend::Portable.get_2d_noise_64(&noise_type_2d).unwrap().0,
+        expected.0
+    );
+
+    for backend in Backend::ALL.iter() {
+        let noise = backend.get_3d_noise(&noise_type);
+        if backend.is_supported() {
+            assert_eq!(noise.unwrap().0.len(), 17 * 13 * 11);
+        } else {
+            let error = noise.err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("the cpu does not support the {} backend", backend)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_backend_names() {
+    for backend in Backend::ALL.iter() {
+        assert_eq!(Backend::from_name(backend.name()), Some(*backend));
+    }
+    assert_eq!(Backend::from_name("avx512"), None);
+    assert!(Backend::detect().is_supported());
+    assert!(Backend::Scalar.is_supported());
+    assert!(Backend::Portable.is_supported());
+}
@@ -0,0 +1,119 @@
+use simdnoise::{blue_noise_2d, blue_noise_3d, NoiseBuilder, Settings};
+
+// Mean power of the 2d spectrum in the rings below and above `split`, DC excluded.
+fn radial_power(data: &[f32], width: usize, height: usize, split: f32) -> (f32, f32) {
+    let mean = data.iter().sum::<f32>() / data.len() as f32;
+    let (mut low, mut low_count, mut high, mut high_count) = (0.0, 0, 0.0, 0);
+    for v in 0..height {
+        for u in 0..width {
+            let (mut re, mut im) = (0.0f32, 0.0f32);
+            for y in 0..height {
+                for x in 0..width {
+                    let phase = -2.0
+                        * std::f32::consts::PI
+                        * (u as f32 * x as f32 / width as f32
+                            + v as f32 * y as f32 / height as f32);
+                    let value = data[y * width + x] - mean;
+                    re += value * phase.cos();
+                    im += value * phase.sin();
+                }
+            }
+            let fu = u.min(width - u) as f32;
+            let fv = v.min(height - v) as f32;
+            let r = (fu * fu + fv * fv).sqrt();
+            if r == 0.0 {
+                continue;
+            }
+            if r < split {
+                low += re * re + im * im;
+                low_count += 1;
+            } else {
+                high += re * re + im * im;
+                high_count += 1;
+            }
+        }
+    }
+    (low / low_count as f32, high / high_count as f32)
+}
+
+fn threshold(data: &[f32], t: f32) -> Vec<f32> {
+    data.iter()
+        .map(|v| if *v < t { 1.0 } else { 0.0 })
+        .collect()
+}
+
+#[test]
+fn test_blue_noise_2d_ranks() {
+    let noise = blue_noise_2d(16, 8, 1);
+    assert_eq!(noise.len(), 128);
+    let mut ranks: Vec<usize> = noise.iter().map(|v| (v * 127.0).round() as usize).collect();
+    ranks.sort();
+    assert_eq!(ranks, (0..128).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_blue_noise_3d_ranks() {
+    let noise = blue_noise_3d(8, 8, 4, 1);
+    assert_eq!(noise.len(), 256);
+    let mut ranks: Vec<usize> = noise.iter().map(|v| (v * 255.0).round() as usize).collect();
+    ranks.sort();
+    assert_eq!(ranks, (0..256).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_blue_noise_seed() {
+    assert_eq!(blue_noise_2d(16, 16, 5), blue_noise_2d(16, 16, 5));
+    assert_ne!(blue_noise_2d(16, 16, 5), blue_noise_2d(16, 16, 6));
+}
+
+#[test]
+fn test_blue_noise_2d_low_frequencies_suppressed() {
+    let size = 32;
+    let blue = blue_noise_2d(size, size, 1337);
+    let white = NoiseBuilder::white_2d(size, size)
+        .with_seed(1337)
+        .generate_scaled(0.0, 1.0);
+    let split = size as f32 / 8.0;
+
+    let (low, high) = radial_power(&blue, size, size, split);
+    assert!(low * 10.0 < high, "{} {}", low, high);
+    let (white_low, white_high) = radial_power(&white, size, size, split);
+    assert!(white_low * 2.0 > white_high, "{} {}", white_low, white_high);
+
+    // Thresholded point sets keep the property
+    for t in [0.1, 0.5] {
+        let (low, high) = radial_power(&threshold(&blue, t), size, size, split);
+        assert!(low * 4.0 < high, "{}: {} {}", t, low, high);
+    }
+}
+
+#[test]
+fn test_blue_noise_3d_low_frequencies_suppressed() {
+    // Slices through a blue noise volume lack low frequencies as well.
+    let size = 16;
+    let blue = blue_noise_3d(size, size, size, 42);
+    let slice = &blue[..size * size];
+    let (low, high) = radial_power(slice, size, size, size as f32 / 4.0);
+    assert!(low * 2.0 < high, "{} {}", low, high);
+}
+
+#[test]
+fn test_blue_noise_tileable() {
+    // The points of a sparse threshold keep their distance across the edges as well.
+    let size = 32;
+    let blue = blue_noise_2d(size, size, 7);
+    let points: Vec<(usize, usize)> = (0..size * size)
+        .filter(|i| blue[*i] < 0.1)
+        .map(|i| (i % size, i / size))
+        .collect();
+    let wrapped = |a: usize, b: usize| {
+        let d = a.abs_diff(b);
+        d.min(size - d) as f32
+    };
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            let (dx, dy) = (wrapped(a.0, b.0), wrapped(a.1, b.1));
+            assert!((dx * dx + dy * dy).sqrt() >= 2.0, "{:?} {:?}", a, b);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use simdnoise::{CellLattice, CellReturnType, NoiseBuilder, Settings};
+
+fn assert_close(a: &[f32], b: &[f32]) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-4, "{} != {}", x, y);
+    }
+}
+
+// Counts how many samples fall in each cell, ignoring cells touching the border of the block.
+fn cell_sizes(values: &[f32], width: usize, height: usize) -> Vec<usize> {
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    let mut border: Vec<u32> = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let v = values[y * width + x].to_bits();
+            *counts.entry(v).or_insert(0) += 1;
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                border.push(v);
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(v, _)| !border.contains(v))
+        .map(|(_, c)| c)
+        .collect()
+}
+
+#[test]
+fn test_cellular_lattice_square_is_default() {
+    let (expected, _min, _max) = NoiseBuilder::cellular_2d(64, 32)
+        .with_freq_2d(0.04, 0.01)
+        .with_seed(1337)
+        .generate();
+    let (noise, _min, _max) = NoiseBuilder::cellular_2d(64, 32)
+        .with_freq_2d(0.04, 0.01)
+        .with_seed(1337)
+        .with_lattice(CellLattice::Square)
+        .generate();
+    assert_eq!(expected, noise);
+}
+
+#[test]
+fn test_cellular_lattice_hexagonal_uniform_cells() {
+    let (noise, _min, _max) = NoiseBuilder::cellular_2d(128, 128)
+        .with_freq(0.1)
+        .with_jitter(0.0)
+        .with_return_type(CellReturnType::CellValue)
+        .with_lattice(CellLattice::Hexagonal)
+        .generate();
+    let sizes = cell_sizes(&noise, 128, 128);
+    assert!(sizes.len() > 50);
+    // One feature point per unit area, so each cell covers about 1 / freq^2 samples.
+    for size in sizes {
+        assert!((size as f32 - 100.0).abs() < 10.0, "cell size {}", size);
+    }
+}
+
+#[test]
+fn test_cellular_lattice_search_radius() {
+    for (lattice, jitter) in [(CellLattice::Hexagonal, 0.0), (CellLattice::Hexagonal, 0.4)] {
+        let generate = |radius| {
+            NoiseBuilder::cellular_2d(64, 64)
+                .with_freq(0.15)
+                .with_jitter(jitter)
+                .with_lattice(lattice)
+                .with_search_radius(radius)
+                .generate()
+                .0
+        };
+        assert_close(&generate(1), &generate(3));
+    }
+    for (lattice, jitter) in [
+        (CellLattice::BodyCenteredCubic, 0.0),
+        (CellLattice::BodyCenteredCubic, 0.4),
+        (CellLattice::FaceCenteredCubic, 0.0),
+        (CellLattice::FaceCenteredCubic, 0.4),
+    ] {
+        let generate = |radius| {
+            NoiseBuilder::cellular_3d(16, 16, 16)
+                .with_freq(0.3)
+                .with_jitter(jitter)
+                .with_lattice(lattice)
+                .with_search_radius(radius)
+                .generate()
+                .0
+        };
+        assert_close(&generate(1), &generate(3));
+    }
+}
+
+#[test]
+#[should_panic(expected = "lattice not supported")]
+fn test_cellular_lattice_hexagonal_3d() {
+    NoiseBuilder::cellular_3d(8, 8, 8)
+        .with_lattice(CellLattice::Hexagonal)
+        .wrap();
+}
@@ -0,0 +1,125 @@
+use simdnoise::{Cell2ReturnType, CellReturnType, NoiseBuilder, Settings};
+
+// Different radii start walking the neighbourhood from different cells, so cell offsets
+// accumulate different rounding errors.
+fn assert_close(a: &[f32], b: &[f32]) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-4, "{} != {}", x, y);
+    }
+}
+
+mod cellular {
+    use super::*;
+
+    #[test]
+    fn test_cellular_search_radius_default_unchanged() {
+        let (expected, _min, _max) = NoiseBuilder::cellular_2d(64, 32)
+            .with_freq_2d(0.04, 0.01)
+            .with_seed(1337)
+            .generate();
+        let (noise, _min, _max) = NoiseBuilder::cellular_2d(64, 32)
+            .with_freq_2d(0.04, 0.01)
+            .with_seed(1337)
+            .with_search_radius(1)
+            .generate();
+        assert_eq!(expected, noise);
+    }
+
+    #[test]
+    fn test_cellular_search_radius_high_jitter_2d() {
+        let generate = |radius| {
+            NoiseBuilder::cellular_2d(64, 64)
+                .with_freq(0.1)
+                .with_jitter(1.2)
+                .with_seed(1337)
+                .with_search_radius(radius)
+                .generate()
+                .0
+        };
+        let narrow = generate(1);
+        let wide = generate(3);
+        let wider = generate(4);
+        // Once the search covers the true nearest feature point, searching further must not
+        // find anything closer.
+        assert_close(&wide, &wider);
+        assert!(narrow.iter().zip(wide.iter()).any(|(n, w)| w + 1e-3 < *n));
+    }
+
+    #[test]
+    fn test_cellular_search_radius_high_jitter_3d() {
+        let generate = |radius| {
+            NoiseBuilder::cellular_3d(16, 16, 8)
+                .with_freq(0.2)
+                .with_jitter(1.2)
+                .with_return_type(CellReturnType::CellValue)
+                .with_seed(42)
+                .with_search_radius(radius)
+                .generate()
+                .0
+        };
+        assert_close(&generate(2), &generate(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid search radius")]
+    fn test_cellular_search_radius_zero() {
+        NoiseBuilder::cellular_2d(8, 8).with_search_radius(0).wrap();
+    }
+}
+
+mod cellular2 {
+    use super::*;
+
+    #[test]
+    fn test_cellular2_higher_indices() {
+        let generate = |index0, index1| {
+            NoiseBuilder::cellular2_2d(32, 32)
+                .with_freq(0.1)
+                .with_jitter(1.0)
+                .with_index0(index0)
+                .with_index1(index1)
+                .with_search_radius(2)
+                .with_seed(7)
+                .generate()
+                .0
+        };
+        let f2 = generate(0, 1);
+        let f5 = generate(3, 4);
+        for (a, b) in f2.iter().zip(f5.iter()) {
+            assert!(b + 1e-4 >= *a);
+        }
+    }
+
+    #[test]
+    fn test_cellular2_search_radius_high_jitter_3d() {
+        let generate = |radius| {
+            NoiseBuilder::cellular2_3d(16, 16, 8)
+                .with_freq(0.2)
+                .with_jitter(1.2)
+                .with_return_type(Cell2ReturnType::Distance2Sub)
+                .with_index0(1)
+                .with_index1(3)
+                .with_seed(42)
+                .with_search_radius(radius)
+                .generate()
+                .0
+        };
+        assert_close(&generate(3), &generate(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid index settings")]
+    fn test_cellular2_index_too_large() {
+        NoiseBuilder::cellular2_2d(8, 8)
+            .with_index1(16)
+            .with_search_radius(3)
+            .wrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of cells")]
+    fn test_cellular2_index_outside_neighbourhood() {
+        NoiseBuilder::cellular2_2d(8, 8).with_index1(9).wrap();
+    }
+}
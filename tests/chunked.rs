@@ -0,0 +1,175 @@
+use simdnoise::{
+    CellReturnType, ChunkedGenerator, NoiseBuilder, NoiseNode, Settings, SimplexSettings,
+};
+
+fn world_2d(chunk_size: usize) -> ChunkedGenerator {
+    let noise = NoiseBuilder::fbm_2d(1, 1)
+        .with_freq(0.05)
+        .with_octaves(4)
+        .with_seed(21)
+        .wrap();
+    ChunkedGenerator::new(noise, chunk_size)
+}
+
+#[test]
+fn test_chunks_tile() {
+    // Four chunks of 8 are one chunk of 16
+    let small = world_2d(8);
+    let large = world_2d(16).chunk_2d(-1, 2);
+    for (cy, cx) in [(4, -2), (4, -1), (5, -2), (5, -1)].iter() {
+        let block = small.chunk_2d(*cx, *cy);
+        assert_eq!(block.len(), 64);
+        let (x0, y0) = ((cx + 2) as usize * 8, (cy - 4) as usize * 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(block[y * 8 + x], large[(y0 + y) * 16 + x0 + x]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_padding_matches_neighbours() {
+    let mut world = world_2d(10);
+    let plain = world.clone();
+    world.with_padding(3);
+    assert_eq!(world.block_size(), 16);
+    assert_eq!(world.block_origin(-2), -23);
+
+    let n = world.block_size();
+    let block = world.chunk_2d(7, -5);
+    assert_eq!(world.crop(&block), plain.chunk_2d(7, -5));
+
+    // The padding on the right is the first columns of the next chunk
+    let right = plain.chunk_2d(8, -5);
+    for y in 0..10 {
+        for x in 0..3 {
+            assert_eq!(block[(y + 3) * n + 13 + x], right[y * 10 + x]);
+        }
+    }
+    // And the bottom left corner the top right of the chunk diagonally below
+    let corner = plain.chunk_2d(6, -4);
+    assert_eq!(block[13 * n + 2], corner[9]);
+}
+
+#[test]
+fn test_3d_and_crop() {
+    let noise = NoiseBuilder::ridge_3d(1, 1, 1).with_freq(0.1).wrap();
+    let mut world = ChunkedGenerator::new(noise, 6);
+    let plain = world.chunk_3d(3, 0, -1);
+    world.with_padding(2);
+    let block = world.chunk_3d(3, 0, -1);
+    assert_eq!(block.len(), 10 * 10 * 10);
+    assert_eq!(world.crop(&block), plain);
+
+    // Anything per sample crops the same way
+    let indices: Vec<usize> = (0..block.len()).collect();
+    let cropped = world.crop(&indices);
+    assert_eq!(cropped[0], (2 * 10 + 2) * 10 + 2);
+    assert_eq!(cropped[215], (7 * 10 + 7) * 10 + 7);
+}
+
+#[test]
+fn test_graph() {
+    let hills = NoiseNode::from(NoiseBuilder::fbm_2d(1, 1).with_freq(0.02).wrap());
+    let cells = NoiseNode::from(NoiseBuilder::cellular_2d(1, 1).with_freq(0.1).wrap());
+    let world = ChunkedGenerator::new(hills.max(cells).clamp(-0.5, 0.5), 12);
+    let block = world.chunk_2d(-3, 9);
+    assert_eq!(block.len(), 144);
+    assert!(block.iter().all(|v| (-0.5..=0.5).contains(v)));
+}
+
+#[test]
+fn test_chunk_of() {
+    let world = world_2d(16);
+    assert_eq!(world.chunk_of(0), (0, 0));
+    assert_eq!(world.chunk_of(31), (1, 15));
+    assert_eq!(world.chunk_of(-1), (-1, 15));
+    assert_eq!(world.chunk_of(-16), (-1, 0));
+    assert_eq!(world.chunk_of(-17), (-2, 15));
+}
+
+#[test]
+fn test_smooth_far_from_origin() {
+    // A hundred million samples out, where f32 positions are 8 samples apart
+    let world = world_2d(64);
+    let far = world.chunk_2d(100_000_000 / 64, 3);
+    let near = world.chunk_2d(0, 3);
+    let roughness = |block: &[f32]| {
+        let steps: Vec<f32> = block.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+        let flat = steps.iter().filter(|s| **s == 0.0).count();
+        (flat, steps.iter().cloned().fold(0.0, f32::max))
+    };
+    let (near_flat, near_step) = roughness(&near[..64]);
+    let (far_flat, far_step) = roughness(&far[..64]);
+    assert_eq!(far_flat, near_flat);
+    assert!(far_step < near_step * 2.0);
+}
+
+#[test]
+fn test_smooth_far_from_origin_3d() {
+    let noise = NoiseBuilder::fbm_3d(1, 1, 1)
+        .with_freq(0.05)
+        .with_seed(4)
+        .wrap();
+    let world = ChunkedGenerator::new(noise, 16);
+    let far = world.chunk_3d(-100_000_000 / 16, 2, 1_000_000);
+    let near = world.chunk_3d(0, 2, 1);
+    // Rows along x change at every sample, by about as much as next to the origin
+    let max_step = |block: &[f32]| {
+        let steps: Vec<f32> = block.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+        assert!(steps.iter().all(|s| *s != 0.0), "{:?}", steps);
+        steps.iter().cloned().fold(0.0, f32::max)
+    };
+    assert!(max_step(&far[..16]) < max_step(&near[..16]) * 2.0);
+}
+
+#[test]
+fn test_cell_values() {
+    let cells = NoiseBuilder::cellular_2d(1, 1)
+        .with_freq(0.1)
+        .with_return_type(CellReturnType::CellValue)
+        .wrap();
+    let world = ChunkedGenerator::new(cells, 32);
+    for (cx, cy) in [(0, 0), (-1, 2), (3_000_000, -3_000_000)].iter() {
+        let block = world.chunk_2d(*cx, *cy);
+        assert!(
+            block.iter().all(|v| (-1.0..=1.0).contains(v)),
+            "{} {}",
+            cx,
+            cy
+        );
+    }
+
+    // Next to the origin the chunks are the f32 blocks
+    let block = NoiseBuilder::cellular_2d_offset(-32.0, 32, 64.0, 32)
+        .with_freq(0.1)
+        .with_return_type(CellReturnType::CellValue)
+        .generate()
+        .0;
+    assert_eq!(world.chunk_2d(-1, 2), block);
+}
+
+#[test]
+#[should_panic(expected = "do not match")]
+fn test_wrong_dimensions() {
+    world_2d(8).chunk_3d(0, 0, 0);
+}
+
+#[test]
+fn test_every_dimension() {
+    let cells = NoiseBuilder::cellular_3d(1, 1, 1).with_freq(0.2).wrap();
+    assert_eq!(
+        ChunkedGenerator::new(cells, 5).chunk_3d(1, -1, 2).len(),
+        125
+    );
+    let white = NoiseBuilder::white_3d(1, 1, 1).wrap();
+    assert_eq!(ChunkedGenerator::new(white, 5).chunk_3d(0, 0, 0).len(), 125);
+    let line = NoiseBuilder::turbulence_1d(1).with_freq(0.1).wrap();
+    assert_eq!(ChunkedGenerator::new(line, 7).chunk_1d(-4).len(), 7);
+    let volume = NoiseBuilder::fbm_4d(1, 1, 1, 1).with_freq(0.1).wrap();
+    assert_eq!(
+        ChunkedGenerator::new(volume, 3).chunk_4d(1, 2, 3, 4).len(),
+        81
+    );
+}
@@ -0,0 +1,118 @@
+use simdnoise::{ColorRamp, NoiseBuilder, RampInterpolation, Settings};
+
+fn two_stops(interpolation: RampInterpolation) -> ColorRamp {
+    ColorRamp::new(
+        vec![(0.0, [0, 100, 200, 0]), (1.0, [200, 100, 0, 255])],
+        interpolation,
+    )
+}
+
+#[test]
+fn test_grayscale_matches_values() {
+    let noise = NoiseBuilder::fbm_2d(33, 21).generate_scaled(0.0, 1.0);
+    let rgba = ColorRamp::grayscale().map_rgba8(&noise);
+    assert_eq!(rgba.len(), noise.len() * 4);
+    for (pixel, v) in rgba.chunks(4).zip(&noise) {
+        let gray = (v * 255.0).round() as i32;
+        assert!((pixel[0] as i32 - gray).abs() <= 1);
+        assert_eq!(pixel[0], pixel[1]);
+        assert_eq!(pixel[1], pixel[2]);
+        assert_eq!(pixel[3], 255);
+    }
+}
+
+#[test]
+fn test_linear_interpolation() {
+    let rgba = two_stops(RampInterpolation::Linear).map_rgba8(&[0.0, 0.25, 0.5, 1.0]);
+    assert_eq!(
+        rgba,
+        [0, 100, 200, 0, 50, 100, 150, 64, 100, 100, 100, 128, 200, 100, 0, 255]
+    );
+}
+
+#[test]
+fn test_values_outside_are_clamped() {
+    let ramp = two_stops(RampInterpolation::Linear);
+    assert_eq!(ramp.map_rgba8(&[-5.0]), ramp.map_rgba8(&[0.0]));
+    assert_eq!(ramp.map_rgba8(&[5.0]), ramp.map_rgba8(&[1.0]));
+}
+
+#[test]
+fn test_step_interpolation() {
+    let ramp = ColorRamp::new(
+        vec![
+            (0.0, [10, 0, 0, 255]),
+            (0.5, [20, 0, 0, 255]),
+            (1.0, [30, 0, 0, 255]),
+        ],
+        RampInterpolation::Step,
+    );
+    let red: Vec<u8> = ramp
+        .map_rgba8(&[0.0, 0.49, 0.5, 0.99, 1.0])
+        .chunks(4)
+        .map(|c| c[0])
+        .collect();
+    assert_eq!(red, [10, 10, 20, 20, 30]);
+}
+
+#[test]
+fn test_smooth_interpolation() {
+    let smooth = two_stops(RampInterpolation::Smooth).map_rgba8(&[0.0, 0.25, 0.5, 0.75, 1.0]);
+    let linear = two_stops(RampInterpolation::Linear).map_rgba8(&[0.0, 0.25, 0.5, 0.75, 1.0]);
+    // Same at the stops and the midpoint, eased in between
+    assert_eq!(smooth[..4], linear[..4]);
+    assert_eq!(smooth[8..12], linear[8..12]);
+    assert_eq!(smooth[16..], linear[16..]);
+    assert!(smooth[4] < linear[4]);
+    assert!(smooth[12] > linear[12]);
+}
+
+#[test]
+fn test_single_stop() {
+    let ramp = ColorRamp::new(vec![(0.3, [1, 2, 3, 4])], RampInterpolation::Linear);
+    assert_eq!(
+        ramp.map_rgba8(&[-1.0, 0.3, 7.0]),
+        [1, 2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn test_linear_rgba() {
+    let ramp = ColorRamp::grayscale();
+    let rgba = ramp.map_linear_rgba(&[0.0, 0.5, 1.0]);
+    assert_eq!(rgba.len(), 12);
+    assert_eq!(rgba[..4], [0.0, 0.0, 0.0, 1.0]);
+    // sRGB 0.5 is about 0.214 in linear light
+    assert!((rgba[4] - 0.214).abs() < 0.001);
+    assert_eq!(rgba[7], 1.0);
+    assert!((rgba[8] - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_builtin_ramps() {
+    let noise = NoiseBuilder::ridge_2d(17, 5).generate_scaled(0.0, 1.0);
+    for ramp in [
+        ColorRamp::terrain(),
+        ColorRamp::heat(),
+        ColorRamp::grayscale(),
+    ]
+    .iter()
+    {
+        let rgba = ramp.map_rgba8(&noise);
+        assert_eq!(rgba.len(), noise.len() * 4);
+        assert_eq!(ramp.map_rgba8(&[0.0])[..], ramp.stops()[0].1[..]);
+        assert_eq!(
+            ramp.map_rgba8(&[1.0])[..],
+            ramp.stops().last().unwrap().1[..]
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_descending_stops() {
+    ColorRamp::new(
+        vec![(0.5, [0, 0, 0, 255]), (0.2, [255, 255, 255, 255])],
+        RampInterpolation::Linear,
+    );
+}
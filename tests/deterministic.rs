@@ -0,0 +1,216 @@
+#![cfg(all(
+    feature = "deterministic",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+
+use simdeez::prelude::*;
+use simdnoise::intrinsics::scalar;
+use simdnoise::{
+    Cell2ReturnType, CellDistanceFunction, CellLattice, CellReturnType, NoiseBuilder, NoiseType,
+    Settings, SimplexSettings,
+};
+
+/// Generates the f32 and f64 noise with the instruction set it is run with.
+struct Generate;
+
+impl __SimdRunner<(NoiseType, usize), (Vec<f32>, Vec<f64>)> for Generate {
+    unsafe fn run<S: Simd>(args: (NoiseType, usize)) -> (Vec<f32>, Vec<f64>) {
+        S::invoke(
+            #[inline(always)]
+            || {
+                let (noise_type, dim) = (&args.0, args.1);
+                match dim {
+                    1 => (
+                        scalar::get_1d_noise::<S>(noise_type).0,
+                        scalar::get_1d_noise_64::<S>(noise_type).0,
+                    ),
+                    2 => (
+                        scalar::get_2d_noise::<S>(noise_type).0,
+                        scalar::get_2d_noise_64::<S>(noise_type).0,
+                    ),
+                    3 => (
+                        scalar::get_3d_noise::<S>(noise_type).0,
+                        scalar::get_3d_noise_64::<S>(noise_type).0,
+                    ),
+                    _ => (
+                        scalar::get_4d_noise::<S>(noise_type).0,
+                        scalar::get_4d_noise_64::<S>(noise_type).0,
+                    ),
+                }
+            },
+        )
+    }
+}
+
+fn noise_types() -> Vec<(NoiseType, usize)> {
+    vec![
+        (NoiseBuilder::gradient_1d(100).with_freq(0.13).wrap(), 1),
+        (NoiseBuilder::gradient_2d(37, 23).with_freq(0.13).wrap(), 2),
+        (
+            NoiseBuilder::gradient_3d(17, 13, 11).with_freq(0.13).wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::gradient_4d(9, 7, 5, 3).with_freq(0.13).wrap(),
+            4,
+        ),
+        (NoiseBuilder::fbm_1d(100).with_freq(0.07).wrap(), 1),
+        (NoiseBuilder::fbm_2d(37, 23).with_freq(0.07).wrap(), 2),
+        (NoiseBuilder::fbm_3d(17, 13, 11).with_freq(0.07).wrap(), 3),
+        (NoiseBuilder::fbm_4d(9, 7, 5, 3).with_freq(0.07).wrap(), 4),
+        (NoiseBuilder::ridge_1d(100).with_octaves(4).wrap(), 1),
+        (NoiseBuilder::ridge_2d(37, 23).with_octaves(4).wrap(), 2),
+        (NoiseBuilder::ridge_3d(17, 13, 11).with_octaves(4).wrap(), 3),
+        (NoiseBuilder::ridge_4d(9, 7, 5, 3).with_octaves(4).wrap(), 4),
+        (NoiseBuilder::turbulence_1d(100).with_gain(1.5).wrap(), 1),
+        (NoiseBuilder::turbulence_2d(37, 23).with_gain(1.5).wrap(), 2),
+        (
+            NoiseBuilder::turbulence_3d(17, 13, 11)
+                .with_gain(1.5)
+                .wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::turbulence_4d(9, 7, 5, 3)
+                .with_gain(1.5)
+                .wrap(),
+            4,
+        ),
+        (NoiseBuilder::cellular_2d(37, 23).with_freq(0.1).wrap(), 2),
+        (
+            NoiseBuilder::cellular_2d(37, 23)
+                .with_freq(0.1)
+                .with_distance_function(CellDistanceFunction::Natural)
+                .with_return_type(CellReturnType::CellValue)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::cellular_2d(37, 23)
+                .with_freq(0.1)
+                .with_search_radius(2)
+                .with_lattice(CellLattice::Hexagonal)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::cellular_3d(17, 13, 11).with_freq(0.1).wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::cellular_3d(17, 13, 11)
+                .with_freq(0.1)
+                .with_lattice(CellLattice::BodyCenteredCubic)
+                .wrap(),
+            3,
+        ),
+        (NoiseBuilder::cellular2_2d(37, 23).with_freq(0.1).wrap(), 2),
+        (
+            NoiseBuilder::cellular2_2d(37, 23)
+                .with_freq(0.1)
+                .with_return_type(Cell2ReturnType::Distance2Div)
+                .with_search_radius(2)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::cellular2_3d(17, 13, 11)
+                .with_freq(0.1)
+                .with_distance_function(CellDistanceFunction::Manhattan)
+                .wrap(),
+            3,
+        ),
+        (NoiseBuilder::white_2d(37, 23).wrap(), 2),
+        (NoiseBuilder::white_4d(9, 7, 5, 3).wrap(), 4),
+        // Far from the origin, where the f32 noise is sampled with the f64 kernels
+        (
+            NoiseBuilder::gradient_1d_offset(-1e7, 100)
+                .with_freq(0.13)
+                .wrap(),
+            1,
+        ),
+        (
+            NoiseBuilder::fbm_2d_offset(1e7, 37, -1e7, 23)
+                .with_freq(0.07)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::ridge_3d_offset(1e7, 17, 1e7, 13, -1e7, 11)
+                .with_octaves(4)
+                .wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::turbulence_4d_offset(1e7, 9, -1e7, 7, 1e7, 5, 1e7, 3)
+                .with_gain(1.5)
+                .wrap(),
+            4,
+        ),
+        (
+            NoiseBuilder::cellular_2d_offset(1e7, 37, 1e7, 23)
+                .with_freq(0.1)
+                .with_return_type(CellReturnType::CellValue)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::cellular_3d_offset(-1e7, 17, 1e7, 13, 1e7, 11)
+                .with_freq(0.1)
+                .wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::cellular2_2d_offset(1e7, 37, -1e7, 23)
+                .with_freq(0.1)
+                .wrap(),
+            2,
+        ),
+        // Across the 4096 sample threshold, where the kernels change from lane to lane
+        (
+            NoiseBuilder::fbm_2d_offset(4080.0, 37, 0.0, 23)
+                .with_freq(0.07)
+                .wrap(),
+            2,
+        ),
+    ]
+}
+
+fn assert_bit_exact<T: Copy + Into<f64>>(backend: &str, i: usize, expected: &[T], noise: &[T]) {
+    assert_eq!(expected.len(), noise.len());
+    for (a, b) in expected.iter().zip(noise.iter()) {
+        let (a, b): (f64, f64) = ((*a).into(), (*b).into());
+        assert!(
+            a.to_bits() == b.to_bits(),
+            "noise type {} differs on {}: {} != {}",
+            i,
+            backend,
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn test_deterministic_backends() {
+    for (i, noise_type) in noise_types().into_iter().enumerate() {
+        let (expected_32, expected_64) = __run_simd_invoke_scalar::<Generate, _, _>(noise_type);
+        let mut backends = vec![("sse2", unsafe {
+            __run_simd_invoke_sse2::<Generate, _, _>(noise_type)
+        })];
+        if is_x86_feature_detected!("sse4.1") {
+            backends.push(("sse41", unsafe {
+                __run_simd_invoke_sse41::<Generate, _, _>(noise_type)
+            }));
+        }
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            backends.push(("avx2", unsafe {
+                __run_simd_invoke_avx2::<Generate, _, _>(noise_type)
+            }));
+        }
+        for (backend, (noise_32, noise_64)) in backends {
+            assert_bit_exact(backend, i, &expected_32, &noise_32);
+            assert_bit_exact(backend, i, &expected_64, &noise_64);
+        }
+    }
+}
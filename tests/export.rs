@@ -0,0 +1,341 @@
+use simdnoise::export::{
+    load_npy, load_raw, read_npy, save_image, save_image_stack, save_npy, save_raw, write_image,
+    write_npy, write_ppm, ImageFormat,
+};
+use simdnoise::{NoiseBuilder, Settings};
+use std::convert::TryInto;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("simdnoise_export_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+// Splits a PNM header of `fields` whitespace separated fields from the pixels.
+fn pnm_header(bytes: &[u8], fields: usize) -> (Vec<String>, &[u8]) {
+    let mut header = Vec::new();
+    let mut pos = 0;
+    while header.len() < fields {
+        let start = pos;
+        while !bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        header.push(String::from_utf8(bytes[start..pos].to_vec()).unwrap());
+        pos += 1;
+    }
+    (header, &bytes[pos..])
+}
+
+// Reads back a 16 bit grayscale PNG as written by `ImageFormat::Png16`.
+fn read_png_16(bytes: &[u8]) -> (usize, usize, Vec<u16>) {
+    assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+    let mut pos = 8;
+    let (mut width, mut height, mut zlib) = (0, 0, Vec::new());
+    loop {
+        let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let data = &bytes[pos + 8..pos + 8 + len];
+        pos += 12 + len;
+        match kind {
+            b"IHDR" => {
+                width = u32::from_be_bytes(data[0..4].try_into().unwrap()) as usize;
+                height = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+                assert_eq!(&data[8..], &[16, 0, 0, 0, 0]);
+            }
+            b"IDAT" => zlib.extend(data),
+            b"IEND" => break,
+            _ => panic!("unexpected chunk"),
+        }
+    }
+    // Stored deflate blocks only
+    let mut raw = Vec::new();
+    let mut pos = 2;
+    loop {
+        let last = zlib[pos] & 1 == 1;
+        assert_eq!(zlib[pos] & 6, 0);
+        let len = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]) as usize;
+        let nlen = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]) as usize;
+        assert_eq!(len, !nlen & 0xffff);
+        raw.extend(&zlib[pos + 5..pos + 5 + len]);
+        pos += 5 + len;
+        if last {
+            break;
+        }
+    }
+    let mut samples = Vec::with_capacity(width * height);
+    for row in raw.chunks(width * 2 + 1) {
+        assert_eq!(row[0], 0);
+        samples.extend(row[1..].chunks(2).map(|s| u16::from_be_bytes([s[0], s[1]])));
+    }
+    (width, height, samples)
+}
+
+#[test]
+fn test_pgm8_round_trip() {
+    let noise = NoiseBuilder::fbm_2d(37, 19).generate_scaled(0.0, 1.0);
+    let path = temp_path("fbm.pgm");
+    save_image(&path, ImageFormat::Pgm8, &noise, 37, 19).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    let (header, pixels) = pnm_header(&bytes, 4);
+    assert_eq!(header, ["P5", "37", "19", "255"]);
+    assert_eq!(pixels.len(), noise.len());
+    for (pixel, v) in pixels.iter().zip(&noise) {
+        assert!((*pixel as f32 / 255.0 - v).abs() <= 0.5 / 255.0 + 1e-6);
+    }
+}
+
+#[test]
+fn test_pgm16_round_trip() {
+    let noise = NoiseBuilder::gradient_2d(16, 9).generate_scaled(0.0, 1.0);
+    let mut bytes = Vec::new();
+    write_image(&mut bytes, ImageFormat::Pgm16, &noise, 16, 9).unwrap();
+    let (header, pixels) = pnm_header(&bytes, 4);
+    assert_eq!(header, ["P5", "16", "9", "65535"]);
+    assert_eq!(pixels.len(), noise.len() * 2);
+    for (pixel, v) in pixels.chunks(2).zip(&noise) {
+        let pixel = u16::from_be_bytes([pixel[0], pixel[1]]);
+        assert!((pixel as f32 / 65535.0 - v).abs() <= 1e-5);
+    }
+}
+
+#[test]
+fn test_integer_formats_clamp() {
+    let mut bytes = Vec::new();
+    write_image(&mut bytes, ImageFormat::Pgm8, &[-1.0, 0.5, 2.0], 3, 1).unwrap();
+    let (_, pixels) = pnm_header(&bytes, 4);
+    assert_eq!(pixels, &[0, 128, 255]);
+}
+
+#[test]
+fn test_pfm_round_trip() {
+    let (width, height) = (11, 7);
+    let noise = NoiseBuilder::ridge_2d(width, height).generate().0;
+    let mut bytes = Vec::new();
+    write_image(&mut bytes, ImageFormat::Pfm, &noise, width, height).unwrap();
+    let (header, pixels) = pnm_header(&bytes, 4);
+    assert_eq!(header, ["Pf", "11", "7", "-1.0"]);
+    let floats: Vec<f32> = pixels
+        .chunks(4)
+        .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    // Rows are stored bottom to top
+    for y in 0..height {
+        let row = &floats[(height - 1 - y) * width..(height - y) * width];
+        assert_eq!(row, &noise[y * width..(y + 1) * width]);
+    }
+}
+
+#[test]
+fn test_png16_round_trip() {
+    let noise = NoiseBuilder::cellular_2d(33, 17).generate_scaled(0.0, 1.0);
+    let path = temp_path("cells.png");
+    save_image(&path, ImageFormat::Png16, &noise, 33, 17).unwrap();
+    let (width, height, samples) = read_png_16(&std::fs::read(&path).unwrap());
+    assert_eq!((width, height), (33, 17));
+    assert_eq!(samples.len(), noise.len());
+    for (sample, v) in samples.iter().zip(&noise) {
+        assert!((*sample as f32 / 65535.0 - v).abs() <= 1e-5);
+    }
+}
+
+#[test]
+fn test_png16_multiple_blocks() {
+    // Over 64k of raw data needs more than one stored deflate block
+    let (width, height) = (300, 200);
+    let noise = NoiseBuilder::white_2d(width, height).generate_scaled(0.0, 1.0);
+    let mut bytes = Vec::new();
+    write_image(&mut bytes, ImageFormat::Png16, &noise, width, height).unwrap();
+    let (_, _, samples) = read_png_16(&bytes);
+    assert_eq!(samples.len(), noise.len());
+    for (sample, v) in samples.iter().zip(&noise) {
+        assert_eq!(*sample, (v.clamp(0.0, 1.0) * 65535.0).round() as u16);
+    }
+}
+
+#[test]
+fn test_image_stack() {
+    let (width, height, depth) = (8, 6, 3);
+    let noise = NoiseBuilder::fbm_3d(width, height, depth).generate_scaled(0.0, 1.0);
+    let paths = save_image_stack(
+        temp_path("stack"),
+        ImageFormat::Pgm8,
+        &noise,
+        width,
+        height,
+        depth,
+    )
+    .unwrap();
+    assert_eq!(paths.len(), depth);
+    for (z, path) in paths.iter().enumerate() {
+        assert!(path.ends_with(format!("stack_{:04}.pgm", z)));
+        let bytes = std::fs::read(path).unwrap();
+        let (header, pixels) = pnm_header(&bytes, 4);
+        assert_eq!(header, ["P5", "8", "6", "255"]);
+        let slice = &noise[z * width * height..(z + 1) * width * height];
+        for (pixel, v) in pixels.iter().zip(slice) {
+            assert_eq!(*pixel, (v.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+}
+
+#[test]
+fn test_ppm() {
+    let rgb = [255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30];
+    let mut bytes = Vec::new();
+    write_ppm(&mut bytes, &rgb, 2, 2).unwrap();
+    let (header, pixels) = pnm_header(&bytes, 4);
+    assert_eq!(header, ["P6", "2", "2", "255"]);
+    assert_eq!(pixels, &rgb);
+}
+
+#[test]
+fn test_image_wrong_size() {
+    let kind = |result: std::io::Result<()>| result.unwrap_err().kind();
+    let noise = vec![0.5; 12];
+    let mut bytes = Vec::new();
+    assert_eq!(
+        kind(write_image(&mut bytes, ImageFormat::Pgm8, &noise, 5, 2)),
+        std::io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        kind(write_image(
+            &mut bytes,
+            ImageFormat::Pfm,
+            &noise,
+            usize::MAX,
+            2
+        )),
+        std::io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        kind(write_ppm(&mut bytes, &[0; 12], 2, 3)),
+        std::io::ErrorKind::InvalidInput
+    );
+    assert!(bytes.is_empty());
+    let stack = save_image_stack(temp_path("short"), ImageFormat::Pgm8, &noise, 2, 2, 4);
+    assert_eq!(stack.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_data_wrong_shape() {
+    let noise = vec![0.5f32; 12];
+    let error = write_npy(Vec::new(), &noise, &[5, 2]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    let error = save_raw(temp_path("short.raw"), &noise, &[usize::MAX, 2]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_npy_round_trip() {
+    let settings = NoiseBuilder::ridge_3d(13, 7, 5);
+    let shape = settings.wrap().dimensions().shape();
+    assert_eq!(shape, [5, 7, 13]);
+    let (noise, _min, _max) = settings.generate();
+    let path = temp_path("ridge.npy");
+    save_npy(&path, &noise, &shape).unwrap();
+    let (back, back_shape) = load_npy::<_, f32>(&path).unwrap();
+    assert_eq!(back_shape, shape);
+    assert_eq!(back, noise);
+
+    // The data starts at a multiple of 64 bytes and the header describes it
+    let bytes = std::fs::read(&path).unwrap();
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    assert_eq!((10 + header_len) % 64, 0);
+    assert_eq!(bytes.len(), 10 + header_len + noise.len() * 4);
+    let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+    assert!(header.contains("'descr': '<f4'"));
+    assert!(header.contains("'shape': (5, 7, 13,)"));
+}
+
+#[test]
+fn test_npy_f64_and_shapes() {
+    let data: Vec<f64> = (0..24).map(|i| i as f64 * 0.1 - 1.0).collect();
+    for shape in [vec![24], vec![4, 6], vec![2, 3, 4], vec![1, 2, 3, 4]].iter() {
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, &data, shape).unwrap();
+        let (back, back_shape) = read_npy::<_, f64>(&bytes[..]).unwrap();
+        assert_eq!(&back_shape, shape);
+        assert_eq!(back, data);
+    }
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &data, &[24]).unwrap();
+    assert!(String::from_utf8_lossy(&bytes[10..]).contains("'shape': (24,)"));
+    // Reading with the wrong type fails
+    assert!(read_npy::<_, f32>(&bytes[..]).is_err());
+}
+
+#[test]
+fn test_npy_written_by_numpy() {
+    // np.save of np.arange(6, dtype='<f4').reshape(2, 3)
+    let mut bytes =
+        b"\x93NUMPY\x01\x00v\x00{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"
+            .to_vec();
+    bytes.resize(127, b' ');
+    bytes.push(b'\n');
+    for i in 0..6 {
+        bytes.extend(&(i as f32).to_le_bytes());
+    }
+    let (data, shape) = read_npy::<_, f32>(&bytes[..]).unwrap();
+    assert_eq!(shape, [2, 3]);
+    assert_eq!(data, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn test_npy_corrupt_shape() {
+    let npy = |shape: &str| {
+        let mut bytes = format!(
+            "\x00\x00{{'descr': '<f4', 'fortran_order': False, 'shape': ({}), }}\n",
+            shape
+        )
+        .into_bytes();
+        let header_len = (bytes.len() - 2) as u16;
+        bytes[..2].copy_from_slice(&header_len.to_le_bytes());
+        let mut file = b"\x93NUMPY\x01\x00".to_vec();
+        file.extend(bytes);
+        file.extend(&[0; 16]);
+        file
+    };
+    let kind = |shape: &str| read_npy::<_, f32>(&npy(shape)[..]).unwrap_err().kind();
+    // The element count, and the byte count, overflow
+    assert_eq!(
+        kind("4294967296, 4294967296"),
+        std::io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        kind("4611686018427387904,"),
+        std::io::ErrorKind::InvalidData
+    );
+    // More values than the file holds
+    assert_eq!(kind("1000000000000,"), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(read_npy::<_, f32>(&npy("2, 2")[..]).unwrap().0, [0.0; 4]);
+}
+
+#[test]
+fn test_raw_round_trip() {
+    let settings = NoiseBuilder::cellular_3d(9, 8, 3);
+    let shape = settings.wrap().dimensions().shape();
+    let (noise, _min, _max) = settings.generate();
+    let header_path = save_raw(temp_path("cells.raw"), &noise, &shape).unwrap();
+    assert!(header_path.ends_with("cells.nhdr"));
+    let header = std::fs::read_to_string(&header_path).unwrap();
+    assert!(header.starts_with("NRRD0004\n"));
+    assert!(header.contains("type: float\n"));
+    assert!(header.contains("sizes: 9 8 3\n"));
+    assert!(header.contains("data file: cells.raw\n"));
+    let bytes = std::fs::read(temp_path("cells.raw")).unwrap();
+    let raw: Vec<f32> = bytes
+        .chunks(4)
+        .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(raw, noise);
+
+    let (back, back_shape) = load_raw::<_, f32>(&header_path).unwrap();
+    assert_eq!(back_shape, shape);
+    assert_eq!(back, noise);
+    assert!(load_raw::<_, f64>(&header_path).is_err());
+
+    let data: Vec<f64> = (0..10).map(|i| i as f64 / 3.0).collect();
+    let header_path = save_raw(temp_path("line.raw"), &data, &[10]).unwrap();
+    assert_eq!(load_raw::<_, f64>(&header_path).unwrap(), (data, vec![10]));
+}
@@ -0,0 +1,105 @@
+use simdnoise::{
+    blue_noise_2d, curvature_map, normal_map, normal_map_rgb8, slope_map, EdgeMode, NoiseBuilder,
+    Settings,
+};
+
+// A plane rising by `dx` per sample along x and `dy` along y.
+fn plane(width: usize, height: usize, dx: f32, dy: f32) -> Vec<f32> {
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| x as f32 * dx + y as f32 * dy))
+        .collect()
+}
+
+#[test]
+fn test_flat_normals() {
+    let normals = normal_map(&vec![0.5; 12], 4, 3, 10.0, EdgeMode::Clamp);
+    assert!(normals.iter().all(|n| *n == [0.0, 0.0, 1.0]));
+    let rgb = normal_map_rgb8(&vec![0.5; 12], 4, 3, 10.0, EdgeMode::Wrap);
+    assert_eq!(rgb.len(), 36);
+    assert!(rgb.chunks(3).all(|c| c == [128, 128, 255]));
+}
+
+#[test]
+fn test_plane_normals() {
+    let data = plane(8, 6, 1.0, 0.0);
+    // The edges are one sided differences, so the whole plane has the same normal
+    for n in normal_map(&data, 8, 6, 1.0, EdgeMode::Clamp) {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((n[0] + half).abs() < 1e-6);
+        assert_eq!(n[1], 0.0);
+        assert!((n[2] - half).abs() < 1e-6);
+    }
+    for slope in slope_map(&data, 8, 6, 1.0, EdgeMode::Clamp) {
+        assert!((slope - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+    }
+    // Rising along y tilts the normals back along y
+    for n in normal_map(&plane(5, 5, 0.0, 0.5), 5, 5, 2.0, EdgeMode::Clamp) {
+        assert_eq!(n[0], 0.0);
+        assert!(n[1] < 0.0);
+    }
+}
+
+#[test]
+fn test_strength() {
+    let data = plane(6, 6, 0.25, 0.0);
+    let weak = slope_map(&data, 6, 6, 1.0, EdgeMode::Clamp);
+    let strong = slope_map(&data, 6, 6, 4.0, EdgeMode::Clamp);
+    assert!((weak[7] - 0.25f32.atan()).abs() < 1e-6);
+    assert!((strong[7] - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+}
+
+#[test]
+fn test_curvature() {
+    let (width, height) = (9, 9);
+    let bowl: Vec<f32> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x as f32 - 4.0).powi(2) + (y as f32 - 4.0).powi(2)))
+        .collect();
+    let curvature = curvature_map(&bowl, width, height, 1.0, EdgeMode::Clamp);
+    assert_eq!(curvature[4 * width + 4], 4.0);
+    let hill: Vec<f32> = bowl.iter().map(|v| -v).collect();
+    let curvature = curvature_map(&hill, width, height, 0.5, EdgeMode::Clamp);
+    assert_eq!(curvature[4 * width + 4], -2.0);
+    let curvature = curvature_map(
+        &plane(width, height, 0.3, 0.7),
+        width,
+        height,
+        1.0,
+        EdgeMode::Wrap,
+    );
+    assert!(curvature[4 * width + 4].abs() < 1e-5);
+}
+
+#[test]
+fn test_wrap_is_seamless() {
+    // Tiling a tileable texture three by three and taking the middle tile matches the wrapped map
+    let (width, height) = (16, 12);
+    let tile = blue_noise_2d(width, height, 3);
+    let tiled: Vec<f32> = (0..height * 3)
+        .flat_map(|y| (0..width * 3).map(move |x| (x % width, y % height)))
+        .map(|(x, y)| tile[y * width + x])
+        .collect();
+    let wrapped = normal_map(&tile, width, height, 2.0, EdgeMode::Wrap);
+    let big = normal_map(&tiled, width * 3, height * 3, 2.0, EdgeMode::Clamp);
+    for y in 0..height {
+        for x in 0..width {
+            assert_eq!(
+                wrapped[y * width + x],
+                big[(y + height) * width * 3 + x + width]
+            );
+        }
+    }
+    let wrapped = curvature_map(&tile, width, height, 1.0, EdgeMode::Wrap);
+    let big = curvature_map(&tiled, width * 3, height * 3, 1.0, EdgeMode::Clamp);
+    assert_eq!(wrapped[0], big[height * width * 3 + width]);
+}
+
+#[test]
+fn test_noise_normals_are_unit() {
+    let noise = NoiseBuilder::fbm_2d(32, 24).generate_scaled(0.0, 1.0);
+    let normals = normal_map(&noise, 32, 24, 8.0, EdgeMode::Clamp);
+    assert_eq!(normals.len(), noise.len());
+    for n in normals {
+        assert!((n[0] * n[0] + n[1] * n[1] + n[2] * n[2] - 1.0).abs() < 1e-5);
+        assert!(n[2] > 0.0);
+    }
+}
@@ -0,0 +1,340 @@
+use simdeez::scalar::Scalar;
+use simdnoise::intrinsics::scalar::get_2d_noise_64;
+use simdnoise::{
+    AlgorithmVersion, CellReturnType, NoiseBuilder, NoiseNode, NoiseType, Settings, SimplexSettings,
+};
+
+// The golden files are generated without the `deterministic` feature, which changes the output
+#[cfg(not(feature = "deterministic"))]
+mod helpers;
+#[cfg(not(feature = "deterministic"))]
+use helpers::{read_from_file_f32, /*save_to_file_f32, */ BIN_PATH};
+
+// The positions of neighbouring samples that round to the same f32 position, and the largest
+// step between neighbours.
+fn roughness(row: &[f32]) -> (usize, f32) {
+    let steps: Vec<f32> = row.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+    let flat = steps.iter().filter(|s| **s == 0.0).count();
+    (flat, steps.iter().cloned().fold(0.0, f32::max))
+}
+
+#[test]
+fn test_near_origin_unchanged() {
+    let fbm = |version| {
+        NoiseBuilder::fbm_2d_offset(-300.0, 64, 200.5, 32)
+            .with_freq(0.05)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    assert_eq!(fbm(AlgorithmVersion::V2), fbm(AlgorithmVersion::V3));
+    let gradient = |version| {
+        NoiseBuilder::gradient_4d_offset(-20.0, 9, 3.0, 5, 100.0, 3, -7.0, 2)
+            .with_freq(0.3)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    assert_eq!(
+        gradient(AlgorithmVersion::V2),
+        gradient(AlgorithmVersion::V3)
+    );
+}
+
+#[test]
+fn test_far_blocks_smooth() {
+    // A hundred million samples out, where f32 positions are 8 samples apart
+    let fbm = |offset, version| {
+        NoiseBuilder::fbm_2d_offset(offset, 64, offset, 1)
+            .with_freq(0.05)
+            .with_octaves(4)
+            .with_seed(21)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    let (near_flat, near_step) = roughness(&fbm(0.0, AlgorithmVersion::V3));
+    let (far_flat, far_step) = roughness(&fbm(100_000_000.0, AlgorithmVersion::V3));
+    assert_eq!(far_flat, near_flat);
+    assert!(far_step < near_step * 2.0);
+    let (stairs, _) = roughness(&fbm(100_000_000.0, AlgorithmVersion::V2));
+    assert!(stairs > 32);
+
+    let line = NoiseBuilder::turbulence_1d_offset(-30_000_000.0, 100)
+        .with_freq(0.1)
+        .with_lacunarity(2.0)
+        .generate()
+        .0;
+    assert_eq!(roughness(&line).0, 0);
+}
+
+#[test]
+fn test_far_quantized_matches_scaled() {
+    let noise = *NoiseBuilder::ridge_2d_offset(50_000_000.0, 37, -3_000_000.0, 5).with_freq(0.1);
+    let scaled = noise.generate_scaled(0.0, 255.0);
+    for (q, v) in noise.generate_u8(0, 255).iter().zip(&scaled) {
+        assert!(
+            (*q as f32 - v).abs() <= 0.5 + 1e-3,
+            "{} is not {} rounded",
+            q,
+            v
+        );
+    }
+}
+
+#[test]
+fn test_far_blocks_match_f64() {
+    // Far blocks are the f64 noise rounded to f32
+    let noise = NoiseBuilder::fbm_2d_offset(3_000_000.0, 40, -2_000_000.0, 6)
+        .with_freq(0.25)
+        .with_octaves(3)
+        .with_seed(8)
+        .wrap();
+    let far = match noise {
+        NoiseType::Fbm(s) => s.generate().0,
+        _ => unreachable!(),
+    };
+    let exact = unsafe { get_2d_noise_64::<Scalar>(&noise).0 };
+    assert_eq!(far.len(), exact.len());
+    for (f, e) in far.iter().zip(&exact) {
+        assert!((*f as f64 - e).abs() < 1e-5, "{} {}", f, e);
+    }
+}
+
+#[test]
+fn test_far_every_noise_type() {
+    // All noise types and lacunarities sample far blocks in f64, unlike V2
+    let far = |noise: &dyn Fn(AlgorithmVersion) -> Vec<f32>| {
+        let v3 = noise(AlgorithmVersion::V3);
+        assert_eq!(roughness(&v3).0, 0);
+        assert!(v3 != noise(AlgorithmVersion::V2));
+    };
+    far(&|version| {
+        NoiseBuilder::cellular_2d_offset(1e7, 64, 1e7, 1)
+            .with_freq(0.1)
+            .with_version(version)
+            .generate()
+            .0
+    });
+    far(&|version| {
+        NoiseBuilder::cellular2_3d_offset(-1e7, 64, 5.0, 1, 1e7, 1)
+            .with_freq(0.1)
+            .with_version(version)
+            .generate()
+            .0
+    });
+    far(&|version| {
+        NoiseBuilder::fbm_3d_offset(1e7, 64, 1e7, 1, 0.0, 1)
+            .with_freq(0.05)
+            .with_version(version)
+            .generate()
+            .0
+    });
+    far(&|version| {
+        NoiseBuilder::fbm_2d_offset(1e7, 64, 1e7, 1)
+            .with_freq(0.05)
+            .with_lacunarity(0.3)
+            .with_version(version)
+            .generate()
+            .0
+    });
+    far(&|version| {
+        NoiseBuilder::white_3d_offset(1e8, 64, 1e8, 1, 1e8, 1)
+            .with_version(version)
+            .generate()
+            .0
+    });
+
+    // Cell values hash their cells like the f32 kernels, so across the 4096 sample threshold
+    // they stay in [-1, 1] and match the f32 output of V2
+    let cell_values = |version| {
+        NoiseBuilder::cellular_2d_offset(3900.0, 400, -50.0, 4)
+            .with_freq(0.1)
+            .with_return_type(CellReturnType::CellValue)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    let v3 = cell_values(AlgorithmVersion::V3);
+    assert!(v3.iter().all(|v| (-1.0..=1.0).contains(v)));
+    assert_eq!(v3, cell_values(AlgorithmVersion::V2));
+}
+
+#[test]
+fn test_far_noise_node() {
+    let fbm = |version| {
+        NoiseBuilder::fbm_2d(64, 1)
+            .with_freq(0.05)
+            .with_version(version)
+            .wrap()
+    };
+    let node = |version| {
+        (NoiseNode::from(fbm(version)).translate([5e7, 5e7, 0.0, 0.0]) * NoiseNode::Constant(2.0))
+            .generate()
+            .0
+    };
+    let v3 = node(AlgorithmVersion::V3);
+    assert_eq!(roughness(&v3).0, 0);
+    assert!(v3 != node(AlgorithmVersion::V2));
+
+    // Without the translation the graph stays on the f32 helpers
+    let near = |version| NoiseNode::from(fbm(version)).generate().0;
+    assert_eq!(near(AlgorithmVersion::V2), near(AlgorithmVersion::V3));
+}
+
+#[test]
+fn test_far_displaced_noise_node() {
+    let fbm = |version| {
+        NoiseBuilder::fbm_2d(64, 16)
+            .with_freq(0.05)
+            .with_version(version)
+            .wrap()
+    };
+    let gradient = |version| {
+        NoiseBuilder::gradient_2d(64, 16)
+            .with_freq(0.1)
+            .with_seed(3)
+            .with_version(version)
+            .wrap()
+    };
+    let distance = |version| {
+        NoiseBuilder::cellular_2d(64, 16)
+            .with_freq(0.1)
+            .with_version(version)
+            .wrap()
+    };
+    // Bounded offsets keep a graph near the origin on the f32 helpers
+    let near = |version| {
+        NoiseNode::from(fbm(version))
+            .displace(vec![
+                NoiseNode::from(gradient(version)) * NoiseNode::Constant(8.0),
+            ])
+            .generate()
+            .0
+    };
+    assert_eq!(near(AlgorithmVersion::V2), near(AlgorithmVersion::V3));
+
+    // Cellular distances have no bound and move the positions anywhere
+    let unbounded = |version| {
+        NoiseNode::from(fbm(version))
+            .displace(vec![NoiseNode::from(distance(version))])
+            .generate()
+            .0
+    };
+    assert!(unbounded(AlgorithmVersion::V2) != unbounded(AlgorithmVersion::V3));
+}
+
+#[test]
+fn test_far_seam_matches_tiles() {
+    // A block across the 4096 sample threshold gives each position the value the blocks on
+    // either side of it do
+    let fbm = |x, width, y, height| {
+        NoiseBuilder::fbm_2d_offset(x, width, y, height)
+            .with_freq(0.05)
+            .with_octaves(3)
+            .with_seed(4)
+            .with_version(AlgorithmVersion::V3)
+            .generate()
+            .0
+    };
+    let across = fbm(4000.0, 200, -2.0, 3);
+    let near = fbm(4000.0, 96, -2.0, 3);
+    let far = fbm(4096.0, 104, -2.0, 3);
+    for row in 0..3 {
+        assert_eq!(
+            across[row * 200..row * 200 + 96],
+            near[row * 96..(row + 1) * 96]
+        );
+        assert_eq!(
+            across[row * 200 + 96..(row + 1) * 200],
+            far[row * 104..(row + 1) * 104]
+        );
+    }
+
+    // Positions short of the threshold keep the f32 kernels, whatever the block reaches
+    let origin = |width, version| {
+        NoiseBuilder::gradient_1d(width)
+            .with_freq(0.3)
+            .with_version(version)
+            .generate()
+            .0
+    };
+    let wide = origin(4097, AlgorithmVersion::V3);
+    assert_eq!(wide[..4096], origin(4096, AlgorithmVersion::V3)[..]);
+    assert_eq!(wide[..1024], origin(1024, AlgorithmVersion::V2)[..]);
+}
+
+#[test]
+fn test_far_seam_noise_node() {
+    let node = |x, width| {
+        let fbm = NoiseBuilder::fbm_2d_offset(x, width, 0.0, 2)
+            .with_freq(0.05)
+            .with_version(AlgorithmVersion::V3)
+            .wrap();
+        (NoiseNode::from(fbm).translate([-100.0, 0.0, 0.0, 0.0]) * NoiseNode::Constant(2.0))
+            .generate()
+            .0
+    };
+    // The translation moves the threshold to 3996 samples
+    let across = node(3900.0, 200);
+    let near = node(3900.0, 96);
+    let far = node(3996.0, 104);
+    for row in 0..2 {
+        assert_eq!(
+            across[row * 200..row * 200 + 96],
+            near[row * 96..(row + 1) * 96]
+        );
+        assert_eq!(
+            across[row * 200 + 96..(row + 1) * 200],
+            far[row * 104..(row + 1) * 104]
+        );
+    }
+}
+
+#[cfg(not(feature = "deterministic"))]
+fn file_name(dim: &str) -> String {
+    format!("{}/noisebuilder_fbm_faroffset_32_{}_v3.bin", BIN_PATH, dim)
+}
+
+#[test]
+#[cfg(not(feature = "deterministic"))]
+fn test_far_blocks_v3() {
+    let blocks = [
+        (
+            "1d",
+            NoiseBuilder::fbm_1d_offset(-40_000_000.0, 200)
+                .with_freq(0.05)
+                .with_octaves(4)
+                .with_seed(5)
+                .with_version(AlgorithmVersion::V3)
+                .generate()
+                .0,
+        ),
+        (
+            "2d",
+            NoiseBuilder::fbm_2d_offset(10_000_000.0, 48, -20_000_000.0, 24)
+                .with_freq(0.05)
+                .with_octaves(4)
+                .with_seed(5)
+                .with_version(AlgorithmVersion::V3)
+                .generate()
+                .0,
+        ),
+        (
+            "4d",
+            NoiseBuilder::fbm_4d_offset(1e6, 16, -1e6, 8, 3e6, 4, -5e6, 4)
+                .with_freq(0.05)
+                .with_octaves(4)
+                .with_seed(5)
+                .with_version(AlgorithmVersion::V3)
+                .generate()
+                .0,
+        ),
+    ];
+    for (dim, noise) in blocks.iter() {
+        let file_name = file_name(dim);
+        //save_to_file_f32(&file_name, noise.as_slice()).unwrap();
+        let expected = read_from_file_f32(&file_name).unwrap();
+        assert!(expected == *noise, "{}", dim);
+    }
+}
@@ -0,0 +1,245 @@
+#![cfg(feature = "mesh")]
+
+use std::collections::HashMap;
+
+use simdnoise::mesh::{
+    chunk_origin, chunk_samples, heightfield_mesh, marching_cubes, marching_cubes_chunk, Mesh,
+};
+use simdnoise::{NoiseBuilder, Settings};
+
+// How often each directed edge of the triangles is used.
+fn directed_edges(mesh: &Mesh) -> HashMap<(u32, u32), usize> {
+    let mut edges = HashMap::new();
+    for t in mesh.indices.chunks_exact(3) {
+        for k in 0..3 {
+            *edges.entry((t[k], t[(k + 1) % 3])).or_insert(0) += 1;
+        }
+    }
+    edges
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[test]
+fn test_sphere_is_closed() {
+    let n = 20;
+    let center = [9.3, 9.6, 10.1];
+    let mut data = Vec::with_capacity(n * n * n);
+    for z in 0..n {
+        for y in 0..n {
+            for x in 0..n {
+                let d = sub([x as f32, y as f32, z as f32], center);
+                data.push(6.0 - dot(d, d).sqrt());
+            }
+        }
+    }
+    let mesh = marching_cubes(&data, n, n, n, 0.0);
+    assert!(mesh.triangle_count() > 100);
+    assert_eq!(mesh.normals.len(), mesh.vertex_count());
+
+    // Every edge is used once in each direction, so the surface is closed and consistently
+    // wound, and it is a sphere
+    let edges = directed_edges(&mesh);
+    for (&(a, b), &count) in &edges {
+        assert_eq!(count, 1);
+        assert_eq!(edges.get(&(b, a)), Some(&1));
+    }
+    let euler = mesh.vertex_count() as i64 - edges.len() as i64 / 2 + mesh.triangle_count() as i64;
+    assert_eq!(euler, 2);
+
+    for (p, n) in mesh.positions.iter().zip(&mesh.normals) {
+        let radius = sub(*p, center);
+        assert!((dot(radius, radius).sqrt() - 6.0).abs() < 0.1);
+        assert!(dot(*n, radius) / 6.0 > 0.95);
+        assert!((dot(*n, *n) - 1.0).abs() < 1e-4);
+    }
+    for t in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| mesh.positions[t[k] as usize]);
+        let face = cross(sub(b, a), sub(c, a));
+        assert!(dot(face, sub(a, center)) > 0.0, "triangle faces inwards");
+    }
+}
+
+#[test]
+fn test_plane() {
+    let (width, height, depth) = (7, 5, 6);
+    let mut data = Vec::new();
+    for z in 0..depth {
+        for _ in 0..width * height {
+            data.push(2.25 - z as f32);
+        }
+    }
+    let mesh = marching_cubes(&data, width, height, depth, 0.0);
+    assert_eq!(mesh.vertex_count(), width * height);
+    assert_eq!(mesh.triangle_count(), 2 * (width - 1) * (height - 1));
+    for (p, n) in mesh.positions.iter().zip(&mesh.normals) {
+        assert!((p[2] - 2.25).abs() < 1e-6);
+        assert_eq!(*n, [0.0, 0.0, 1.0]);
+    }
+
+    let empty = marching_cubes(&data, width, height, depth, 10.0);
+    assert_eq!(empty, Mesh::new());
+}
+
+#[test]
+fn test_chunks_weld() {
+    let size = 12;
+    let n = chunk_samples(size);
+    let chunks = [[-1, 0, 0], [0, 0, 0], [0, 1, 0], [-1, 1, 0]];
+    let meshes: Vec<Mesh> = chunks
+        .iter()
+        .map(|&chunk| {
+            let [x, y, z] = chunk_origin(chunk, size);
+            let noise = NoiseBuilder::fbm_3d_offset(x as f32, n, y as f32, n, z as f32, n)
+                .with_freq(0.15)
+                .with_seed(11)
+                .generate()
+                .0;
+            marching_cubes_chunk(&noise, chunk, size, 0.0)
+        })
+        .collect();
+
+    // Vertices on the shared faces have the same normal in every chunk
+    let mut normals = HashMap::new();
+    let mut shared = 0;
+    for mesh in &meshes {
+        for (p, n) in mesh.positions.iter().zip(&mesh.normals) {
+            let key = [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()];
+            if let Some(other) = normals.insert(key, *n) {
+                assert_eq!(other, *n);
+                shared += 1;
+            }
+        }
+        let (min, max) = (-(size as f32), size as f32);
+        assert!(mesh
+            .positions
+            .iter()
+            .all(|p| p[0] >= min && p[0] <= max && p[1] >= 0.0 && p[1] <= 2.0 * max));
+    }
+    assert!(shared > 0);
+
+    let world = Mesh::weld(&meshes);
+    assert_eq!(world.vertex_count(), normals.len());
+    assert_eq!(
+        world.triangle_count(),
+        meshes.iter().map(Mesh::triangle_count).sum::<usize>()
+    );
+
+    // Edges across the faces inside the world are shared by two triangles, wound opposite
+    let edges = directed_edges(&world);
+    let inside = |p: [f32; 3]| p[0] == 0.0 || p[1] == size as f32;
+    for &(a, b) in edges.keys() {
+        let (pa, pb) = (world.positions[a as usize], world.positions[b as usize]);
+        if inside(pa) && inside(pb) && pa[2] > 0.0 && pa[2] < size as f32 {
+            assert!(edges.contains_key(&(b, a)), "open seam at {:?}", pa);
+        }
+    }
+}
+
+#[test]
+fn test_obj_and_ply() {
+    let mesh = Mesh {
+        positions: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.5, 0.0]],
+        normals: vec![[0.0, 0.0, 1.0]; 3],
+        uvs: Vec::new(),
+        indices: vec![0, 1, 2],
+    };
+
+    let mut obj = Vec::new();
+    mesh.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    let lines: Vec<&str> = obj.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "v 0 0 0",
+            "v 1 0 0",
+            "v 0 1.5 0",
+            "vn 0 0 1",
+            "vn 0 0 1",
+            "vn 0 0 1",
+            "f 1//1 2//2 3//3",
+        ]
+    );
+
+    let mut ply = Vec::new();
+    mesh.write_ply(&mut ply).unwrap();
+    let end = b"end_header\n";
+    let body = ply.windows(end.len()).position(|w| w == end).unwrap() + end.len();
+    let header = String::from_utf8_lossy(&ply[..body]);
+    assert!(header.starts_with("ply\nformat binary_little_endian 1.0\n"));
+    assert!(header.contains("element vertex 3\n"));
+    assert!(header.contains("element face 1\n"));
+    assert_eq!(ply.len() - body, 3 * 24 + 13);
+    assert_eq!(&ply[body + 24..body + 28], &1.0f32.to_le_bytes());
+    assert_eq!(ply[body + 72], 3);
+    assert_eq!(&ply[body + 81..], &2u32.to_le_bytes());
+}
+
+#[test]
+fn test_heightfield_grid() {
+    let (width, height) = (5, 4);
+    // Rising along x by half a sample per sample, doubled by the scale
+    let data: Vec<f32> = (0..width * height)
+        .map(|i| (i % width) as f32 * 0.5)
+        .collect();
+    let mesh = heightfield_mesh(&data, width, height, 2.0);
+    assert_eq!(mesh.vertex_count(), width * height);
+    assert_eq!(mesh.uvs.len(), width * height);
+    assert_eq!(mesh.triangle_count(), 2 * (width - 1) * (height - 1));
+
+    assert_eq!(mesh.positions[0], [0.0, 0.0, 0.0]);
+    assert_eq!(mesh.positions[width * height - 1], [4.0, 4.0, 3.0]);
+    assert_eq!(mesh.uvs[0], [0.0, 1.0]);
+    assert_eq!(mesh.uvs[width * height - 1], [1.0, 0.0]);
+
+    // A 45 degree slope facing away from x, up and to the left
+    let s = 0.5f32.sqrt();
+    for n in &mesh.normals {
+        assert!((n[0] + s).abs() < 1e-6 && (n[1] - s).abs() < 1e-6 && n[2] == 0.0);
+    }
+    for t in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| mesh.positions[t[k] as usize]);
+        let face = cross(sub(b, a), sub(c, a));
+        assert!(face[1] > 0.0, "triangle faces down");
+        assert!(dot(face, mesh.normals[t[0] as usize]) > 0.0);
+    }
+}
+
+#[test]
+fn test_heightfield_files() {
+    let noise = NoiseBuilder::fbm_2d(9, 6).generate_scaled(0.0, 1.0);
+    let mesh = heightfield_mesh(&noise, 9, 6, 3.0);
+
+    let mut obj = Vec::new();
+    mesh.write_obj(&mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("v "), 54);
+    assert_eq!(count("vt "), 54);
+    assert_eq!(count("vn "), 54);
+    assert_eq!(count("f "), 80);
+    assert!(obj.contains("\nf 1/1/1 10/10/10 2/2/2\n"));
+
+    let mut ply = Vec::new();
+    mesh.write_ply(&mut ply).unwrap();
+    let end = b"end_header\n";
+    let body = ply.windows(end.len()).position(|w| w == end).unwrap() + end.len();
+    let header = String::from_utf8_lossy(&ply[..body]);
+    assert!(header.contains("property float nz\nproperty float s\nproperty float t\n"));
+    assert_eq!(ply.len() - body, 54 * 32 + 80 * 13);
+}
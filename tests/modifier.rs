@@ -0,0 +1,149 @@
+use simdnoise::{modify_buffer, Modifier, NoiseBuilder, NoiseNode, NoiseType, Settings};
+
+fn reference(modifier: &Modifier, v: f32) -> f32 {
+    match modifier {
+        Modifier::Terrace { points, invert } => {
+            let v = v.clamp(points[0], points[points.len() - 1]);
+            let i = (1..points.len() - 1)
+                .rev()
+                .find(|i| v >= points[*i])
+                .unwrap_or(0);
+            let (lo, hi) = (points[i], points[i + 1]);
+            let t = (v - lo) / (hi - lo);
+            if *invert {
+                hi + (lo - hi) * (1.0 - t) * (1.0 - t)
+            } else {
+                lo + (hi - lo) * t * t
+            }
+        }
+        Modifier::Curve(points) => {
+            let v = v.clamp(points[0][0], points[points.len() - 1][0]);
+            let i = (1..points.len() - 1)
+                .rev()
+                .find(|i| v >= points[*i][0])
+                .unwrap_or(0);
+            let (lo, hi) = (points[i], points[i + 1]);
+            lo[1] + (hi[1] - lo[1]) * (v - lo[0]) / (hi[0] - lo[0])
+        }
+        Modifier::Exponent(e) => v.abs().powf(*e).copysign(v),
+        Modifier::Clamp { min, max } => v.clamp(*min, *max),
+    }
+}
+
+fn modifiers() -> Vec<Modifier> {
+    vec![
+        Modifier::Terrace {
+            points: vec![-1.0, -0.25, 0.0, 0.6, 1.0],
+            invert: false,
+        },
+        Modifier::Terrace {
+            points: vec![-0.5, 0.5],
+            invert: true,
+        },
+        Modifier::Curve(vec![[-1.0, 0.0], [0.0, 0.8], [0.5, 0.1], [1.0, 1.0]]),
+        Modifier::Exponent(2.5),
+        Modifier::Exponent(3.0),
+        Modifier::Exponent(0.7),
+        Modifier::Clamp {
+            min: -0.3,
+            max: 0.4,
+        },
+    ]
+}
+
+#[test]
+fn test_modify_buffer() {
+    // Not a multiple of any vector width, and past the ends of every modifier
+    let values: Vec<f32> = (0..301).map(|i| i as f32 / 100.0 - 1.5).collect();
+    for modifier in modifiers() {
+        let mut data = values.clone();
+        modify_buffer(&mut data, std::slice::from_ref(&modifier));
+        for (v, m) in values.iter().zip(data.iter()) {
+            let expected = reference(&modifier, *v);
+            assert!(
+                (m - expected).abs() < 1e-5,
+                "{:?} {} {} {}",
+                modifier,
+                v,
+                m,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_modify_buffer_chain() {
+    let mut data = NoiseBuilder::fbm_2d(33, 17).with_seed(5).generate().0;
+    let expected: Vec<f32> = data
+        .iter()
+        .map(|v| modifiers().iter().fold(*v, |v, m| reference(m, v)))
+        .collect();
+    modify_buffer(&mut data, &modifiers());
+    for (m, e) in data.iter().zip(expected.iter()) {
+        assert!((m - e).abs() < 1e-5, "{} {}", m, e);
+    }
+}
+
+#[test]
+fn test_terrace_points() {
+    let points = vec![-1.0, -0.25, 0.0, 0.6, 1.0];
+    for invert in [false, true] {
+        let mut data = points.clone();
+        modify_buffer(
+            &mut data,
+            &[Modifier::Terrace {
+                points: points.clone(),
+                invert,
+            }],
+        );
+        assert_eq!(data, points);
+    }
+}
+
+#[test]
+fn test_noise_node_modify() {
+    let noise_type = NoiseBuilder::gradient_3d(9, 8, 7)
+        .with_freq(0.1)
+        .with_seed(6)
+        .wrap();
+    let mut expected = match noise_type {
+        NoiseType::Gradient(s) => s.generate().0,
+        _ => unreachable!(),
+    };
+    modify_buffer(&mut expected, &modifiers()[2..4]);
+    let (noise, min, max) = NoiseNode::from(noise_type)
+        .curve(vec![[-1.0, 0.0], [0.0, 0.8], [0.5, 0.1], [1.0, 1.0]])
+        .exponent(2.5)
+        .generate();
+    for (n, e) in noise.iter().zip(expected.iter()) {
+        assert!((n - e).abs() < 1e-6, "{} {}", n, e);
+        assert!(*n >= min && *n <= max);
+    }
+}
+
+#[test]
+#[should_panic(expected = "needs at least two ascending points")]
+fn test_terrace_unsorted() {
+    modify_buffer(
+        &mut [0.0],
+        &[Modifier::Terrace {
+            points: vec![0.0, 1.0, 0.5],
+            invert: false,
+        }],
+    );
+}
+
+#[test]
+#[should_panic(expected = "needs at least two points")]
+fn test_curve_single_point() {
+    NoiseNode::from(NoiseBuilder::fbm_2d(4, 4).wrap())
+        .curve(vec![[0.0, 1.0]])
+        .generate();
+}
+
+#[test]
+#[should_panic(expected = "invalid clamp range in modifier")]
+fn test_clamp_invalid() {
+    modify_buffer(&mut [0.0], &[Modifier::Clamp { min: 1.0, max: 0.0 }]);
+}
@@ -0,0 +1,338 @@
+use std::f32::consts::PI;
+
+use simdnoise::{NoiseBuilder, NoiseNode, NoiseType, Settings, SimplexSettings};
+
+fn fbm() -> NoiseType {
+    NoiseBuilder::fbm_2d(37, 20)
+        .with_freq(0.05)
+        .with_octaves(3)
+        .with_seed(1)
+        .wrap()
+}
+
+fn cellular() -> NoiseType {
+    NoiseBuilder::cellular_2d(37, 20)
+        .with_freq(0.1)
+        .with_seed(2)
+        .wrap()
+}
+
+fn generate(noise_type: NoiseType) -> Vec<f32> {
+    match noise_type {
+        NoiseType::Fbm(s) => s.generate().0,
+        NoiseType::Cellular(s) => s.generate().0,
+        _ => unreachable!(),
+    }
+}
+
+fn check<F: Fn(f32, f32) -> f32>(node: NoiseNode, f: F) {
+    let a = generate(fbm());
+    let b = generate(cellular());
+    let (noise, min, max) = node.generate();
+    assert_eq!(noise.len(), a.len());
+    for i in 0..noise.len() {
+        let expected = f(a[i], b[i]);
+        assert!(
+            (noise[i] - expected).abs() < 1e-6 * (1.0 + expected.abs()),
+            "{} {}",
+            noise[i],
+            expected
+        );
+        assert!(noise[i] >= min && noise[i] <= max);
+    }
+}
+
+#[test]
+fn test_noise_node_source() {
+    let (noise, min, max) = NoiseNode::from(fbm()).generate();
+    let (expected, expected_min, expected_max) = match fbm() {
+        NoiseType::Fbm(s) => s.generate(),
+        _ => unreachable!(),
+    };
+    assert_eq!(noise, expected);
+    assert_eq!((min, max), (expected_min, expected_max));
+}
+
+#[test]
+fn test_noise_node_arithmetic() {
+    check(
+        NoiseNode::from(fbm()) + NoiseNode::from(cellular()),
+        |a, b| a + b,
+    );
+    check(
+        NoiseNode::from(fbm()) * NoiseNode::from(cellular()),
+        |a, b| a * b,
+    );
+    check(
+        NoiseNode::from(fbm()).min(NoiseNode::from(cellular())),
+        |a, b| a.min(b),
+    );
+    check(
+        NoiseNode::from(fbm()).max(NoiseNode::from(cellular())),
+        |a, b| a.max(b),
+    );
+    check(NoiseNode::from(fbm()) + NoiseNode::Constant(0.5), |a, _| {
+        a + 0.5
+    });
+}
+
+#[test]
+fn test_noise_node_unary() {
+    check(NoiseNode::from(fbm()).abs(), |a, _| a.abs());
+    check(NoiseNode::from(fbm()).invert(), |a, _| -a);
+    check(NoiseNode::from(fbm()).clamp(-0.1, 0.2), |a, _| {
+        a.clamp(-0.1, 0.2)
+    });
+    check(
+        NoiseNode::from(fbm()).abs().power(NoiseNode::Constant(2.0)),
+        |a, _| a * a,
+    );
+    check(
+        NoiseNode::Constant(2.0).power(NoiseNode::from(cellular())),
+        |_, b| 2f32.powf(b),
+    );
+    check(
+        NoiseNode::from(fbm()).abs().power(NoiseNode::Constant(2.5)),
+        |a, _| a.abs().powf(2.5),
+    );
+    check(
+        (NoiseNode::from(fbm()).abs() + NoiseNode::Constant(0.5)).power(NoiseNode::Constant(-3.0)),
+        |a, _| (a.abs() + 0.5).powf(-3.0),
+    );
+    check(
+        NoiseNode::from(fbm()).abs().power(NoiseNode::Constant(0.3)),
+        |a, _| a.abs().powf(0.3),
+    );
+}
+
+#[test]
+fn test_noise_node_blend() {
+    let blend = |control: f32| {
+        NoiseNode::from(fbm()).blend(NoiseNode::from(cellular()), NoiseNode::Constant(control))
+    };
+    check(blend(-1.0), |a, _| a);
+    check(blend(1.0), |_, b| b);
+    check(blend(0.0), |a, b| a + (b - a) * 0.5);
+    check(blend(5.0), |_, b| b);
+}
+
+#[test]
+fn test_noise_node_select() {
+    let select = |falloff: f32| {
+        NoiseNode::from(fbm()).select(
+            NoiseNode::Constant(10.0),
+            NoiseNode::from(cellular()),
+            0.1,
+            falloff,
+        )
+    };
+    check(select(0.0), |a, b| if b < 0.1 { a } else { 10.0 });
+    check(select(0.05), |a, b| {
+        let t = ((b - 0.05) / 0.1).clamp(0.0, 1.0);
+        let t = t * t * (3.0 - 2.0 * t);
+        a + (10.0 - a) * t
+    });
+}
+
+#[test]
+fn test_noise_node_3d() {
+    let gradient = NoiseBuilder::gradient_3d(9, 8, 7).with_seed(3).wrap();
+    let cellular = NoiseBuilder::cellular_3d(9, 8, 7)
+        .with_freq(0.2)
+        .with_seed(4)
+        .wrap();
+    let (noise, _min, _max) = (NoiseNode::from(gradient) * NoiseNode::from(cellular)).generate();
+    let a = match gradient {
+        NoiseType::Gradient(s) => s.generate().0,
+        _ => unreachable!(),
+    };
+    let b = match cellular {
+        NoiseType::Cellular(s) => s.generate().0,
+        _ => unreachable!(),
+    };
+    for i in 0..noise.len() {
+        assert!((noise[i] - a[i] * b[i]).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_noise_node_scaled() {
+    let noise = (NoiseNode::from(fbm()) + NoiseNode::from(cellular())).generate_scaled(0.0, 1.0);
+    for v in noise {
+        assert!((-1e-6..=1.0 + 1e-6).contains(&v));
+    }
+}
+
+#[test]
+#[should_panic(expected = "needs at least one noise source")]
+fn test_noise_node_without_source() {
+    (NoiseNode::Constant(1.0) + NoiseNode::Constant(2.0)).generate();
+}
+
+#[test]
+#[should_panic(expected = "different dimensions")]
+fn test_noise_node_mixed_dimensions() {
+    let noise_3d = NoiseBuilder::gradient_3d(4, 4, 4).wrap();
+    (NoiseNode::from(fbm()) + NoiseNode::from(noise_3d)).generate();
+}
+
+#[test]
+#[should_panic(expected = "different sizes or offsets")]
+fn test_noise_node_mixed_sizes() {
+    let smaller = NoiseBuilder::gradient_2d(36, 20).wrap();
+    (NoiseNode::from(fbm()) + NoiseNode::from(smaller)).generate();
+}
+
+#[test]
+#[should_panic(expected = "different sizes or offsets")]
+fn test_noise_node_mixed_offsets() {
+    let shifted = NoiseBuilder::gradient_2d_offset(3.0, 37, 0.0, 20).wrap();
+    (NoiseNode::from(fbm()) + NoiseNode::from(shifted)).generate();
+}
+
+#[test]
+#[should_panic(expected = "invalid clamp range")]
+fn test_noise_node_invalid_clamp() {
+    NoiseNode::from(fbm()).clamp(1.0, 0.0).generate();
+}
+
+fn gradient_2d(x: f32, width: usize, y: f32, height: usize) -> NoiseType {
+    NoiseBuilder::gradient_2d_offset(x, width, y, height)
+        .with_freq(0.07)
+        .with_seed(8)
+        .wrap()
+}
+
+fn assert_close(a: &[f32], b: &[f32], tolerance: f32) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < tolerance, "{} {}", x, y);
+    }
+}
+
+fn matrix(node: &NoiseNode) -> [[f32; 4]; 4] {
+    match node {
+        NoiseNode::Transform { matrix, .. } => *matrix,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_noise_node_translate() {
+    let translated = NoiseNode::from(gradient_2d(0.0, 24, 0.0, 16))
+        .translate([16.0, -4.0, 0.0, 0.0])
+        .generate()
+        .0;
+    let (offset, _min, _max) = NoiseNode::from(gradient_2d(16.0, 24, -4.0, 16)).generate();
+    assert_eq!(translated, offset);
+}
+
+#[test]
+fn test_noise_node_scale() {
+    let scaled = NoiseNode::from(gradient_2d(0.0, 24, 0.0, 16))
+        .scale([2.0, 0.5, 1.0, 1.0])
+        .generate()
+        .0;
+    let (expected, _min, _max) = NoiseBuilder::gradient_2d(24, 16)
+        .with_freq_2d(0.14, 0.035)
+        .with_seed(8)
+        .generate();
+    assert_close(&scaled, &expected, 1e-5);
+}
+
+#[test]
+fn test_noise_node_rotate_2d() {
+    let (width, height) = (24, 16);
+    // A quarter turn samples the source at (-y, x)
+    let rotated = NoiseNode::from(gradient_2d(0.0, width, 0.0, height))
+        .rotate_2d(PI / 2.0)
+        .generate()
+        .0;
+    let (source, _min, _max) =
+        NoiseNode::from(gradient_2d(1.0 - height as f32, height, 0.0, width)).generate();
+    for y in 0..height {
+        for x in 0..width {
+            let expected = source[x * height + (height - 1 - y)];
+            assert!((rotated[y * width + x] - expected).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn test_noise_node_rotate_3d() {
+    let source = NoiseNode::from(NoiseBuilder::gradient_3d(8, 8, 8).with_freq(0.1).wrap());
+    let around_z = source.clone().rotate_3d([0.0, 0.0, 2.0], 0.3).generate().0;
+    let in_plane = source.clone().rotate_4d(0, 1, 0.3).generate().0;
+    assert_close(&around_z, &in_plane, 1e-5);
+
+    let m = matrix(&source.rotate_3d([1.0, 2.0, -0.5], 1.1));
+    for i in 0..3 {
+        for j in 0..3 {
+            let dot = (0..3).map(|k| m[i][k] * m[j][k]).sum::<f32>();
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn test_noise_node_rotate_4d() {
+    let source = NoiseNode::from(NoiseBuilder::gradient_4d(4, 4, 4, 4).with_freq(0.2).wrap());
+    let full_turn = source.clone().rotate_4d(2, 3, 2.0 * PI).generate().0;
+    assert_close(&full_turn, &source.generate().0, 1e-4);
+}
+
+#[test]
+fn test_noise_node_shear() {
+    let m = matrix(&NoiseNode::Constant(0.0).shear(0, 1, 0.5));
+    assert_eq!(m[0], [1.0, 0.5, 0.0, 0.0]);
+    assert_eq!(m[1], [0.0, 1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_noise_node_displace() {
+    let source = NoiseNode::from(gradient_2d(0.0, 24, 0.0, 16));
+    let constant = source
+        .clone()
+        .displace(vec![NoiseNode::Constant(3.0), NoiseNode::Constant(-2.0)])
+        .generate()
+        .0;
+    let translated = source.clone().translate([3.0, -2.0, 0.0, 0.0]).generate().0;
+    assert_eq!(constant, translated);
+
+    let turbulence = NoiseNode::from(
+        NoiseBuilder::fbm_2d(24, 16)
+            .with_freq(0.1)
+            .with_seed(9)
+            .wrap(),
+    ) * NoiseNode::Constant(4.0);
+    let displaced = source.clone().displace(vec![turbulence]).generate().0;
+    assert_ne!(displaced, source.generate().0);
+}
+
+#[test]
+#[should_panic(expected = "more displacement offsets")]
+fn test_noise_node_displace_too_many_offsets() {
+    let offsets = vec![NoiseNode::Constant(1.0); 3];
+    NoiseNode::from(gradient_2d(0.0, 4, 0.0, 4))
+        .displace(offsets)
+        .generate();
+}
+
+#[test]
+#[should_panic(expected = "invalid rotation axis")]
+fn test_noise_node_zero_rotation_axis() {
+    NoiseNode::Constant(0.0).rotate_3d([0.0; 3], 1.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid rotation plane")]
+fn test_noise_node_same_rotation_axes() {
+    NoiseNode::Constant(0.0).rotate_4d(2, 2, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "invalid shear axes")]
+fn test_noise_node_shear_out_of_range() {
+    NoiseNode::Constant(0.0).shear(4, 0, 1.0);
+}
@@ -1,3 +1,6 @@
+// The golden files are generated without the `deterministic` feature, which changes the output
+#![cfg(not(feature = "deterministic"))]
+
 use simdnoise::{NoiseBuilder, Settings, SimplexSettings};
 
 mod helpers;
@@ -0,0 +1,216 @@
+use simdnoise::{
+    feature_points_2d, feature_points_3d, poisson_disk_2d, poisson_disk_3d, CellLattice,
+    CellReturnType, FeaturePoint, NoFeaturePoints, NoiseBuilder, Settings,
+};
+
+fn nearest<const N: usize>(points: &[FeaturePoint<N>], p: [f32; N]) -> (f32, f32, f32) {
+    let mut distances: Vec<(f32, f32)> = points
+        .iter()
+        .map(|fp| {
+            let d2 = (0..N).map(|i| (fp.position[i] - p[i]).powi(2)).sum::<f32>();
+            (d2, fp.value)
+        })
+        .collect();
+    distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    (distances[0].0, distances[0].1, distances[1].0)
+}
+
+#[test]
+fn test_feature_points_match_cellular_2d() {
+    for lattice in [CellLattice::Square, CellLattice::Hexagonal] {
+        let (width, height, freq, jitter, seed) = (48, 40, 0.1, 0.45, 1234);
+        let generate = |return_type| {
+            NoiseBuilder::cellular_2d(width, height)
+                .with_freq(freq)
+                .with_jitter(jitter)
+                .with_lattice(lattice)
+                .with_return_type(return_type)
+                .with_seed(seed)
+                .generate()
+                .0
+        };
+        let values = generate(CellReturnType::CellValue);
+        let distances = generate(CellReturnType::Distance);
+        let points = feature_points_2d([-2.0, -2.0], [7.0, 6.0], lattice, jitter, seed);
+        for y in 0..height {
+            for x in 0..width {
+                let i = y * width + x;
+                let p = [x as f32 * freq, y as f32 * freq];
+                let (d2, value, second) = nearest(&points, p);
+                assert!((distances[i] - d2).abs() < 1e-3, "{} {}", distances[i], d2);
+                if second - d2 > 1e-3 {
+                    assert_eq!(values[i], value);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_feature_points_match_cellular_3d() {
+    let (size, freq, jitter, seed) = (12, 0.25, 0.4, 99);
+    let values = NoiseBuilder::cellular_3d(size, size, size)
+        .with_freq(freq)
+        .with_jitter(jitter)
+        .with_search_radius(2)
+        .with_return_type(CellReturnType::CellValue)
+        .with_seed(seed)
+        .generate()
+        .0;
+    let points = feature_points_3d([-2.0; 3], [5.0; 3], CellLattice::Square, jitter, seed);
+    for z in 0..size {
+        for y in 0..size {
+            for x in 0..size {
+                let p = [x as f32 * freq, y as f32 * freq, z as f32 * freq];
+                let (d2, value, second) = nearest(&points, p);
+                if second - d2 > 1e-3 {
+                    assert_eq!(values[(z * size + y) * size + x], value);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_feature_points_region() {
+    let points = feature_points_2d([1.0, -3.0], [6.0, 2.0], CellLattice::Square, 0.3, 0);
+    // One point per unit square
+    assert!(points.len() >= 16 && points.len() <= 36, "{}", points.len());
+    for p in &points {
+        assert!(p.position[0] >= 1.0 && p.position[0] < 6.0);
+        assert!(p.position[1] >= -3.0 && p.position[1] < 2.0);
+    }
+    let with_border = feature_points_2d([0.0, -4.0], [7.0, 3.0], CellLattice::Square, 0.3, 0);
+    for p in &points {
+        assert!(with_border.contains(p));
+    }
+}
+
+#[test]
+fn test_feature_points_settings() {
+    let mut settings = NoiseBuilder::cellular_2d_offset(10.0, 64, 20.0, 32);
+    settings.with_freq(0.05).with_jitter(0.4).with_seed(3);
+    let distances = settings.generate().0;
+    let points = settings.feature_points_2d();
+    assert!(!points.is_empty());
+    for p in &points {
+        let [x, y] = p.position;
+        assert!((0.0..64.0).contains(&x) && (0.0..32.0).contains(&y));
+        // The closest sample is less than 0.75 samples away; distances are in noise space
+        let i = y.round().min(31.0) as usize * 64 + x.round().min(63.0) as usize;
+        assert!(distances[i].sqrt() < 0.05 * 0.75, "{}", distances[i]);
+    }
+}
+
+#[test]
+fn test_feature_points_settings_3d() {
+    let mut settings = NoiseBuilder::cellular_3d_offset(-6.0, 10, 3.0, 9, 20.0, 8);
+    settings
+        .with_freq(0.3)
+        .with_jitter(0.4)
+        .with_search_radius(2)
+        .with_return_type(CellReturnType::CellValue)
+        .with_seed(7);
+    let values = settings.generate().0;
+    let points = settings.feature_points_3d().unwrap();
+    assert!(!points.is_empty());
+    // Only points inside the volume are listed, so skip samples closer to its edge than to
+    // their nearest point, and ones where two points are about as close
+    let size = [10.0, 9.0, 8.0];
+    for z in 0..8 {
+        for y in 0..9 {
+            for x in 0..10 {
+                let p = [x as f32, y as f32, z as f32];
+                let (d2, value, second) = nearest(&points, p);
+                let edge = (0..3)
+                    .map(|i| p[i].min(size[i] - p[i]))
+                    .fold(f32::MAX, f32::min);
+                if d2.sqrt() < edge && second - d2 > 1e-2 {
+                    assert_eq!(values[(z * 9 + y) * 10 + x], value);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_feature_points_settings_3d_radius_1() {
+    assert_eq!(
+        NoiseBuilder::cellular_3d(4, 4, 4).feature_points_3d(),
+        Err(NoFeaturePoints)
+    );
+}
+
+#[test]
+fn test_feature_points_settings_zero_frequency() {
+    assert!(NoiseBuilder::cellular_2d(8, 8)
+        .with_freq_2d(0.2, 0.0)
+        .feature_points_2d()
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "lattice not supported")]
+fn test_feature_points_unsupported_lattice() {
+    feature_points_2d([0.0; 2], [1.0; 2], CellLattice::FaceCenteredCubic, 0.3, 0);
+}
+
+#[test]
+fn test_poisson_disk_2d() {
+    let (width, height, r) = (50.0, 30.0, 2.0);
+    let points = poisson_disk_2d(width, height, r, 17);
+    assert_eq!(points, poisson_disk_2d(width, height, r, 17));
+    assert_ne!(points, poisson_disk_2d(width, height, r, 18));
+    for (i, a) in points.iter().enumerate() {
+        assert!(a[0] >= 0.0 && a[0] < width && a[1] >= 0.0 && a[1] < height);
+        for b in &points[i + 1..] {
+            let d = ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt();
+            assert!(d >= r - 1e-4, "{}", d);
+        }
+    }
+    // Maximal: no gap is left that could hold another disk
+    for y in 0..30 {
+        for x in 0..50 {
+            let p = [x as f32 + 0.5, y as f32 + 0.5];
+            let covered = points
+                .iter()
+                .any(|q| ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt() < 2.0 * r);
+            assert!(covered, "{:?}", p);
+        }
+    }
+}
+
+#[test]
+fn test_poisson_disk_3d() {
+    let r = 1.5;
+    let points = poisson_disk_3d(10.0, 8.0, 6.0, r, 5);
+    assert!(points.len() > 50, "{}", points.len());
+    for (i, a) in points.iter().enumerate() {
+        assert!(a
+            .iter()
+            .zip([10.0, 8.0, 6.0])
+            .all(|(v, s)| *v >= 0.0 && *v < s));
+        for b in &points[i + 1..] {
+            let d = (0..3).map(|k| (a[k] - b[k]).powi(2)).sum::<f32>().sqrt();
+            assert!(d >= r - 1e-4, "{}", d);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid min_distance")]
+fn test_poisson_disk_zero_distance() {
+    poisson_disk_2d(10.0, 10.0, 0.0, 0);
+}
+
+#[test]
+#[should_panic(expected = "invalid min_distance")]
+fn test_poisson_disk_infinite_distance() {
+    poisson_disk_2d(10.0, 10.0, f32::INFINITY, 0);
+}
+
+#[test]
+#[should_panic(expected = "min_distance too small for the size")]
+fn test_poisson_disk_too_many_cells() {
+    poisson_disk_3d(1e6, 1e6, 1e6, 1e-3, 0);
+}
@@ -0,0 +1,165 @@
+use simdeez::prelude::*;
+use simdeez::scalar::Scalar;
+use simdnoise::intrinsics::portable::{self, Portable};
+use simdnoise::intrinsics::scalar;
+use simdnoise::{
+    Cell2ReturnType, CellDistanceFunction, CellReturnType, NoiseBuilder, NoiseType, Settings,
+    SimplexSettings,
+};
+
+macro_rules! assert_delta {
+    ($x:expr, $y:expr, $d:expr) => {
+        assert!(($x - $y).abs() < $d, "{} != {}", $x, $y);
+    };
+}
+
+// The cellular frequencies avoid coordinates halfway between two integers, which the scalar
+// engine rounds away from zero and the others to even
+fn noise_types() -> Vec<(NoiseType, usize)> {
+    vec![
+        (NoiseBuilder::gradient_1d(100).with_freq(0.13).wrap(), 1),
+        (NoiseBuilder::fbm_2d(37, 23).with_freq(0.07).wrap(), 2),
+        (NoiseBuilder::ridge_3d(17, 13, 11).with_octaves(4).wrap(), 3),
+        (
+            NoiseBuilder::turbulence_4d(9, 7, 5, 3)
+                .with_gain(1.5)
+                .wrap(),
+            4,
+        ),
+        (
+            NoiseBuilder::cellular_2d(37, 23)
+                .with_freq(0.093)
+                .with_distance_function(CellDistanceFunction::Natural)
+                .with_return_type(CellReturnType::CellValue)
+                .wrap(),
+            2,
+        ),
+        (
+            NoiseBuilder::cellular_3d(17, 13, 11)
+                .with_freq(0.093)
+                .wrap(),
+            3,
+        ),
+        (
+            NoiseBuilder::cellular2_2d(37, 23)
+                .with_freq(0.093)
+                .with_return_type(Cell2ReturnType::Distance2Div)
+                .wrap(),
+            2,
+        ),
+        (NoiseBuilder::white_4d(9, 7, 5, 3).wrap(), 4),
+    ]
+}
+
+#[test]
+fn test_portable_matches_scalar() {
+    for (noise_type, dim) in noise_types() {
+        let (expected, noise) = unsafe {
+            match dim {
+                1 => (
+                    scalar::get_1d_noise::<Scalar>(&noise_type),
+                    portable::get_1d_noise::<Portable>(&noise_type),
+                ),
+                2 => (
+                    scalar::get_2d_noise::<Scalar>(&noise_type),
+                    portable::get_2d_noise::<Portable>(&noise_type),
+                ),
+                3 => (
+                    scalar::get_3d_noise::<Scalar>(&noise_type),
+                    portable::get_3d_noise::<Portable>(&noise_type),
+                ),
+                _ => (
+                    scalar::get_4d_noise::<Scalar>(&noise_type),
+                    portable::get_4d_noise::<Portable>(&noise_type),
+                ),
+            }
+        };
+        assert_eq!(expected.0.len(), noise.0.len());
+        for (a, b) in expected.0.iter().zip(noise.0.iter()) {
+            assert_delta!(a, b, 0.0001);
+        }
+        assert_delta!(expected.1, noise.1, 0.0001);
+        assert_delta!(expected.2, noise.2, 0.0001);
+    }
+}
+
+#[test]
+fn test_portable_matches_scalar_64() {
+    let noise_type = NoiseBuilder::fbm_2d(37, 23).with_freq(0.07).wrap();
+    let (expected, noise) = unsafe {
+        (
+            scalar::get_2d_noise_64::<Scalar>(&noise_type),
+            portable::get_2d_noise_64::<Portable>(&noise_type),
+        )
+    };
+    for (a, b) in expected.0.iter().zip(noise.0.iter()) {
+        assert_delta!(a, b, 0.0000001);
+    }
+}
+
+#[test]
+fn test_portable_functions() {
+    let x = portable::F32x8::set1(5.0);
+    let y = portable::F32x8::set1(10.0);
+    let noise = unsafe { portable::simplex_2d::<Portable>(x, y, 42) };
+    let expected = unsafe { scalar::simplex_2d::<Scalar>(5.0, 10.0, 42) };
+    for i in 0..portable::F32x8::WIDTH {
+        assert_delta!(noise[i], expected, 0.0001);
+    }
+
+    let lacunarity = portable::F64x4::set1(0.5);
+    let gain = portable::F64x4::set1(2.0);
+    let noise = unsafe {
+        portable::ridge_2d_f64::<Portable>(
+            portable::F64x4::set1(5.0),
+            portable::F64x4::set1(10.0),
+            lacunarity,
+            gain,
+            3,
+            42,
+        )
+    };
+    let expected = unsafe { scalar::ridge_2d_f64::<Scalar>(5.0, 10.0, 0.5, 2.0, 3, 42) };
+    for i in 0..portable::F64x4::WIDTH {
+        assert_delta!(noise[i], expected, 0.0000001);
+    }
+}
+
+#[test]
+#[cfg(feature = "deterministic")]
+fn test_portable_deterministic() {
+    for (i, (noise_type, dim)) in noise_types().into_iter().enumerate() {
+        let (expected, noise) = unsafe {
+            match dim {
+                1 => (
+                    scalar::get_1d_noise::<Scalar>(&noise_type).0,
+                    portable::get_1d_noise::<Portable>(&noise_type).0,
+                ),
+                2 => (
+                    scalar::get_2d_noise::<Scalar>(&noise_type).0,
+                    portable::get_2d_noise::<Portable>(&noise_type).0,
+                ),
+                3 => (
+                    scalar::get_3d_noise::<Scalar>(&noise_type).0,
+                    portable::get_3d_noise::<Portable>(&noise_type).0,
+                ),
+                _ => (
+                    scalar::get_4d_noise::<Scalar>(&noise_type).0,
+                    portable::get_4d_noise::<Portable>(&noise_type).0,
+                ),
+            }
+        };
+        let bits = |noise: &[f32]| noise.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        assert!(bits(&expected) == bits(&noise), "noise type {}", i);
+    }
+
+    let noise_type = NoiseBuilder::ridge_2d(37, 23).with_octaves(4).wrap();
+    let (expected, noise) = unsafe {
+        (
+            scalar::get_2d_noise_64::<Scalar>(&noise_type).0,
+            portable::get_2d_noise_64::<Portable>(&noise_type).0,
+        )
+    };
+    let bits = |noise: &[f64]| noise.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+    assert!(bits(&expected) == bits(&noise));
+}
@@ -0,0 +1,122 @@
+use simdnoise::{
+    AlgorithmVersion, Cell2ReturnType, CellDistanceFunction, CellLattice, CellReturnType,
+    NoiseBuilder, NoiseNode, NoiseType, PresetError, Settings, SimplexSettings,
+};
+
+fn noise_types() -> Vec<NoiseType> {
+    vec![
+        NoiseBuilder::fbm_3d_offset(1.5, 8, -2.0, 8, 3.0, 8)
+            .with_freq_3d(0.1, 0.2, 0.3)
+            .with_octaves(4)
+            .with_gain(1.5)
+            .with_lacunarity(0.4)
+            .with_seed(7)
+            .with_version(AlgorithmVersion::V1)
+            .wrap(),
+        NoiseBuilder::ridge_2d(16, 8).with_seed(-3).wrap(),
+        NoiseBuilder::turbulence_1d(32).with_freq(0.3).wrap(),
+        NoiseBuilder::gradient_4d(4, 5, 6, 7)
+            .with_freq(0.123456)
+            .wrap(),
+        NoiseBuilder::cellular_2d(16, 16)
+            .with_distance_function(CellDistanceFunction::Manhattan)
+            .with_return_type(CellReturnType::CellValue)
+            .with_jitter(0.6)
+            .with_search_radius(2)
+            .with_lattice(CellLattice::Hexagonal)
+            .wrap(),
+        NoiseBuilder::cellular2_3d(8, 8, 8)
+            .with_distance_function(CellDistanceFunction::Natural)
+            .with_return_type(Cell2ReturnType::Distance2Sub)
+            .with_index0(1)
+            .with_index1(3)
+            .wrap(),
+        NoiseBuilder::white_2d(16, 16).with_seed(11).wrap(),
+    ]
+}
+
+fn preset_error(preset: &str) -> PresetError {
+    NoiseType::from_preset(preset).err().unwrap()
+}
+
+#[test]
+fn test_preset_round_trip() {
+    for noise_type in noise_types() {
+        let preset = noise_type.to_preset();
+        let back = NoiseType::from_preset(&preset).unwrap();
+        assert_eq!(back.to_preset(), preset);
+        assert_eq!(
+            NoiseNode::from(back).generate().0,
+            NoiseNode::from(noise_type).generate().0
+        );
+    }
+}
+
+#[test]
+fn test_preset_parse() {
+    let preset = "
+        # Rolling hills
+        type = fbm
+        size = 64 32   # width and height
+        freq = 0.05
+        octaves = 5
+        seed = 42
+    ";
+    let expected = NoiseBuilder::fbm_2d(64, 32)
+        .with_freq(0.05)
+        .with_octaves(5)
+        .with_seed(42)
+        .generate()
+        .0;
+    let noise_type = NoiseType::from_preset(preset).unwrap();
+    assert_eq!(NoiseNode::from(noise_type).generate().0, expected);
+}
+
+#[test]
+fn test_preset_cellular() {
+    let preset = "type = cellular\nsize = 24 24 24\nfreq = 0.1 0.2 0.3\nlattice = body_centered_cubic\nreturn = cell_value\n";
+    match NoiseType::from_preset(preset).unwrap() {
+        NoiseType::Cellular(s) => {
+            assert!(matches!(s.lattice, CellLattice::BodyCenteredCubic));
+            assert!(matches!(s.return_type, CellReturnType::CellValue));
+            assert_eq!((s.freq_x, s.freq_y, s.freq_z), (0.1, 0.2, 0.3));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_preset_errors() {
+    assert_eq!(preset_error("size = 4 4\n").message, "missing key `type`");
+    assert_eq!(preset_error("type = fbm\n").message, "missing key `size`");
+
+    let error = preset_error("type = perlin\nsize = 4 4\n");
+    assert_eq!(error.line, Some(1));
+    assert_eq!(error.to_string(), "line 1: unknown noise type `perlin`");
+
+    let error = preset_error("type = gradient\nsize = 4 4\noctaves = 3\n");
+    assert_eq!(error.line, Some(3));
+    assert!(error.message.contains("not used by gradient"));
+
+    let error = preset_error("type = fbm\nsize = 4 4\nfreq = 0.1 0.2 0.3\n");
+    assert_eq!(error.line, Some(3));
+
+    let error = preset_error("type = fbm\nsize = 4 four\n");
+    assert_eq!(error.message, "invalid value for `size`");
+
+    let error = preset_error("type = fbm\nsize = 4 4\nseed = 1\nseed = 2\n");
+    assert_eq!(error.line, Some(4));
+
+    let error = preset_error("type = fbm\nsize = 4 4\noctaves\n");
+    assert_eq!(error.message, "expected `key = value`");
+
+    let error = preset_error("type = cellular\nsize = 4 4 4 4\n");
+    assert_eq!(error.line, Some(2));
+}
+
+#[test]
+fn test_preset_validation() {
+    let error = preset_error("type = cellular\nsize = 4 4\nsearch_radius = 0\n");
+    assert_eq!(error.line, None);
+    assert!(error.message.contains("invalid search radius"));
+}
@@ -0,0 +1,99 @@
+use simdnoise::{NoiseBuilder, NoiseNode, Settings, SimplexSettings};
+
+// Every quantized value is the scaled float rounded to the nearest integer.
+fn assert_rounded<Q: Copy + Into<f32>>(quantized: &[Q], scaled: &[f32]) {
+    assert_eq!(quantized.len(), scaled.len());
+    for (q, v) in quantized.iter().zip(scaled) {
+        let q: f32 = (*q).into();
+        assert!((q - v).abs() <= 0.5 + 1e-3, "{} is not {} rounded", q, v);
+    }
+}
+
+#[test]
+fn test_u8_matches_scaled() {
+    // Widths that leave a remainder on every instruction set
+    let noise = NoiseBuilder::fbm_1d(37);
+    assert_rounded(
+        &noise.generate_u8(0, 255),
+        &noise.generate_scaled(0.0, 255.0),
+    );
+    let noise = *NoiseBuilder::ridge_2d(37, 11).with_octaves(4);
+    assert_rounded(
+        &noise.generate_u8(10, 200),
+        &noise.generate_scaled(10.0, 200.0),
+    );
+    let noise = NoiseBuilder::cellular_3d(13, 9, 5);
+    assert_rounded(
+        &noise.generate_u8(0, 255),
+        &noise.generate_scaled(0.0, 255.0),
+    );
+    let noise = NoiseBuilder::gradient_4d(9, 5, 3, 2);
+    assert_rounded(
+        &noise.generate_u8(0, 255),
+        &noise.generate_scaled(0.0, 255.0),
+    );
+}
+
+#[test]
+fn test_u16_matches_scaled() {
+    let noise = *NoiseBuilder::turbulence_3d(21, 17, 6).with_seed(5);
+    assert_rounded(
+        &noise.generate_u16(0, 65535),
+        &noise.generate_scaled(0.0, 65535.0),
+    );
+    let noise = NoiseBuilder::white_2d(33, 7);
+    assert_rounded(
+        &noise.generate_u16(1000, 2000),
+        &noise.generate_scaled(1000.0, 2000.0),
+    );
+}
+
+#[test]
+fn test_i16_full_range() {
+    let noise = *NoiseBuilder::fbm_2d(64, 48).with_seed(9);
+    let quantized = noise.generate_i16(i16::MIN, i16::MAX);
+    assert_rounded(
+        &quantized,
+        &noise.generate_scaled(i16::MIN as f32, i16::MAX as f32),
+    );
+    // The extremes of the noise land on the extremes of the type
+    assert_eq!(*quantized.iter().min().unwrap(), i16::MIN);
+    assert_eq!(*quantized.iter().max().unwrap(), i16::MAX);
+}
+
+#[test]
+fn test_inverted_range() {
+    let noise = NoiseBuilder::gradient_2d(19, 19);
+    let up = noise.generate_u8(0, 255);
+    let down = noise.generate_u8(255, 0);
+    for (u, d) in up.iter().zip(&down) {
+        assert!((*u as i32 + *d as i32 - 255).abs() <= 1);
+    }
+}
+
+#[test]
+fn test_node() {
+    let node = NoiseNode::from(NoiseBuilder::fbm_3d(15, 7, 3).wrap())
+        .blend(
+            NoiseNode::from(NoiseBuilder::cellular_3d(15, 7, 3).wrap()),
+            NoiseNode::Constant(0.25),
+        )
+        .terrace(vec![-1.0, 0.0, 1.0], false);
+    assert_rounded(&node.generate_u8(0, 255), &node.generate_scaled(0.0, 255.0));
+    assert_rounded(
+        &node.generate_u16(0, 4095),
+        &node.generate_scaled(0.0, 4095.0),
+    );
+    assert_rounded(
+        &node.generate_i16(-100, 100),
+        &node.generate_scaled(-100.0, 100.0),
+    );
+}
+
+#[test]
+fn test_flat_block() {
+    assert_eq!(NoiseBuilder::fbm_1d(1).generate_u8(20, 200), vec![20]);
+    let flat = *NoiseBuilder::gradient_2d(9, 5).with_freq(0.0);
+    assert_eq!(flat.generate_u16(7, 900), vec![7; 45]);
+    assert_eq!(flat.generate_i16(-30, 30), vec![-30; 45]);
+}
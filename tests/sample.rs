@@ -0,0 +1,152 @@
+use simdeez::prelude::*;
+use simdeez::scalar::Scalar;
+use simdnoise::noise::simplex_32::{simplex_2d, simplex_3d};
+use simdnoise::{
+    get_sample_noise_generic, AlgorithmVersion, NoiseBuilder, NoiseDimensions, Sample, Settings,
+};
+
+/// Records the sample positions it is asked for.
+struct Position {
+    dim: NoiseDimensions,
+    frequency: [f32; 4],
+}
+
+impl Sample for Position {
+    fn dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+
+    fn frequency(&self) -> [f32; 4] {
+        self.frequency
+    }
+
+    fn sample_3d<S: Simd>(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        x + y * S::Vf32::set1(100.0) + z * S::Vf32::set1(10000.0)
+    }
+}
+
+/// Ridges built on the crate's own simplex kernels.
+struct Ridges {
+    dim: NoiseDimensions,
+    freq: f32,
+}
+
+impl Sample for Ridges {
+    fn dimensions(&self) -> NoiseDimensions {
+        self.dim
+    }
+
+    fn frequency(&self) -> [f32; 4] {
+        [self.freq; 4]
+    }
+
+    fn sample_2d<S: Simd>(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        S::Vf32::set1(1.0) - simplex_2d::<S>(x, y, self.dim.seed).abs()
+    }
+
+    fn sample_3d<S: Simd>(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        S::Vf32::set1(1.0) - simplex_3d::<S>(x, y, z, self.dim.seed).abs()
+    }
+}
+
+fn dimensions(dim: usize, size: usize) -> NoiseDimensions {
+    let mut d = NoiseDimensions::default(dim);
+    d.width = size;
+    d.height = size;
+    d.depth = size;
+    d.seed = 12;
+    d
+}
+
+#[test]
+fn test_sample_positions() {
+    let mut dim = dimensions(3, 7);
+    dim.x = 3.0;
+    dim.y = -2.0;
+    dim.z = 1.0;
+    let position = Position {
+        dim,
+        frequency: [1.0, 0.5, 0.25, 1.0],
+    };
+    let (noise, min, max) = position.generate();
+    assert_eq!(noise.len(), 7 * 7 * 7);
+    let mut i = 0;
+    for z in 0..7 {
+        for y in 0..7 {
+            for x in 0..7 {
+                let expected = (x as f32 + 3.0)
+                    + (y as f32 - 2.0) * 0.5 * 100.0
+                    + (z as f32 + 1.0) * 0.25 * 10000.0;
+                assert_eq!(noise[i], expected);
+                i += 1;
+            }
+        }
+    }
+    assert_eq!(min, noise.iter().cloned().fold(f32::MAX, f32::min));
+    assert_eq!(max, noise.iter().cloned().fold(f32::MIN, f32::max));
+}
+
+#[test]
+fn test_sample_matches_builtin() {
+    let ridges = Ridges {
+        dim: dimensions(2, 45),
+        freq: 0.04,
+    };
+    let (noise, _min, _max) = ridges.generate();
+    let (gradient, _min, _max) = NoiseBuilder::gradient_2d(45, 45)
+        .with_freq(0.04)
+        .with_seed(12)
+        .generate();
+    for (n, g) in noise.iter().zip(gradient.iter()) {
+        assert!((n - (1.0 - g.abs())).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_sample_generic_backend() {
+    let ridges = Ridges {
+        dim: dimensions(3, 9),
+        freq: 0.1,
+    };
+    let (scalar, _min, _max) = unsafe { get_sample_noise_generic::<Scalar, _>(&ridges) };
+    let (dispatched, _min, _max) = ridges.generate();
+    for (s, d) in scalar.iter().zip(dispatched.iter()) {
+        assert!((s - d).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_sample_scaled() {
+    let ridges = Ridges {
+        dim: dimensions(2, 30),
+        freq: 0.1,
+    };
+    let noise = ridges.generate_scaled(-2.0, 3.0);
+    let min = noise.iter().cloned().fold(f32::MAX, f32::min);
+    let max = noise.iter().cloned().fold(f32::MIN, f32::max);
+    assert!((min + 2.0).abs() < 1e-5 && (max - 3.0).abs() < 1e-5);
+}
+
+#[test]
+#[should_panic(expected = "4d sampling not implemented")]
+fn test_sample_missing_dimension() {
+    Ridges {
+        dim: dimensions(4, 4),
+        freq: 0.1,
+    }
+    .generate();
+}
+
+#[test]
+fn test_sample_far() {
+    // Custom noise keeps the f32 positions far from the origin in every version
+    let far = |version| {
+        let mut dim = dimensions(2, 8);
+        dim.x = 10_000_000.0;
+        dim.version = version;
+        Ridges { dim, freq: 0.1 }.generate().0
+    };
+    let v3 = far(AlgorithmVersion::V3);
+    assert_eq!(v3.len(), 64);
+    assert_eq!(v3, far(AlgorithmVersion::V2));
+}
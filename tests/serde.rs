@@ -0,0 +1,118 @@
+#![cfg(feature = "serde")]
+
+use simdnoise::{
+    AlgorithmVersion, Cell2ReturnType, CellDistanceFunction, CellLattice, CellReturnType,
+    FbmSettings, NoiseBuilder, NoiseDimensions, NoiseNode, NoiseType, Settings, SimplexSettings,
+};
+
+fn noise_types() -> Vec<NoiseType> {
+    vec![
+        NoiseBuilder::fbm_3d_offset(1.0, 8, 2.0, 8, 3.0, 8)
+            .with_freq_3d(0.1, 0.2, 0.3)
+            .with_octaves(4)
+            .with_gain(1.5)
+            .with_lacunarity(0.4)
+            .with_seed(7)
+            .with_version(AlgorithmVersion::V1)
+            .wrap(),
+        NoiseBuilder::ridge_2d(16, 8).with_seed(-3).wrap(),
+        NoiseBuilder::turbulence_1d(32).with_freq(0.3).wrap(),
+        NoiseBuilder::gradient_4d(4, 4, 4, 4).with_freq(0.2).wrap(),
+        NoiseBuilder::cellular_2d(16, 16)
+            .with_distance_function(CellDistanceFunction::Manhattan)
+            .with_return_type(CellReturnType::CellValue)
+            .with_jitter(0.6)
+            .with_search_radius(2)
+            .with_lattice(CellLattice::Hexagonal)
+            .wrap(),
+        NoiseBuilder::cellular2_3d(8, 8, 8)
+            .with_distance_function(CellDistanceFunction::Natural)
+            .with_return_type(Cell2ReturnType::Distance2Sub)
+            .with_index0(1)
+            .with_index1(3)
+            .wrap(),
+        NoiseBuilder::white_2d(16, 16).with_seed(11).wrap(),
+    ]
+}
+
+#[test]
+fn test_serde_round_trip() {
+    for noise_type in noise_types() {
+        let json = serde_json::to_string(&noise_type).unwrap();
+        let back: NoiseType = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert_eq!(
+            NoiseNode::from(back).generate().0,
+            NoiseNode::from(noise_type).generate().0
+        );
+    }
+}
+
+#[test]
+fn test_serde_settings() {
+    let settings = *NoiseBuilder::fbm_2d(10, 10).with_octaves(6);
+    let json = serde_json::to_value(settings).unwrap();
+    assert_eq!(json["octaves"], 6);
+    assert_eq!(json["dim"]["width"], 10);
+    let back: FbmSettings = serde_json::from_value(json).unwrap();
+    assert_eq!(back.octaves, 6);
+    assert_eq!(back.generate().0, settings.generate().0);
+}
+
+#[test]
+fn test_serde_enums() {
+    let json = serde_json::to_string(&CellLattice::BodyCenteredCubic).unwrap();
+    assert_eq!(json, "\"BodyCenteredCubic\"");
+    let back: Cell2ReturnType = serde_json::from_str("\"Distance2Div\"").unwrap();
+    assert!(matches!(back, Cell2ReturnType::Distance2Div));
+}
+
+#[test]
+fn test_serde_untagged_version() {
+    let mut json = serde_json::to_value(NoiseDimensions::default(3)).unwrap();
+    assert_eq!(json["version"], "V3");
+    json.as_object_mut().unwrap().remove("version");
+    let back: NoiseDimensions = serde_json::from_value(json).unwrap();
+    assert_eq!(back.version, AlgorithmVersion::LATEST);
+}
+
+#[test]
+fn test_serde_invalid_dimension() {
+    let mut json = serde_json::to_value(NoiseDimensions::default(2)).unwrap();
+    json["dim"] = 5.into();
+    let error = serde_json::from_value::<NoiseDimensions>(json)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("dimension invalid"), "{}", error);
+}
+
+#[test]
+fn test_serde_invalid_settings() {
+    let mut json = serde_json::to_value(NoiseBuilder::cellular_2d(8, 8).wrap()).unwrap();
+    json["Cellular"]["search_radius"] = 0.into();
+    let error = serde_json::from_value::<NoiseType>(json).err().unwrap();
+    assert!(
+        error.to_string().contains("invalid search radius"),
+        "{}",
+        error
+    );
+
+    let mut json = serde_json::to_value(NoiseBuilder::cellular_2d(8, 8).wrap()).unwrap();
+    json["Cellular"]["lattice"] = "FaceCenteredCubic".into();
+    let error = serde_json::from_value::<NoiseType>(json).err().unwrap();
+    assert!(
+        error.to_string().contains("lattice not supported"),
+        "{}",
+        error
+    );
+
+    let mut json = serde_json::to_value(NoiseBuilder::cellular2_2d(8, 8).wrap()).unwrap();
+    json["Cellular2"]["index0"] = 2.into();
+    json["Cellular2"]["index1"] = 1.into();
+    let error = serde_json::from_value::<NoiseType>(json).err().unwrap();
+    assert!(
+        error.to_string().contains("invalid index settings"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,89 @@
+use simdnoise::intrinsics::scalar;
+use simdnoise::{NoiseBuilder, Settings};
+
+#[test]
+fn test_white_range() {
+    let (noise, min, max) = NoiseBuilder::white_2d(64, 64).with_seed(3).generate();
+    assert!(min >= -1.0 && max <= 1.0);
+    assert!(noise.iter().all(|v| (-1.0..=1.0).contains(v)));
+}
+
+#[test]
+fn test_white_all_dimensions() {
+    assert_eq!(NoiseBuilder::white_1d(100).generate().0.len(), 100);
+    assert_eq!(NoiseBuilder::white_2d(10, 10).generate().0.len(), 100);
+    assert_eq!(NoiseBuilder::white_3d(10, 5, 2).generate().0.len(), 100);
+    assert_eq!(NoiseBuilder::white_4d(5, 5, 2, 2).generate().0.len(), 100);
+}
+
+#[test]
+fn test_white_seed() {
+    let a = NoiseBuilder::white_3d(16, 16, 4).with_seed(1).generate().0;
+    let b = NoiseBuilder::white_3d(16, 16, 4).with_seed(1).generate().0;
+    let c = NoiseBuilder::white_3d(16, 16, 4).with_seed(2).generate().0;
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_white_uncorrelated() {
+    let (noise, _min, _max) = NoiseBuilder::white_1d(4096).with_seed(1337).generate();
+    let mean = noise.iter().sum::<f32>() / noise.len() as f32;
+    let lag1 = noise
+        .windows(2)
+        .map(|w| (w[0] - mean) * (w[1] - mean))
+        .sum::<f32>()
+        / (noise.len() - 1) as f32;
+    let variance = noise.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / noise.len() as f32;
+    assert!(mean.abs() < 0.05);
+    assert!((lag1 / variance).abs() < 0.05);
+}
+
+#[test]
+fn test_white_cells() {
+    // At a frequency of 0.25 every four neighbouring samples share one cell.
+    let (noise, _min, _max) = NoiseBuilder::white_1d(64).with_freq(0.25).generate();
+    for cell in noise.chunks(4) {
+        assert!(cell.iter().all(|v| *v == cell[0]));
+    }
+    assert!(noise
+        .chunks(4)
+        .zip(noise.chunks(4).skip(1))
+        .any(|(a, b)| a[0] != b[0]));
+}
+
+#[test]
+fn test_white_offset() {
+    let (full, _min, _max) = NoiseBuilder::white_2d(32, 8).with_seed(5).generate();
+    let (part, _min, _max) = NoiseBuilder::white_2d_offset(16.0, 16, 0.0, 8)
+        .with_seed(5)
+        .generate();
+    for y in 0..8 {
+        assert_eq!(&full[y * 32 + 16..y * 32 + 32], &part[y * 16..y * 16 + 16]);
+    }
+}
+
+#[test]
+fn test_white_f64_matches_f32() {
+    let noise_type = NoiseBuilder::white_2d(32, 32).with_seed(9).wrap();
+    let (noise_32, _min, _max) =
+        unsafe { scalar::get_2d_noise::<simdeez::scalar::Scalar>(&noise_type) };
+    let (noise_64, _min, _max) =
+        unsafe { scalar::get_2d_noise_64::<simdeez::scalar::Scalar>(&noise_type) };
+    for (a, b) in noise_32.iter().zip(noise_64.iter()) {
+        assert!((*a as f64 - b).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_white_single_value() {
+    let (noise, _min, _max) = NoiseBuilder::white_2d(8, 8).with_seed(11).generate();
+    let value = unsafe { scalar::white_2d::<simdeez::scalar::Scalar>(3.0, 5.0, 11) };
+    assert_eq!(noise[5 * 8 + 3], value);
+}
+
+#[test]
+#[should_panic(expected = "invalid frequency in white noise")]
+fn test_white_invalid_frequency() {
+    NoiseBuilder::white_2d(8, 8).with_freq(f32::NAN).generate();
+}